@@ -1,112 +1,513 @@
+use std::path::Path;
+
 use anyhow::{Context, Result, bail};
-use image::{Rgba, RgbaImage};
+use image::{DynamicImage, Rgba, RgbaImage};
+use rand::seq::SliceRandom;
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
+
+use crate::color::{lerp_color, lerp_color_linear, parse_hex_rgba};
+use crate::compose::resize_cover;
+use crate::config::{
+    AccentBeam, BackgroundConfig, BackgroundTemplate, BlurConfig, RadialConfig, VignetteConfig,
+};
+use crate::filters::gaussian_blur;
 
-use crate::color::{lerp_color, parse_hex_rgba};
-use crate::config::{BackgroundConfig, BackgroundTemplate};
+/// How template colors are mixed: [`lerp_color`] in sRGB, or [`lerp_color_linear`]
+type Blend = fn(Rgba<u8>, Rgba<u8>, f32) -> Rgba<u8>;
 
+/// Render `cfg` as a `width`x`height` canvas. The `blurred_screenshot` template needs a
+/// screenshot, so it only renders as part of [`render_scene`](crate::render_scene).
 pub fn render_background(cfg: &BackgroundConfig, width: u32, height: u32) -> Result<RgbaImage> {
+    render(cfg, width, height, None)
+}
+
+/// Like [`render_background`], with the scene's first screenshot available to the
+/// `blurred_screenshot` template
+pub fn render_scene_background(
+    cfg: &BackgroundConfig,
+    width: u32,
+    height: u32,
+    screenshot: &DynamicImage,
+) -> Result<RgbaImage> {
+    render(cfg, width, height, Some(screenshot))
+}
+
+fn render(
+    cfg: &BackgroundConfig,
+    width: u32,
+    height: u32,
+    screenshot: Option<&DynamicImage>,
+) -> Result<RgbaImage> {
     if width == 0 || height == 0 {
         bail!("invalid canvas size {}x{}", width, height);
     }
 
+    // Compose blends source-over, so everything drawn later keeps its own alpha
+    if cfg.transparent {
+        return Ok(RgbaImage::new(width, height));
+    }
+
+    // A background image replaces the generated template entirely
+    let mut image = match &cfg.image {
+        Some(path) => load_background_image(path, width, height)?,
+        None => render_template(cfg, width, height, screenshot)?,
+    };
+
+    if let Some(vignette) = &cfg.vignette {
+        apply_vignette(&mut image, vignette)?;
+    }
+
+    Ok(image)
+}
+
+fn load_background_image(path: &Path, width: u32, height: u32) -> Result<RgbaImage> {
+    let source = image::open(path)
+        .with_context(|| format!("failed to open background image {}", path.display()))?;
+    Ok(resize_cover(&source, width, height))
+}
+
+/// Cover-scale the screenshot to the canvas and blur it into a frosted backdrop
+fn blur_screenshot(
+    screenshot: Option<&DynamicImage>,
+    width: u32,
+    height: u32,
+    cfg: &BlurConfig,
+) -> Result<RgbaImage> {
+    let Some(screenshot) = screenshot else {
+        bail!("blurred_screenshot background needs a screenshot to blur");
+    };
+    Ok(gaussian_blur(
+        &resize_cover(screenshot, width, height),
+        cfg.sigma.max(0.0),
+    ))
+}
+
+fn render_template(
+    cfg: &BackgroundConfig,
+    width: u32,
+    height: u32,
+    screenshot: Option<&DynamicImage>,
+) -> Result<RgbaImage> {
     let palette = cfg
         .colors
         .iter()
         .map(|raw| parse_hex_rgba(raw).with_context(|| format!("invalid palette color '{}'", raw)))
         .collect::<Result<Vec<_>>>()?;
 
-    if palette.len() < 2 {
-        bail!("background needs at least 2 colors");
+    let min_colors = match cfg.template {
+        BackgroundTemplate::BlurredScreenshot => 0,
+        BackgroundTemplate::Solid => 1,
+        _ => 2,
+    };
+    if palette.len() < min_colors {
+        bail!(
+            "{:?} background needs at least {} color(s)",
+            cfg.template,
+            min_colors
+        );
+    }
+
+    if let Some(beam) = &cfg.stripes.accent_beam
+        && beam.color >= palette.len()
+    {
+        bail!(
+            "accent beam color index {} is out of range for {} background colors",
+            beam.color,
+            palette.len()
+        );
     }
 
-    let mut rng = ChaCha8Rng::seed_from_u64(cfg.seed);
+    let blend: Blend = if cfg.linear_blend {
+        lerp_color_linear
+    } else {
+        lerp_color
+    };
+    let seeds = TemplateSeeds::derive(cfg.seed, cfg.template);
+    let mut rng = ChaCha8Rng::seed_from_u64(seeds.layout);
     let image = match cfg.template {
-        BackgroundTemplate::Mesh => render_mesh(width, height, &palette, &mut rng, cfg.seed),
-        BackgroundTemplate::Stripes => render_stripes(width, height, &palette, &mut rng, cfg.seed),
+        BackgroundTemplate::Mesh => {
+            render_mesh(width, height, &palette, &mut rng, seeds.noise, cfg, blend)
+        }
+        BackgroundTemplate::Stripes => {
+            render_stripes(width, height, &palette, &mut rng, seeds.noise, cfg, blend)
+        }
+        BackgroundTemplate::Radial => render_radial(
+            width,
+            height,
+            &palette,
+            seeds.noise,
+            cfg.grain.max(0.0),
+            &cfg.radial,
+            blend,
+        ),
+        BackgroundTemplate::Solid => RgbaImage::from_pixel(width, height, palette[0]),
+        BackgroundTemplate::Linear => {
+            render_linear(width, height, palette[0], palette[1], cfg.angle, blend)
+        }
+        BackgroundTemplate::Dots => {
+            render_dots(width, height, &palette, &mut rng, seeds.noise, cfg, blend)
+        }
+        BackgroundTemplate::BlurredScreenshot => {
+            blur_screenshot(screenshot, width, height, &cfg.blur)?
+        }
     };
 
     Ok(image)
 }
 
+/// Independent sub-seeds derived from `BackgroundConfig::seed`.
+///
+/// `layout` seeds the RNG that picks colors and geometry (stripe size, drift, dot spacing);
+/// `noise` feeds the position-based grain. Keeping them apart means a template can draw more
+/// or fewer values from the RNG without shifting its grain pattern. The noise salts reproduce
+/// the per-template multipliers used before the split, so existing seeds render unchanged.
+struct TemplateSeeds {
+    layout: u64,
+    noise: u64,
+}
+
+impl TemplateSeeds {
+    fn derive(seed: u64, template: BackgroundTemplate) -> Self {
+        let noise_salt = match template {
+            BackgroundTemplate::Mesh
+            | BackgroundTemplate::Solid
+            | BackgroundTemplate::Linear
+            | BackgroundTemplate::BlurredScreenshot => 1,
+            BackgroundTemplate::Stripes => 13,
+            BackgroundTemplate::Radial => 29,
+            BackgroundTemplate::Dots => 41,
+        };
+        Self {
+            layout: seed,
+            noise: seed.wrapping_mul(noise_salt),
+        }
+    }
+}
+
+fn apply_vignette(image: &mut RgbaImage, cfg: &VignetteConfig) -> Result<()> {
+    let color = parse_hex_rgba(&cfg.color)
+        .with_context(|| format!("invalid vignette color '{}'", cfg.color))?;
+    let strength = cfg.strength.clamp(0.0, 1.0);
+    let radius = cfg.radius.clamp(0.0, 0.99);
+    let width_f = (image.width().max(1) - 1) as f32;
+    let height_f = (image.height().max(1) - 1) as f32;
+
+    for (x, y, pixel) in image.enumerate_pixels_mut() {
+        let dx = (x as f32 / width_f.max(1.0) - 0.5) * 2.0;
+        let dy = (y as f32 / height_f.max(1.0) - 0.5) * 2.0;
+        // Normalize so the canvas corners sit at distance 1.0
+        let dist = (dx * dx + dy * dy).sqrt() / std::f32::consts::SQRT_2;
+        let t = ((dist - radius) / (1.0 - radius)).clamp(0.0, 1.0);
+        let amount = strength * t * t * (3.0 - 2.0 * t);
+        if amount <= 0.0 {
+            continue;
+        }
+
+        for channel in 0..3 {
+            let value = pixel[channel] as f32 * (1.0 - amount) + color[channel] as f32 * amount;
+            pixel[channel] = value.round().clamp(0.0, 255.0) as u8;
+        }
+    }
+
+    Ok(())
+}
+
 fn render_mesh(
     width: u32,
     height: u32,
     palette: &[Rgba<u8>],
     rng: &mut ChaCha8Rng,
-    seed: u64,
+    noise_seed: u64,
+    cfg: &BackgroundConfig,
+    blend: Blend,
 ) -> RgbaImage {
-    let c0 = palette[rng.gen_range(0..palette.len())];
-    let c1 = palette[rng.gen_range(0..palette.len())];
-    let c2 = palette[rng.gen_range(0..palette.len())];
-    let c3 = palette[rng.gen_range(0..palette.len())];
+    let grain = cfg.grain.max(0.0);
+    let vignette_scale = cfg.mesh.vignette.max(0.0);
+    let deterministic = cfg.deterministic_layout;
+    let c0 = pick_color(palette, rng, 0, deterministic);
+    let c1 = pick_color(palette, rng, 1, deterministic);
+    let c2 = pick_color(palette, rng, 2, deterministic);
+    let c3 = pick_color(palette, rng, 3, deterministic);
+
+    // Up to four colors sit on the corners. Larger palettes are laid out on a two-row grid,
+    // the top row taking the extra color when the count is odd, so every color contributes.
+    let (top_row, bottom_row) = if palette.len() > MESH_CORNER_COLORS {
+        let mut ordered = palette.to_vec();
+        if !deterministic {
+            ordered.shuffle(rng);
+        }
+        let split = ordered.len().div_ceil(2);
+        let bottom = ordered.split_off(split);
+        (ordered, bottom)
+    } else {
+        (vec![c0, c1], vec![c2, c3])
+    };
 
-    let mut out = RgbaImage::new(width, height);
     let width_f = (width.max(1) - 1) as f32;
     let height_f = (height.max(1) - 1) as f32;
 
-    for y in 0..height {
+    render_rows(width, height, |y, row| {
         let fy = y as f32 / height_f.max(1.0);
-        for x in 0..width {
+        for (x, pixel) in (0..width).zip(row.chunks_exact_mut(4)) {
             let fx = x as f32 / width_f.max(1.0);
 
-            let top = lerp_color(c0, c1, fx);
-            let bottom = lerp_color(c2, c3, fx);
-            let mut mixed = lerp_color(top, bottom, fy);
+            let top = sample_row(&top_row, fx, blend);
+            let bottom = sample_row(&bottom_row, fx, blend);
+            let mut mixed = blend(top, bottom, fy);
 
             let dx = (fx - 0.5).abs() * 2.0;
             let dy = (fy - 0.5).abs() * 2.0;
-            let vignette = ((dx + dy) * 0.12).clamp(0.0, 0.16);
-            let grain = pseudo_noise(seed, x, y) * 10.0;
+            let vignette = ((dx + dy) * 0.12 * vignette_scale).clamp(0.0, 0.16 * vignette_scale);
+            let noise = pseudo_noise(noise_seed, x, y) * 10.0 * grain;
 
             for channel in 0..3 {
-                let base = mixed[channel] as f32 * (1.0 - vignette) + grain;
+                let base = mixed[channel] as f32 * (1.0 - vignette) + noise;
                 mixed[channel] = base.clamp(0.0, 255.0) as u8;
             }
 
-            out.put_pixel(x, y, mixed);
+            pixel.copy_from_slice(&mixed.0);
         }
-    }
+    })
+}
 
+/// Fill a fresh canvas one row at a time across threads. Every template pixel depends only on
+/// its position and values drawn from the RNG up front, so the output is identical to a
+/// sequential render.
+fn render_rows<F>(width: u32, height: u32, fill_row: F) -> RgbaImage
+where
+    F: Fn(u32, &mut [u8]) + Sync,
+{
+    let mut out = RgbaImage::new(width, height);
+    let row_len = width as usize * 4;
+    if row_len == 0 {
+        return out;
+    }
+    out.par_chunks_mut(row_len)
+        .enumerate()
+        .for_each(|(y, row)| fill_row(y as u32, row));
     out
 }
 
+const MESH_CORNER_COLORS: usize = 4;
+
+/// Interpolate piecewise across a row of evenly spaced colors, `t` running from 0 to 1.
+fn sample_row(colors: &[Rgba<u8>], t: f32, blend: Blend) -> Rgba<u8> {
+    let segments = (colors.len() - 1) as f32;
+    let scaled = t.clamp(0.0, 1.0) * segments;
+    let index = (scaled.floor() as usize).min(colors.len() - 2);
+    blend(colors[index], colors[index + 1], scaled - index as f32)
+}
+
+/// Pick a template color. The RNG is always advanced so the rest of the seeded layout
+/// (stripe size, drift) is the same whether or not the palette order is honored.
+fn pick_color(
+    palette: &[Rgba<u8>],
+    rng: &mut ChaCha8Rng,
+    slot: usize,
+    deterministic: bool,
+) -> Rgba<u8> {
+    let random = rng.gen_range(0..palette.len());
+    if deterministic {
+        palette[slot % palette.len()]
+    } else {
+        palette[random]
+    }
+}
+
 fn render_stripes(
     width: u32,
     height: u32,
     palette: &[Rgba<u8>],
     rng: &mut ChaCha8Rng,
-    seed: u64,
+    noise_seed: u64,
+    cfg: &BackgroundConfig,
+    blend: Blend,
 ) -> RgbaImage {
-    let c0 = palette[rng.gen_range(0..palette.len())];
-    let c1 = palette[rng.gen_range(0..palette.len())];
-    let c2 = palette[rng.gen_range(0..palette.len())];
-    let stripe_size = rng.gen_range(28..92) as i32;
-    let drift = rng.gen_range(18..72) as i32;
+    let grain = cfg.grain.max(0.0);
+    let stripes = &cfg.stripes;
+    let deterministic = cfg.deterministic_layout;
+    let c0 = pick_color(palette, rng, 0, deterministic);
+    let c1 = pick_color(palette, rng, 1, deterministic);
+    let c2 = pick_color(palette, rng, 2, deterministic);
+    let stripe_size: i32 = rng.gen_range(28..92);
+    let drift: i32 = rng.gen_range(18..72);
+
+    // No angle keeps the original integer 45° pattern, so existing seeds render identically
+    let direction = stripes
+        .angle_degrees
+        .map(|angle| angle.to_radians().sin_cos());
 
-    let mut out = RgbaImage::new(width, height);
     let height_f = (height.max(1) - 1) as f32;
 
-    for y in 0..height {
+    let mut out = render_rows(width, height, |y, row| {
         let fy = y as f32 / height_f.max(1.0);
-        let row_tint = lerp_color(c2, c0, fy);
-        for x in 0..width {
-            let line = ((x as i32 + y as i32 + drift) / stripe_size) % 2;
+        let row_tint = blend(c2, c0, fy);
+        for (x, pixel) in (0..width).zip(row.chunks_exact_mut(4)) {
+            let line = match direction {
+                Some((sin, cos)) => {
+                    let coord = x as f32 * sin + y as f32 * cos + drift as f32;
+                    (coord / stripe_size as f32).floor().rem_euclid(2.0) as i32
+                }
+                None => ((x as i32 + y as i32 + drift) / stripe_size) % 2,
+            };
             let base = if line == 0 { c0 } else { c1 };
-            let mut mixed = lerp_color(base, row_tint, 0.22);
-            let grain = pseudo_noise(seed.wrapping_mul(13), x, y) * 8.0;
+            let mut mixed = blend(base, row_tint, 0.22);
+            let noise = pseudo_noise(noise_seed, x, y) * 8.0 * grain;
             for channel in 0..3 {
-                let value = mixed[channel] as f32 + grain;
+                let value = mixed[channel] as f32 + noise;
                 mixed[channel] = value.clamp(0.0, 255.0) as u8;
             }
-            out.put_pixel(x, y, mixed);
+            pixel.copy_from_slice(&mixed.0);
+        }
+    });
+
+    if let Some(beam) = &stripes.accent_beam {
+        draw_accent_beam(&mut out, palette[beam.color], beam, blend);
+    }
+
+    out
+}
+
+const DOT_ALPHA: f32 = 0.28;
+
+fn render_dots(
+    width: u32,
+    height: u32,
+    palette: &[Rgba<u8>],
+    rng: &mut ChaCha8Rng,
+    noise_seed: u64,
+    cfg: &BackgroundConfig,
+    blend: Blend,
+) -> RgbaImage {
+    let grain = cfg.grain.max(0.0);
+    // The last color is reserved for the dots once there are enough colors for a separate base
+    let base = if palette.len() > 2 {
+        &palette[..palette.len() - 1]
+    } else {
+        palette
+    };
+    let start = base[0];
+    let end = base[base.len() - 1];
+    let dot_color = palette[palette.len() - 1];
+    let spacing = rng.gen_range(28..56) as f32;
+    let radius = spacing * rng.gen_range(0.08..0.16);
+    let offset_x = rng.gen_range(0.0..spacing);
+    let offset_y = rng.gen_range(0.0..spacing);
+
+    let mut out = render_linear(width, height, start, end, 60.0, blend);
+    for (x, y, pixel) in out.enumerate_pixels_mut() {
+        // Distance to the nearest grid center, feathered over one pixel for a smooth edge
+        let gx = (x as f32 + 0.5 - offset_x).rem_euclid(spacing) - spacing / 2.0;
+        let gy = (y as f32 + 0.5 - offset_y).rem_euclid(spacing) - spacing / 2.0;
+        let dist = (gx * gx + gy * gy).sqrt();
+        let coverage = (radius + 0.5 - dist).clamp(0.0, 1.0);
+        let mut mixed = blend(*pixel, dot_color, DOT_ALPHA * coverage);
+
+        let noise = pseudo_noise(noise_seed, x, y) * 6.0 * grain;
+        for channel in 0..3 {
+            let value = mixed[channel] as f32 + noise;
+            mixed[channel] = value.clamp(0.0, 255.0) as u8;
         }
+        *pixel = mixed;
     }
 
     out
 }
 
+fn render_linear(
+    width: u32,
+    height: u32,
+    start: Rgba<u8>,
+    end: Rgba<u8>,
+    angle: f32,
+    blend: Blend,
+) -> RgbaImage {
+    let (dir_y, dir_x) = angle.to_radians().sin_cos();
+    // Project the corners onto the gradient axis so both ends land exactly on the canvas edge
+    let corners = [
+        (0.0, 0.0),
+        (width as f32, 0.0),
+        (0.0, height as f32),
+        (width as f32, height as f32),
+    ];
+    let projections = corners.map(|(x, y)| x * dir_x + y * dir_y);
+    let min = projections.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = projections
+        .iter()
+        .copied()
+        .fold(f32::NEG_INFINITY, f32::max);
+    let span = (max - min).max(1.0);
+
+    let mut out = RgbaImage::new(width, height);
+    for (x, y, pixel) in out.enumerate_pixels_mut() {
+        let projected = (x as f32 + 0.5) * dir_x + (y as f32 + 0.5) * dir_y;
+        *pixel = blend(start, end, ((projected - min) / span).clamp(0.0, 1.0));
+    }
+
+    out
+}
+
+fn render_radial(
+    width: u32,
+    height: u32,
+    palette: &[Rgba<u8>],
+    noise_seed: u64,
+    grain: f32,
+    cfg: &RadialConfig,
+    blend: Blend,
+) -> RgbaImage {
+    let cx = cfg.center_x.clamp(0.0, 1.0) * width as f32;
+    let cy = cfg.center_y.clamp(0.0, 1.0) * height as f32;
+    // Normalize by the farthest corner so the last palette color lands exactly there
+    let max_dist = [
+        (0.0, 0.0),
+        (width as f32, 0.0),
+        (0.0, height as f32),
+        (width as f32, height as f32),
+    ]
+    .iter()
+    .map(|(x, y)| ((x - cx).powi(2) + (y - cy).powi(2)).sqrt())
+    .fold(1.0f32, f32::max);
+
+    let mut out = RgbaImage::new(width, height);
+    for (x, y, pixel) in out.enumerate_pixels_mut() {
+        let dist = ((x as f32 - cx).powi(2) + (y as f32 - cy).powi(2)).sqrt() / max_dist;
+        let mut mixed = sample_row(palette, dist, blend);
+
+        let noise = pseudo_noise(noise_seed, x, y) * 8.0 * grain;
+        for channel in 0..3 {
+            let value = mixed[channel] as f32 + noise;
+            mixed[channel] = value.clamp(0.0, 255.0) as u8;
+        }
+        *pixel = mixed;
+    }
+
+    out
+}
+
+fn draw_accent_beam(image: &mut RgbaImage, color: Rgba<u8>, beam: &AccentBeam, blend: Blend) {
+    let half_width = beam.width as f32 / 2.0;
+    if half_width <= 0.0 {
+        return;
+    }
+    let (sin, cos) = beam.angle.to_radians().sin_cos();
+    let cx = image.width() as f32 * beam.position;
+    let cy = image.height() as f32 / 2.0;
+    // Soften the outer fifth of the band so it doesn't read as a hard cut
+    let feather = (half_width * 0.2).max(1.0);
+
+    for (x, y, pixel) in image.enumerate_pixels_mut() {
+        let dist = ((x as f32 - cx) * sin - (y as f32 - cy) * cos).abs();
+        if dist >= half_width {
+            continue;
+        }
+        let edge = ((half_width - dist) / feather).clamp(0.0, 1.0);
+        *pixel = blend(*pixel, color, 0.85 * edge);
+    }
+}
+
 fn pseudo_noise(seed: u64, x: u32, y: u32) -> f32 {
     let mut v = seed
         ^ (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
@@ -119,3 +520,167 @@ fn pseudo_noise(seed: u64, x: u32, y: u32) -> f32 {
     let n = (v & 1023) as f32 / 1023.0;
     (n - 0.5) * 2.0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(template: BackgroundTemplate, colors: &[&str]) -> BackgroundConfig {
+        let mut cfg: BackgroundConfig = serde_yaml::from_str("{}").expect("default background");
+        cfg.template = template;
+        cfg.colors = colors.iter().map(|color| color.to_string()).collect();
+        cfg
+    }
+
+    #[test]
+    fn solid_accepts_a_single_color() {
+        let cfg = config(BackgroundTemplate::Solid, &["#336699"]);
+        let image = render_background(&cfg, 8, 8).expect("solid");
+        assert!(
+            image
+                .pixels()
+                .all(|pixel| *pixel == Rgba([51, 102, 153, 255]))
+        );
+
+        let cfg = config(BackgroundTemplate::Mesh, &["#336699"]);
+        assert!(render_background(&cfg, 8, 8).is_err());
+    }
+
+    #[test]
+    fn transparent_skips_the_template() {
+        let mut cfg = config(BackgroundTemplate::Mesh, &["#336699"]);
+        cfg.transparent = true;
+        cfg.vignette = Some(serde_yaml::from_str("{}").expect("vignette"));
+        let image = render_background(&cfg, 8, 8).expect("transparent");
+        assert!(image.pixels().all(|pixel| pixel[3] == 0));
+    }
+
+    #[test]
+    fn blurred_screenshot_covers_the_canvas_with_the_screenshot() {
+        let cfg = config(BackgroundTemplate::BlurredScreenshot, &[]);
+        assert!(render_background(&cfg, 8, 8).is_err());
+
+        let mut screenshot = RgbaImage::from_pixel(10, 20, Rgba([200, 40, 40, 255]));
+        for y in 10..20 {
+            for x in 0..10 {
+                screenshot.put_pixel(x, y, Rgba([40, 40, 200, 255]));
+            }
+        }
+        let screenshot = DynamicImage::ImageRgba8(screenshot);
+        let image = render_scene_background(&cfg, 30, 30, &screenshot).expect("blurred");
+        assert_eq!(image.dimensions(), (30, 30));
+        // Cover scaling crops the sides, so the top stays red and the bottom blue
+        assert!(image.get_pixel(15, 0)[0] > image.get_pixel(15, 0)[2]);
+        assert!(image.get_pixel(15, 29)[2] > image.get_pixel(15, 29)[0]);
+        assert!(image.pixels().all(|pixel| pixel[3] == 255));
+    }
+
+    #[test]
+    fn zero_grain_renders_without_noise() {
+        let mut cfg = config(BackgroundTemplate::Radial, &["#406080", "#406080"]);
+        cfg.grain = 0.0;
+        let image = render_background(&cfg, 16, 16).expect("radial");
+        assert!(
+            image
+                .pixels()
+                .all(|pixel| *pixel == Rgba([64, 96, 128, 255]))
+        );
+
+        cfg.grain = 1.0;
+        let image = render_background(&cfg, 16, 16).expect("radial");
+        assert!(
+            image
+                .pixels()
+                .any(|pixel| *pixel != Rgba([64, 96, 128, 255]))
+        );
+    }
+
+    #[test]
+    fn stripes_angle_zero_gives_horizontal_bands() {
+        let mut cfg = config(BackgroundTemplate::Stripes, &["#000000", "#FFFFFF"]);
+        cfg.grain = 0.0;
+        cfg.stripes.angle_degrees = Some(0.0);
+        let image = render_background(&cfg, 64, 256).expect("stripes");
+        for y in 0..256 {
+            let first = image.get_pixel(0, y);
+            assert!((1..64).all(|x| image.get_pixel(x, y) == first));
+        }
+    }
+
+    #[test]
+    fn deterministic_layout_maps_mesh_corners_in_order() {
+        let mut cfg = config(
+            BackgroundTemplate::Mesh,
+            &["#FF0000", "#00FF00", "#0000FF", "#FFFFFF"],
+        );
+        cfg.grain = 0.0;
+        cfg.mesh.vignette = 0.0;
+        cfg.deterministic_layout = true;
+        let image = render_background(&cfg, 32, 32).expect("mesh");
+        assert_eq!(*image.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+        assert_eq!(*image.get_pixel(31, 0), Rgba([0, 255, 0, 255]));
+        assert_eq!(*image.get_pixel(0, 31), Rgba([0, 0, 255, 255]));
+        assert_eq!(*image.get_pixel(31, 31), Rgba([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn dots_tint_a_grid_with_the_last_color() {
+        let mut cfg = config(BackgroundTemplate::Dots, &["#000000", "#000000", "#FFFFFF"]);
+        cfg.grain = 0.0;
+        let image = render_background(&cfg, 200, 200).expect("dots");
+        let max = image.pixels().map(|pixel| pixel[0]).max().unwrap_or(0);
+        assert!(max > 40 && max < 90);
+        let dark = image.pixels().filter(|pixel| pixel[0] == 0).count();
+        assert!(dark > image.pixels().len() / 2);
+    }
+
+    #[test]
+    fn mesh_spreads_large_palettes_over_a_grid() {
+        let mut cfg = config(
+            BackgroundTemplate::Mesh,
+            &[
+                "#FF0000", "#00FF00", "#0000FF", "#FFFF00", "#00FFFF", "#FF00FF",
+            ],
+        );
+        cfg.grain = 0.0;
+        cfg.mesh.vignette = 0.0;
+        cfg.deterministic_layout = true;
+        let image = render_background(&cfg, 33, 33).expect("mesh");
+        assert_eq!(*image.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+        assert_eq!(*image.get_pixel(16, 0), Rgba([0, 255, 0, 255]));
+        assert_eq!(*image.get_pixel(32, 0), Rgba([0, 0, 255, 255]));
+        assert_eq!(*image.get_pixel(0, 32), Rgba([255, 255, 0, 255]));
+        assert_eq!(*image.get_pixel(16, 32), Rgba([0, 255, 255, 255]));
+        assert_eq!(*image.get_pixel(32, 32), Rgba([255, 0, 255, 255]));
+    }
+
+    #[test]
+    fn sub_seeds_keep_layout_on_the_base_seed() {
+        let mesh = TemplateSeeds::derive(42, BackgroundTemplate::Mesh);
+        assert_eq!((mesh.layout, mesh.noise), (42, 42));
+
+        let stripes = TemplateSeeds::derive(42, BackgroundTemplate::Stripes);
+        assert_eq!(stripes.layout, 42);
+        assert_eq!(stripes.noise, 42 * 13);
+    }
+
+    #[test]
+    fn linear_runs_top_to_bottom_by_default() {
+        let cfg = config(BackgroundTemplate::Linear, &["#000000", "#FFFFFF"]);
+        let image = render_background(&cfg, 4, 100).expect("linear");
+        assert!(image.get_pixel(0, 0)[0] < 5);
+        assert!(image.get_pixel(0, 99)[0] > 250);
+        assert_eq!(image.get_pixel(0, 50), image.get_pixel(3, 50));
+    }
+
+    #[test]
+    fn linear_blend_brightens_gradient_midpoints() {
+        let mut cfg = config(BackgroundTemplate::Linear, &["#FF0000", "#00FF00"]);
+        let srgb = render_background(&cfg, 1, 101).expect("srgb");
+        cfg.linear_blend = true;
+        let linear = render_background(&cfg, 1, 101).expect("linear light");
+        assert!(srgb.get_pixel(0, 0)[0] > 250 && linear.get_pixel(0, 0)[0] > 250);
+        assert!(linear.get_pixel(0, 50)[0] > srgb.get_pixel(0, 50)[0] + 40);
+        assert!(linear.get_pixel(0, 50)[1] > srgb.get_pixel(0, 50)[1] + 40);
+    }
+}
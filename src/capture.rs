@@ -1,34 +1,103 @@
 use std::fs;
+use std::io::ErrorKind;
 use std::path::Path;
 use std::process::Command;
 use std::thread;
 use std::time::Duration;
 
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result, anyhow, bail};
+use image::DynamicImage;
+use image::imageops::crop_imm;
 
-use crate::config::{CaptureConfig, SceneConfig};
+use crate::color::parse_hex_rgba;
+use crate::config::{AlphaMode, CaptureConfig};
 
-pub fn capture_scene(scene: &SceneConfig, config_dir: &Path, raw_path: &Path) -> Result<()> {
+/// Capture one device's screenshot into `raw_path`; `scene_id` is only used in messages
+pub fn capture_source(
+    scene_id: &str,
+    capture: &CaptureConfig,
+    config_dir: &Path,
+    raw_path: &Path,
+) -> Result<()> {
     if let Some(parent) = raw_path.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("failed creating {}", parent.display()))?;
     }
 
-    match &scene.capture {
-        CaptureConfig::File { path } => {
+    match capture {
+        CaptureConfig::File {
+            path,
+            alpha,
+            alpha_color,
+        } => {
             let source_path = resolve_path(config_dir, path);
             let source_img = image::open(&source_path).with_context(|| {
                 format!(
                     "scene '{}' failed to open source image {}",
-                    scene.id,
+                    scene_id,
                     source_path.display()
                 )
             })?;
 
+            let source_img = apply_alpha_mode(scene_id, source_img, *alpha, alpha_color)?;
             source_img.save(raw_path).with_context(|| {
                 format!(
                     "scene '{}' failed to save normalized raw image {}",
-                    scene.id,
+                    scene_id,
+                    raw_path.display()
+                )
+            })?;
+            Ok(())
+        }
+        CaptureConfig::Region {
+            path,
+            x,
+            y,
+            width,
+            height,
+            alpha,
+            alpha_color,
+        } => {
+            let source_path = resolve_path(config_dir, path);
+            let source_img = image::open(&source_path).with_context(|| {
+                format!(
+                    "scene '{}' failed to open source image {}",
+                    scene_id,
+                    source_path.display()
+                )
+            })?;
+
+            let fits_x = x
+                .checked_add(*width)
+                .is_some_and(|end| end <= source_img.width());
+            let fits_y = y
+                .checked_add(*height)
+                .is_some_and(|end| end <= source_img.height());
+            if *width == 0 || *height == 0 || !fits_x || !fits_y {
+                bail!(
+                    "scene '{}' region {}x{}+{}+{} is outside source image {} ({}x{})",
+                    scene_id,
+                    width,
+                    height,
+                    x,
+                    y,
+                    source_path.display(),
+                    source_img.width(),
+                    source_img.height()
+                );
+            }
+
+            let region = crop_imm(&source_img.to_rgba8(), *x, *y, *width, *height).to_image();
+            let region = apply_alpha_mode(
+                scene_id,
+                DynamicImage::ImageRgba8(region),
+                *alpha,
+                alpha_color,
+            )?;
+            region.save(raw_path).with_context(|| {
+                format!(
+                    "scene '{}' failed to save cropped raw image {}",
+                    scene_id,
                     raw_path.display()
                 )
             })?;
@@ -48,14 +117,140 @@ pub fn capture_scene(scene: &SceneConfig, config_dir: &Path, raw_path: &Path) ->
             if !status.success() {
                 bail!(
                     "scene '{}' simctl screenshot failed for device '{}'",
-                    scene.id,
+                    scene_id,
                     device
                 );
             }
 
             Ok(())
         }
+        CaptureConfig::Adb { serial, settle_ms } => {
+            if *settle_ms > 0 {
+                thread::sleep(Duration::from_millis(*settle_ms));
+            }
+
+            let output = Command::new("adb")
+                .args(["-s", serial, "exec-out", "screencap", "-p"])
+                .output()
+                .map_err(|err| match err.kind() {
+                    ErrorKind::NotFound => anyhow!(
+                        "adb not found on PATH; install the Android SDK platform-tools to capture '{}'",
+                        serial
+                    ),
+                    _ => anyhow!(err).context("failed to execute adb"),
+                })?;
+
+            if !output.status.success() || output.stdout.is_empty() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                bail!(
+                    "scene '{}' adb screencap failed for device '{}': {}",
+                    scene_id,
+                    serial,
+                    stderr.trim()
+                );
+            }
+
+            fs::write(raw_path, &output.stdout).with_context(|| {
+                format!(
+                    "scene '{}' failed to save adb screenshot {}",
+                    scene_id,
+                    raw_path.display()
+                )
+            })?;
+            Ok(())
+        }
+        CaptureConfig::Command {
+            program,
+            args,
+            settle_ms,
+        } => {
+            if *settle_ms > 0 {
+                thread::sleep(Duration::from_millis(*settle_ms));
+            }
+
+            // The command runs in the config directory, so hand it an absolute output path
+            // and clear any previous capture so a silent no-op can't reuse a stale file
+            let output_path = std::path::absolute(raw_path)
+                .with_context(|| format!("failed resolving {}", raw_path.display()))?;
+            if let Err(err) = fs::remove_file(&output_path)
+                && err.kind() != ErrorKind::NotFound
+            {
+                return Err(err)
+                    .with_context(|| format!("failed removing {}", output_path.display()));
+            }
+
+            let output_arg = output_path.to_string_lossy();
+            let args: Vec<String> = args
+                .iter()
+                .map(|arg| arg.replace("{output}", &output_arg))
+                .collect();
+            let program_path = if program.contains('/') {
+                resolve_path(config_dir, Path::new(program))
+            } else {
+                program.into()
+            };
+
+            let output = Command::new(&program_path)
+                .args(&args)
+                .current_dir(config_dir)
+                .output()
+                .with_context(|| {
+                    format!(
+                        "scene '{}' failed to execute capture command '{}'",
+                        scene_id, program
+                    )
+                })?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                bail!(
+                    "scene '{}' capture command '{}' failed ({}): {}",
+                    scene_id,
+                    program,
+                    output.status,
+                    stderr.trim()
+                );
+            }
+            if !output_path.is_file() {
+                bail!(
+                    "scene '{}' capture command '{}' exited successfully but did not write {}; pass `{{output}}` in `args` as the PNG destination",
+                    scene_id,
+                    program,
+                    output_path.display()
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Make transparent file captures opaque unless the scene opts into keeping alpha
+fn apply_alpha_mode(
+    scene_id: &str,
+    image: DynamicImage,
+    mode: AlphaMode,
+    color: &str,
+) -> Result<DynamicImage> {
+    // `Warn` keeps the pixels too; the pipeline reports them as a run warning
+    if mode != AlphaMode::Flatten || !image.color().has_alpha() {
+        return Ok(image);
+    }
+
+    let mut rgba = image.to_rgba8();
+    if rgba.pixels().all(|pixel| pixel[3] == 255) {
+        return Ok(DynamicImage::ImageRgba8(rgba));
+    }
+
+    let matte = parse_hex_rgba(color)
+        .with_context(|| format!("scene '{}' has invalid alpha_color '{}'", scene_id, color))?;
+    for pixel in rgba.pixels_mut() {
+        let alpha = pixel[3] as f32 / 255.0;
+        for channel in 0..3 {
+            let value = pixel[channel] as f32 * alpha + matte[channel] as f32 * (1.0 - alpha);
+            pixel[channel] = value.round().clamp(0.0, 255.0) as u8;
+        }
+        pixel[3] = 255;
     }
+    Ok(DynamicImage::ImageRgba8(rgba))
 }
 
 fn resolve_path(config_dir: &Path, path: &Path) -> std::path::PathBuf {
@@ -65,3 +260,147 @@ fn resolve_path(config_dir: &Path, path: &Path) -> std::path::PathBuf {
         config_dir.join(path)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use image::{Rgba, RgbaImage};
+    use tempfile::tempdir;
+
+    use crate::config::{Config, SceneConfig};
+
+    fn capture_scene(scene: &SceneConfig, config_dir: &Path, raw_path: &Path) -> Result<()> {
+        capture_source(&scene.id, &scene.phones[0].capture, config_dir, raw_path)
+    }
+
+    #[test]
+    fn region_capture_crops_source_rectangle() {
+        let temp = tempdir().expect("tempdir");
+        let mut sheet = RgbaImage::from_pixel(40, 20, Rgba([10, 10, 10, 255]));
+        for y in 0..20 {
+            for x in 20..40 {
+                sheet.put_pixel(x, y, Rgba([200, 40, 40, 255]));
+            }
+        }
+        sheet
+            .save(temp.path().join("sheet.png"))
+            .expect("save sheet");
+
+        let config_path = temp.path().join("screenforge.yaml");
+        fs::write(
+            &config_path,
+            r#"
+scenes:
+  - id: right_half
+    capture:
+      adapter: region
+      path: ./sheet.png
+      x: 20
+      y: 0
+      width: 20
+      height: 20
+    output:
+      filename: out.png
+      width: 100
+      height: 200
+    background: {}
+    phone:
+      x: 0
+      y: 0
+      width: 50
+      height: 100
+"#,
+        )
+        .expect("write config");
+
+        let config = Config::from_path(&config_path).expect("config");
+        let raw_path = temp.path().join("raw/right_half.png");
+        capture_scene(&config.scenes[0], temp.path(), &raw_path).expect("capture");
+
+        let raw = image::open(&raw_path).expect("open raw").to_rgba8();
+        assert_eq!(raw.dimensions(), (20, 20));
+        assert_eq!(*raw.get_pixel(0, 0), Rgba([200, 40, 40, 255]));
+    }
+
+    #[test]
+    fn file_capture_flattens_transparency_unless_preserved() {
+        let temp = tempdir().expect("tempdir");
+        RgbaImage::from_pixel(4, 4, Rgba([0, 0, 0, 0]))
+            .save(temp.path().join("clear.png"))
+            .expect("save source");
+
+        let config_path = temp.path().join("screenforge.yaml");
+        fs::write(
+            &config_path,
+            r##"
+scenes:
+  - id: flattened
+    capture:
+      adapter: file
+      path: ./clear.png
+      alpha_color: "#102030"
+    output: { filename: a.png, width: 100, height: 200 }
+    background: {}
+    phone: { x: 0, y: 0, width: 50, height: 100 }
+  - id: preserved
+    capture:
+      adapter: file
+      path: ./clear.png
+      alpha: preserve
+    output: { filename: b.png, width: 100, height: 200 }
+    background: {}
+    phone: { x: 0, y: 0, width: 50, height: 100 }
+"##,
+        )
+        .expect("write config");
+
+        let config = Config::from_path(&config_path).expect("config");
+        let flat_path = temp.path().join("raw/flattened.png");
+        let kept_path = temp.path().join("raw/preserved.png");
+        capture_scene(&config.scenes[0], temp.path(), &flat_path).expect("capture");
+        capture_scene(&config.scenes[1], temp.path(), &kept_path).expect("capture");
+
+        let flat = image::open(&flat_path).expect("open").to_rgba8();
+        assert_eq!(*flat.get_pixel(0, 0), Rgba([16, 32, 48, 255]));
+        let kept = image::open(&kept_path).expect("open").to_rgba8();
+        assert_eq!(kept.get_pixel(0, 0)[3], 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_capture_substitutes_output_and_requires_the_file() {
+        let temp = tempdir().expect("tempdir");
+        RgbaImage::from_pixel(4, 4, Rgba([9, 8, 7, 255]))
+            .save(temp.path().join("shot.png"))
+            .expect("save source");
+
+        let config_path = temp.path().join("screenforge.yaml");
+        fs::write(
+            &config_path,
+            r#"
+scenes:
+  - id: copied
+    capture: { adapter: command, program: cp, args: [shot.png, "{output}"] }
+    output: { filename: a.png, width: 100, height: 200 }
+    background: {}
+    phone: { x: 0, y: 0, width: 50, height: 100 }
+  - id: silent
+    capture: { adapter: command, program: "true" }
+    output: { filename: b.png, width: 100, height: 200 }
+    background: {}
+    phone: { x: 0, y: 0, width: 50, height: 100 }
+"#,
+        )
+        .expect("write config");
+
+        let config = Config::from_path(&config_path).expect("config");
+        let raw_path = temp.path().join("raw/copied.png");
+        capture_scene(&config.scenes[0], temp.path(), &raw_path).expect("capture");
+        let raw = image::open(&raw_path).expect("open").to_rgba8();
+        assert_eq!(*raw.get_pixel(0, 0), Rgba([9, 8, 7, 255]));
+
+        let err = capture_scene(&config.scenes[1], temp.path(), &raw_path).expect_err("no file");
+        assert!(format!("{:#}", err).contains("did not write"));
+    }
+}
@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+
+use screenforge::pipeline::Since;
+use screenforge::snap::{CropRect, InlineScene};
 
 #[derive(Debug, Parser)]
 #[command(
@@ -11,8 +14,18 @@ use clap::{Parser, Subcommand, ValueEnum};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Exit with an error when any warnings are reported
+    #[arg(long, global = true, default_value_t = false)]
+    pub fail_on_warning: bool,
+
+    /// Increase log verbosity (-v for info, -vv for debug); RUST_LOG takes precedence
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    pub verbose: u8,
 }
 
+// Parsed once per process, so the size of the flag-heavy `Snap` variant doesn't matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Subcommand)]
 pub enum Commands {
     /// Run full pipeline: capture -> background -> compose -> preview
@@ -20,12 +33,40 @@ pub enum Commands {
         /// Path to YAML config
         #[arg(short, long, default_value = "screenforge.yaml")]
         config: PathBuf,
+        /// Render one output per headline variant instead of picking one by seed
+        #[arg(long, default_value_t = false)]
+        all_variants: bool,
+        /// Also write each compose layer as a transparent PNG into <DIR>/<scene_id>/
+        #[arg(long, value_name = "DIR")]
+        export_layers: Option<PathBuf>,
+        /// Only re-render file/region scenes whose source changed after this point:
+        /// `last` (previous run) or a Unix timestamp in seconds
+        #[arg(long, value_name = "WHEN")]
+        since: Option<Since>,
+        /// Write <output_dir>/palettes.json with the background colors each scene used
+        #[arg(long, default_value_t = false)]
+        export_palettes: bool,
+        /// Keep running and re-render whenever the config or a file it references changes
+        #[arg(long, default_value_t = false)]
+        watch: bool,
+        /// Package every final image plus manifest.json into a zip archive at <PATH>
+        #[arg(long, value_name = "PATH")]
+        zip: Option<PathBuf>,
+        /// Re-render every scene, even those whose inputs match the previous run's cache
+        #[arg(long, default_value_t = false)]
+        force: bool,
+        /// Print nothing but warnings and errors: no per-scene progress and no summary
+        #[arg(short, long, default_value_t = false)]
+        quiet: bool,
+        /// Open the preview index.html in the default browser after a successful run
+        #[arg(long, default_value_t = false)]
+        open: bool,
     },
     /// List built-in phone model presets
     Devices,
-    /// Import transparent PNG frame overlays into assets/frames
+    /// Import transparent frame overlays (PNG, WebP, TIFF, ...) into assets/frames as PNG
     ImportFrames {
-        /// Source directory containing PNG frame files
+        /// Source directory containing frame images
         #[arg(short, long)]
         source: PathBuf,
         /// Destination directory for normalized overlays
@@ -34,15 +75,59 @@ pub enum Commands {
         /// Overwrite destination overlays if they already exist
         #[arg(long, default_value_t = false)]
         overwrite: bool,
+        /// Run every check and list the planned actions without writing anything
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+        /// Output format (text or json for CI)
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Download a device frame pack and import it into assets/frames
+    ///
+    /// Examples:
+    ///   screenforge fetch-frames --pack iphone-17
+    ///   screenforge fetch-frames --pack https://example.com/frames --overwrite
+    #[command(verbatim_doc_comment)]
+    FetchFrames {
+        /// Built-in pack name or base URL serving <model_slug>.png files
+        #[arg(long)]
+        pack: String,
+        /// Destination directory for normalized overlays
+        #[arg(long, default_value = "assets/frames")]
+        dest: PathBuf,
+        /// Overwrite destination overlays if they already exist
+        #[arg(long, default_value_t = false)]
+        overwrite: bool,
     },
     /// Validate overlay files referenced by config scenes
     VerifyOverlay {
         /// Path to YAML config
         #[arg(short, long, default_value = "screenforge.yaml")]
         config: PathBuf,
-        /// Treat warnings as failures
+        /// Treat warnings as failures (same as --fail-on-warning)
         #[arg(long, default_value_t = false)]
         strict: bool,
+        /// Output format (text or json for CI)
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Check a config for errors without capturing or rendering anything
+    Validate {
+        /// Path to YAML config
+        #[arg(short, long, default_value = "screenforge.yaml")]
+        config: PathBuf,
+        /// Output format (text or json for CI)
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Print each scene's resolved phone style, overlay and screen rect without rendering
+    Explain {
+        /// Path to YAML config
+        #[arg(short, long, default_value = "screenforge.yaml")]
+        config: PathBuf,
+        /// Output format (text or json)
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
     },
     /// Capture and frame a screenshot from a running iOS simulator
     ///
@@ -52,6 +137,7 @@ pub enum Commands {
     /// Examples:
     ///   screenforge snap "iPhone 17 Pro"
     ///   screenforge snap "My-Custom-Simulator" --output hero.png
+    ///   screenforge snap "iPhone 17 Pro" -o hero.png -o hero.jpg
     ///   screenforge snap 864E85BD-BAAF-4BB3-9D02-3D9FD0C34D4A --raw
     ///   screenforge snap --list
     #[command(verbatim_doc_comment)]
@@ -60,9 +146,9 @@ pub enum Commands {
         #[arg(value_name = "SIMULATOR")]
         simulator: Option<String>,
 
-        /// Output file path
+        /// Output file path; repeat to also write other formats (e.g. -o shot.png -o shot.jpg)
         #[arg(short, long, default_value = "snap_output.png")]
-        output: PathBuf,
+        output: Vec<PathBuf>,
 
         /// Capture raw screenshot without framing
         #[arg(long, default_value_t = false)]
@@ -72,25 +158,103 @@ pub enum Commands {
         #[arg(short, long, default_value_t = false)]
         list: bool,
 
+        /// Capture every booted simulator; outputs get the simulator UDID appended to their stem
+        #[arg(long, default_value_t = false, conflicts_with_all = ["simulator", "list"])]
+        batch: bool,
+
+        /// Boot this simulator (name, partial name, or UDID) and wait for it before capturing;
+        /// it is also the capture target when SIMULATOR is omitted
+        #[arg(long, value_name = "NAME", conflicts_with = "list")]
+        boot: Option<String>,
+
+        /// Seconds to wait for --boot to reach the Booted state
+        #[arg(long, value_name = "SECS", default_value_t = 120, requires = "boot")]
+        boot_timeout: u64,
+
+        /// Inline scene to capture, e.g. "sim=iPhone 17 Pro;output=hero.png;headline=Fast";
+        /// repeat for several shots. Unset keys fall back to the other flags.
+        #[arg(
+            long = "scene",
+            value_name = "SPEC",
+            conflicts_with_all = ["simulator", "list", "batch"]
+        )]
+        scenes: Vec<InlineScene>,
+
         /// Output format (text or json for agent consumption)
         #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
         format: OutputFormat,
 
+        /// Open each captured image in the default viewer after a successful capture
+        #[arg(long, default_value_t = false, conflicts_with = "list")]
+        open: bool,
+
+        /// YAML preset with snap settings; flags given on the command line override it
+        #[arg(long, value_name = "PATH")]
+        preset: Option<PathBuf>,
+
         /// Override auto-detected phone model
         #[arg(long, value_enum)]
         model: Option<PhoneModelArg>,
 
-        /// Wait time (ms) before capturing to let UI settle
-        #[arg(long, default_value_t = 500)]
-        settle_ms: u64,
+        /// Phone model to use only when auto-detection fails (--model always wins)
+        #[arg(long, value_enum)]
+        fallback_model: Option<PhoneModelArg>,
+
+        /// URL to open in the simulator before capturing, e.g. a deep link like
+        /// myapp://settings; --settle-ms then gives the screen time to load
+        #[arg(long, value_name = "URL", conflicts_with = "list")]
+        open_url: Option<String>,
+
+        /// Record this many seconds of the simulator's screen as an H.264 video instead of
+        /// taking a screenshot; the video is not framed and non-video outputs become .mp4
+        #[arg(
+            long,
+            value_name = "SECS",
+            conflicts_with_all = ["list", "batch", "scenes", "crop"]
+        )]
+        record: Option<u64>,
+
+        /// Keep only this region of the raw screenshot, as x,y,width,height in screenshot
+        /// pixels (e.g. 0,162,1206,2460 to drop the status bar); applied before framing
+        #[arg(long, value_name = "X,Y,W,H", conflicts_with = "list")]
+        crop: Option<CropRect>,
+
+        /// Wait time (ms) before capturing to let UI settle (default: 500)
+        #[arg(long)]
+        settle_ms: Option<u64>,
+
+        /// Capture repeatedly, --settle-ms apart, until two screenshots match
+        #[arg(long, default_value_t = false)]
+        settle_stable: bool,
+
+        /// Captures to try with --settle-stable before giving up (default: 10)
+        #[arg(long)]
+        settle_attempts: Option<u32>,
 
-        /// Output canvas width
-        #[arg(long, default_value_t = 1284)]
-        width: u32,
+        /// Percentage of pixels that may differ for --settle-stable to accept (default: 0)
+        #[arg(long)]
+        settle_tolerance: Option<f32>,
 
-        /// Output canvas height
-        #[arg(long, default_value_t = 2778)]
-        height: u32,
+        /// App Store size preset for the canvas; --width and --height override it
+        #[arg(long, value_enum)]
+        size: Option<SizeArg>,
+
+        /// Output canvas width (default: 1284)
+        #[arg(long)]
+        width: Option<u32>,
+
+        /// Output canvas height (default: 2778)
+        #[arg(long)]
+        height: Option<u32>,
+
+        /// Phone width as a fraction of the canvas width, 0-1 (default: 0.73)
+        #[arg(long, value_name = "FRACTION")]
+        phone_scale: Option<f32>,
+
+        /// Shift the phone down by this fraction of the canvas height; negative moves it
+        /// up (default: 0.05)
+        #[arg(long, value_name = "FRACTION", allow_hyphen_values = true)]
+        phone_bias_y: Option<f32>,
 
         /// Headline text to render above phone
         #[arg(long)]
@@ -100,13 +264,25 @@ pub enum Commands {
         #[arg(long)]
         subheadline: Option<String>,
 
-        /// Background template
-        #[arg(long, value_enum, default_value_t = BackgroundTemplateArg::Mesh)]
-        background: BackgroundTemplateArg,
+        /// Headline font size in pixels (default: 120)
+        #[arg(long, value_name = "PX")]
+        headline_size: Option<f32>,
+
+        /// Subheadline font size in pixels (default: 56)
+        #[arg(long, value_name = "PX")]
+        subheadline_size: Option<f32>,
+
+        /// Where the headline and subheadline sit (default: above-phone)
+        #[arg(long, value_enum)]
+        text_position: Option<TextPositionArg>,
+
+        /// Background template (default: mesh)
+        #[arg(long, value_enum)]
+        background: Option<BackgroundTemplateArg>,
 
-        /// Background seed for deterministic generation
-        #[arg(long, default_value_t = 42)]
-        seed: u64,
+        /// Background seed for deterministic generation (default: 42)
+        #[arg(long)]
+        seed: Option<u64>,
 
         /// Background colors (comma-separated hex colors)
         #[arg(long, value_delimiter = ',')]
@@ -116,9 +292,39 @@ pub enum Commands {
         #[arg(long, default_value_t = false)]
         auto_colors: bool,
 
-        /// Strategy for auto-generated colors
-        #[arg(long, value_enum, default_value_t = AutoStrategyArg::Analogous)]
-        auto_strategy: AutoStrategyArg,
+        /// Strategy for auto-generated colors (default: analogous)
+        #[arg(long, value_enum)]
+        auto_strategy: Option<AutoStrategyArg>,
+
+        /// Composite a QR code encoding this URL (e.g. App Store link)
+        #[arg(long, value_name = "URL")]
+        qr: Option<String>,
+
+        /// Canvas corner for the QR code (default: bottom-right)
+        #[arg(long, value_enum)]
+        qr_position: Option<CornerArg>,
+
+        /// QR code size in pixels, including its white quiet-zone plate (default: 220)
+        #[arg(long)]
+        qr_size: Option<u32>,
+
+        /// Also write each compose layer as a transparent PNG into this directory
+        #[arg(long, value_name = "DIR")]
+        export_layers: Option<PathBuf>,
+    },
+    /// Frame one screenshot in every built-in phone model as a labeled montage
+    ///
+    /// Examples:
+    ///   screenforge compare-models ./screenshots/home.png
+    ///   screenforge compare-models ./screenshots/home.png --output compare.png
+    #[command(verbatim_doc_comment)]
+    CompareModels {
+        /// Screenshot to frame
+        #[arg(value_name = "IMAGE")]
+        image: PathBuf,
+        /// Output montage path
+        #[arg(short, long, default_value = "compare.png")]
+        output: PathBuf,
     },
     /// Convert mockup frames (white screen) to overlay frames (transparent screen)
     ///
@@ -155,13 +361,58 @@ pub enum OutputFormat {
 pub enum PhoneModelArg {
     Iphone17Pro,
     Iphone17ProMax,
+    #[value(name = "ipad-pro-11")]
+    IpadPro11,
+    #[value(name = "ipad-pro-13")]
+    IpadPro13,
 }
 
-impl From<PhoneModelArg> for crate::config::PhoneModel {
+impl From<PhoneModelArg> for screenforge::config::PhoneModel {
     fn from(arg: PhoneModelArg) -> Self {
         match arg {
             PhoneModelArg::Iphone17Pro => Self::Iphone17Pro,
             PhoneModelArg::Iphone17ProMax => Self::Iphone17ProMax,
+            PhoneModelArg::IpadPro11 => Self::IpadPro11,
+            PhoneModelArg::IpadPro13 => Self::IpadPro13,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SizeArg {
+    /// 1320x2868 (iPhone 6.9")
+    #[value(name = "app-store-6-9")]
+    AppStore6_9,
+    /// 1290x2796 (iPhone 6.7")
+    #[value(name = "app-store-6-7")]
+    AppStore6_7,
+    /// 1284x2778 (iPhone 6.5")
+    #[value(name = "app-store-6-5")]
+    AppStore6_5,
+    /// 1206x2622 (iPhone 6.3")
+    #[value(name = "app-store-6-3")]
+    AppStore6_3,
+    /// 1242x2208 (iPhone 5.5")
+    #[value(name = "app-store-5-5")]
+    AppStore5_5,
+    /// 2064x2752 (iPad 13")
+    #[value(name = "ipad-13")]
+    Ipad13,
+    /// 2048x2732 (iPad 12.9")
+    #[value(name = "ipad-12-9")]
+    Ipad12_9,
+}
+
+impl From<SizeArg> for screenforge::presets::SizePreset {
+    fn from(arg: SizeArg) -> Self {
+        match arg {
+            SizeArg::AppStore6_9 => Self::AppStore6_9,
+            SizeArg::AppStore6_7 => Self::AppStore6_7,
+            SizeArg::AppStore6_5 => Self::AppStore6_5,
+            SizeArg::AppStore6_3 => Self::AppStore6_3,
+            SizeArg::AppStore5_5 => Self::AppStore5_5,
+            SizeArg::Ipad13 => Self::Ipad13,
+            SizeArg::Ipad12_9 => Self::Ipad12_9,
         }
     }
 }
@@ -170,13 +421,23 @@ impl From<PhoneModelArg> for crate::config::PhoneModel {
 pub enum BackgroundTemplateArg {
     Mesh,
     Stripes,
+    Radial,
+    Solid,
+    Linear,
+    Dots,
+    BlurredScreenshot,
 }
 
-impl From<BackgroundTemplateArg> for crate::config::BackgroundTemplate {
+impl From<BackgroundTemplateArg> for screenforge::config::BackgroundTemplate {
     fn from(arg: BackgroundTemplateArg) -> Self {
         match arg {
             BackgroundTemplateArg::Mesh => Self::Mesh,
             BackgroundTemplateArg::Stripes => Self::Stripes,
+            BackgroundTemplateArg::Radial => Self::Radial,
+            BackgroundTemplateArg::Solid => Self::Solid,
+            BackgroundTemplateArg::Linear => Self::Linear,
+            BackgroundTemplateArg::Dots => Self::Dots,
+            BackgroundTemplateArg::BlurredScreenshot => Self::BlurredScreenshot,
         }
     }
 }
@@ -190,7 +451,7 @@ pub enum AutoStrategyArg {
     Triadic,
 }
 
-impl From<AutoStrategyArg> for crate::palette::PaletteStrategy {
+impl From<AutoStrategyArg> for screenforge::PaletteStrategy {
     fn from(arg: AutoStrategyArg) -> Self {
         match arg {
             AutoStrategyArg::Monochromatic => Self::Monochromatic,
@@ -200,3 +461,43 @@ impl From<AutoStrategyArg> for crate::palette::PaletteStrategy {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum TextPositionArg {
+    AbovePhone,
+    BelowPhone,
+    Top,
+    Bottom,
+    Center,
+}
+
+impl From<TextPositionArg> for screenforge::config::TextPosition {
+    fn from(arg: TextPositionArg) -> Self {
+        match arg {
+            TextPositionArg::AbovePhone => Self::AbovePhone,
+            TextPositionArg::BelowPhone => Self::BelowPhone,
+            TextPositionArg::Top => Self::Top,
+            TextPositionArg::Bottom => Self::Bottom,
+            TextPositionArg::Center => Self::Center,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CornerArg {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl From<CornerArg> for screenforge::config::Corner {
+    fn from(arg: CornerArg) -> Self {
+        match arg {
+            CornerArg::TopLeft => Self::TopLeft,
+            CornerArg::TopRight => Self::TopRight,
+            CornerArg::BottomLeft => Self::BottomLeft,
+            CornerArg::BottomRight => Self::BottomRight,
+        }
+    }
+}
@@ -119,28 +119,153 @@ pub fn rgba_to_hex(rgba: Rgba<u8>) -> String {
     format!("#{:02X}{:02X}{:02X}", rgba[0], rgba[1], rgba[2])
 }
 
+/// Parse `#RRGGBB`, `#RRGGBBAA`, shorthand `#RGB`/`#RGBA`, or a CSS color name like `white`
 pub fn parse_hex_rgba(input: &str) -> Result<Rgba<u8>> {
     let value = input.trim();
+    if let Some(color) = named_color(value) {
+        return Ok(color);
+    }
     let hex = value.strip_prefix('#').unwrap_or(value);
 
-    match hex.len() {
-        6 => {
-            let r = u8::from_str_radix(&hex[0..2], 16)?;
-            let g = u8::from_str_radix(&hex[2..4], 16)?;
-            let b = u8::from_str_radix(&hex[4..6], 16)?;
-            Ok(Rgba([r, g, b, 255]))
-        }
-        8 => {
-            let r = u8::from_str_radix(&hex[0..2], 16)?;
-            let g = u8::from_str_radix(&hex[2..4], 16)?;
-            let b = u8::from_str_radix(&hex[4..6], 16)?;
-            let a = u8::from_str_radix(&hex[6..8], 16)?;
-            Ok(Rgba([r, g, b, a]))
-        }
+    let channels: Option<Vec<u8>> = match hex.len() {
+        // Shorthand repeats each digit: #FAB -> #FFAABB
+        3 | 4 => hex
+            .chars()
+            .map(|digit| {
+                let nibble = digit.to_digit(16)? as u8;
+                Some(nibble * 17)
+            })
+            .collect(),
+        6 | 8 if hex.is_ascii() => (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+            .collect(),
+        _ => None,
+    };
+
+    match channels.as_deref() {
+        Some(&[r, g, b]) => Ok(Rgba([r, g, b, 255])),
+        Some(&[r, g, b, a]) => Ok(Rgba([r, g, b, a])),
         _ => bail!("invalid color '{}': expected #RRGGBB or #RRGGBBAA", input),
     }
 }
 
+fn named_color(name: &str) -> Option<Rgba<u8>> {
+    let rgb = match name.to_ascii_lowercase().as_str() {
+        "transparent" => return Some(Rgba([0, 0, 0, 0])),
+        "black" => [0, 0, 0],
+        "white" => [255, 255, 255],
+        "red" => [255, 0, 0],
+        "green" => [0, 128, 0],
+        "lime" => [0, 255, 0],
+        "blue" => [0, 0, 255],
+        "yellow" => [255, 255, 0],
+        "cyan" | "aqua" => [0, 255, 255],
+        "magenta" | "fuchsia" => [255, 0, 255],
+        "gray" | "grey" => [128, 128, 128],
+        "silver" => [192, 192, 192],
+        "maroon" => [128, 0, 0],
+        "olive" => [128, 128, 0],
+        "teal" => [0, 128, 128],
+        "navy" => [0, 0, 128],
+        "purple" => [128, 0, 128],
+        "orange" => [255, 165, 0],
+        "pink" => [255, 192, 203],
+        "brown" => [165, 42, 42],
+        "gold" => [255, 215, 0],
+        "indigo" => [75, 0, 130],
+        "violet" => [238, 130, 238],
+        _ => return None,
+    };
+    Some(Rgba([rgb[0], rgb[1], rgb[2], 255]))
+}
+
+/// WCAG relative luminance (0 = black, 1 = white)
+pub fn relative_luminance(rgba: Rgba<u8>) -> f32 {
+    0.2126 * srgb_to_linear(rgba[0])
+        + 0.7152 * srgb_to_linear(rgba[1])
+        + 0.0722 * srgb_to_linear(rgba[2])
+}
+
+fn srgb_to_linear(channel: u8) -> f32 {
+    let c = channel as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(linear: f32) -> u8 {
+    let l = linear.clamp(0.0, 1.0);
+    let c = if l <= 0.003_130_8 {
+        l * 12.92
+    } else {
+        1.055 * l.powf(1.0 / 2.4) - 0.055
+    };
+    (c * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+/// CIE L*a*b* color (D65 white point; l: 0-100, a/b roughly -128..128)
+#[derive(Debug, Clone, Copy)]
+pub struct Lab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+pub fn rgb_to_lab(rgba: Rgba<u8>) -> Lab {
+    let r = srgb_to_linear(rgba[0]);
+    let g = srgb_to_linear(rgba[1]);
+    let b = srgb_to_linear(rgba[2]);
+
+    // Linear sRGB -> XYZ, normalized by the D65 reference white
+    let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+    let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+
+    let f = |t: f32| -> f32 {
+        if t > 216.0 / 24389.0 {
+            t.cbrt()
+        } else {
+            (24389.0 / 27.0 * t + 16.0) / 116.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+
+    Lab {
+        l: 116.0 * fy - 16.0,
+        a: 500.0 * (fx - fy),
+        b: 200.0 * (fy - fz),
+    }
+}
+
+/// CIE76 color difference; ~2.3 is a just-noticeable difference
+pub fn delta_e(a: Rgba<u8>, b: Rgba<u8>) -> f32 {
+    let la = rgb_to_lab(a);
+    let lb = rgb_to_lab(b);
+    ((la.l - lb.l).powi(2) + (la.a - lb.a).powi(2) + (la.b - lb.b).powi(2)).sqrt()
+}
+
+/// WCAG contrast ratio between two colors (1.0 = identical, 21.0 = black on white)
+pub fn contrast_ratio(a: Rgba<u8>, b: Rgba<u8>) -> f32 {
+    let la = relative_luminance(a);
+    let lb = relative_luminance(b);
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Black or white, whichever has the higher contrast ratio against a backdrop of the given
+/// relative luminance
+pub fn contrasting_text_color(luminance: f32) -> Rgba<u8> {
+    let on_white = 1.05 / (luminance + 0.05);
+    let on_black = (luminance + 0.05) / 0.05;
+    if on_white >= on_black {
+        Rgba([255, 255, 255, 255])
+    } else {
+        Rgba([0, 0, 0, 255])
+    }
+}
+
 pub fn lerp_color(a: Rgba<u8>, b: Rgba<u8>, t: f32) -> Rgba<u8> {
     let clamped = t.clamp(0.0, 1.0);
     Rgba([
@@ -151,8 +276,97 @@ pub fn lerp_color(a: Rgba<u8>, b: Rgba<u8>, t: f32) -> Rgba<u8> {
     ])
 }
 
+/// Like [`lerp_color`], but mixes the color channels in linear light, so the midpoint
+/// between two saturated colors keeps its brightness instead of dipping into a muddy band.
+/// Alpha is still mixed directly.
+pub fn lerp_color_linear(a: Rgba<u8>, b: Rgba<u8>, t: f32) -> Rgba<u8> {
+    let clamped = t.clamp(0.0, 1.0);
+    let mix = |channel: usize| {
+        let from = srgb_to_linear(a[channel]);
+        let to = srgb_to_linear(b[channel]);
+        linear_to_srgb(from + (to - from) * clamped)
+    };
+    Rgba([mix(0), mix(1), mix(2), lerp_channel(a[3], b[3], clamped)])
+}
+
 fn lerp_channel(a: u8, b: u8, t: f32) -> u8 {
     ((a as f32) + ((b as f32) - (a as f32)) * t)
         .round()
         .clamp(0.0, 255.0) as u8
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contrast_ratio_spans_wcag_range() {
+        let black = Rgba([0, 0, 0, 255]);
+        let white = Rgba([255, 255, 255, 255]);
+        assert!((contrast_ratio(black, white) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio(white, black) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio(black, black) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn contrasting_text_color_flips_at_mid_luminance() {
+        let white = Rgba([255, 255, 255, 255]);
+        let black = Rgba([0, 0, 0, 255]);
+        assert_eq!(contrasting_text_color(0.0), white);
+        assert_eq!(contrasting_text_color(0.15), white);
+        assert_eq!(contrasting_text_color(0.2), black);
+        assert_eq!(
+            contrasting_text_color(relative_luminance(Rgba([169, 231, 255, 255]))),
+            black
+        );
+    }
+
+    #[test]
+    fn linear_lerp_keeps_midpoints_bright() {
+        let red = Rgba([255, 0, 0, 255]);
+        let green = Rgba([0, 255, 0, 255]);
+        assert_eq!(lerp_color(red, green, 0.5), Rgba([128, 128, 0, 255]));
+        assert_eq!(lerp_color_linear(red, green, 0.5), Rgba([188, 188, 0, 255]));
+        assert_eq!(lerp_color_linear(red, green, 0.0), red);
+        assert_eq!(lerp_color_linear(red, green, 1.0), green);
+    }
+
+    #[test]
+    fn parses_shorthand_hex_and_named_colors() {
+        assert_eq!(
+            parse_hex_rgba("#FAB").unwrap(),
+            Rgba([0xFF, 0xAA, 0xBB, 255])
+        );
+        assert_eq!(
+            parse_hex_rgba("#fab8").unwrap(),
+            Rgba([0xFF, 0xAA, 0xBB, 0x88])
+        );
+        assert_eq!(
+            parse_hex_rgba("#0A84FF").unwrap(),
+            Rgba([10, 132, 255, 255])
+        );
+        assert_eq!(
+            parse_hex_rgba(" White ").unwrap(),
+            Rgba([255, 255, 255, 255])
+        );
+        assert_eq!(parse_hex_rgba("transparent").unwrap()[3], 0);
+        for invalid in ["#GGG", "#12345", "notacolor", "#ZZZZZZ"] {
+            let err = parse_hex_rgba(invalid).unwrap_err().to_string();
+            assert!(err.contains("expected #RRGGBB"), "{invalid}: {err}");
+        }
+    }
+
+    #[test]
+    fn delta_e_follows_perception_not_rgb_distance() {
+        let white = rgb_to_lab(Rgba([255, 255, 255, 255]));
+        assert!((white.l - 100.0).abs() < 0.1);
+        assert!(white.a.abs() < 0.1 && white.b.abs() < 0.1);
+
+        // Equal RGB steps: a green shift reads as a much bigger change than a blue one
+        let base = Rgba([100, 100, 100, 255]);
+        let greener = Rgba([100, 140, 100, 255]);
+        let bluer = Rgba([100, 100, 140, 255]);
+        assert!(delta_e(base, greener) > delta_e(base, bluer));
+        assert_eq!(delta_e(base, base), 0.0);
+    }
+}
@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use image::imageops::{self, FilterType};
+use image::{Rgba, RgbaImage};
+
+use crate::compose::draw_centered_label;
+use crate::devices::DEVICE_LISTINGS;
+use crate::snap::{SnapConfig, frame_screenshot};
+
+const TILE_WIDTH: u32 = 540;
+const TILE_GAP_PX: u32 = 48;
+const LABEL_HEIGHT_PX: u32 = 96;
+const LABEL_SIZE_PX: f32 = 36.0;
+const MONTAGE_BACKGROUND: Rgba<u8> = Rgba([11, 14, 20, 255]);
+const LABEL_COLOR: Rgba<u8> = Rgba([232, 236, 242, 255]);
+
+pub struct CompareSummary {
+    pub output_path: PathBuf,
+    pub model_count: usize,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Frame one screenshot in every built-in phone model and tile the results side by side
+pub fn compare_models(image_path: &Path, output_path: &Path) -> Result<CompareSummary> {
+    let raw_img = image::open(image_path)
+        .with_context(|| format!("failed to open screenshot {}", image_path.display()))?;
+
+    let config = SnapConfig::default();
+    let tile_height =
+        (TILE_WIDTH as f32 * config.height as f32 / config.width as f32).round() as u32;
+    let model_count = DEVICE_LISTINGS.len() as u32;
+    let width = TILE_GAP_PX + model_count * (TILE_WIDTH + TILE_GAP_PX);
+    let height = TILE_GAP_PX * 2 + tile_height + LABEL_HEIGHT_PX;
+    let mut montage = RgbaImage::from_pixel(width, height, MONTAGE_BACKGROUND);
+
+    for (index, device) in DEVICE_LISTINGS.iter().enumerate() {
+        let framed = frame_screenshot(
+            &raw_img,
+            image_path,
+            &format!("{}.png", device.slug),
+            &config,
+            Some(device.model),
+        )
+        .with_context(|| format!("failed to frame screenshot as {}", device.display_name))?;
+        let tile = imageops::resize(&framed, TILE_WIDTH, tile_height, FilterType::Lanczos3);
+
+        let tile_x = TILE_GAP_PX + index as u32 * (TILE_WIDTH + TILE_GAP_PX);
+        imageops::overlay(&mut montage, &tile, tile_x as i64, TILE_GAP_PX as i64);
+
+        let label_y = TILE_GAP_PX + tile_height + (LABEL_HEIGHT_PX - LABEL_SIZE_PX as u32) / 2;
+        draw_centered_label(
+            &mut montage,
+            device.display_name,
+            (tile_x + TILE_WIDTH / 2) as i32,
+            label_y as i32,
+            LABEL_SIZE_PX,
+            LABEL_COLOR,
+        )?;
+    }
+
+    if let Some(parent) = output_path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create output directory {}", parent.display()))?;
+    }
+    montage
+        .save(output_path)
+        .with_context(|| format!("failed to save montage {}", output_path.display()))?;
+
+    Ok(CompareSummary {
+        output_path: output_path.to_path_buf(),
+        model_count: DEVICE_LISTINGS.len(),
+        width,
+        height,
+    })
+}
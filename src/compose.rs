@@ -1,15 +1,27 @@
-use std::collections::VecDeque;
-use std::path::Path;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 
-use ab_glyph::{Font, FontRef, PxScale, ScaleFont};
+use ab_glyph::{Font, FontArc, PxScale, ScaleFont};
 use anyhow::{Context, Result, bail};
 use image::imageops::{FilterType, crop_imm};
 use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
-
-use crate::color::parse_hex_rgba;
-use crate::config::{CopyConfig, FontWeight, PhoneConfig, SceneConfig, TextPosition};
-use crate::devices::{DynamicIslandSpec, resolve_phone_style};
-use crate::frames::resolve_overlay_for_compose;
+use qrcode::{Color as QrColor, QrCode};
+
+use crate::color::{
+    contrast_ratio, contrasting_text_color, lerp_color, parse_hex_rgba, relative_luminance,
+};
+use crate::config::{
+    CopyConfig, Corner, DEFAULT_LINE_HEIGHT, DeviceProfileConfig, FontWeight, GradientDirection,
+    LogoConfig, ModelRef, Orientation, PhoneConfig, SceneConfig, StatusBarConfig, TextAlign,
+    TextPosition,
+};
+use crate::devices::{
+    DynamicIslandSpec, ResolvedPhoneStyle, inset_screen_rect, resolve_phone_style,
+};
+use crate::filters::{box_blur, duotone};
+use crate::frames::{ResolvedOverlay, resolve_overlay_for_compose};
+use crate::warp::{Homography, warp_layer};
 
 // Embed Geist fonts directly in the binary
 static GEIST_REGULAR: &[u8] = include_bytes!("../assets/fonts/Geist-Regular.ttf");
@@ -21,72 +33,439 @@ const OVERLAY_CUTOUT_ALPHA_MAX: u8 = 254;
 const OVERLAY_CUTOUT_GUARD_PX: i32 = 0;
 const OVERLAY_SEMITRANSPARENT_EXPAND_STEPS: usize = 0;
 const OVERLAY_SEMITRANSPARENT_LUMA_MAX: u16 = 30;
+const RIM_LIGHT_WIDTH_PX: u32 = 3;
+const RIM_SAMPLE_BAND_PX: u32 = 24;
+const QR_QUIET_ZONE_MODULES: u32 = 2;
+const QR_CORNER_MARGIN_PX: u32 = 48;
+// Home indicator size and placement relative to the screen rect
+const HOME_INDICATOR_WIDTH_RATIO: f32 = 0.35;
+const HOME_INDICATOR_HEIGHT_RATIO: f32 = 0.006;
+const HOME_INDICATOR_BOTTOM_RATIO: f32 = 0.009;
+// Status bar geometry, in points of a 402pt-wide iPhone screen
+const STATUS_BAR_SCREEN_POINTS: f32 = 402.0;
+const STATUS_BAR_HEIGHT_PT: f32 = 54.0;
+const STATUS_BAR_TIME_PT: f32 = 17.0;
+// Where the time and the right cluster sit within their ear, measured from the screen edge
+const STATUS_BAR_EAR_CENTER: f32 = 0.55;
+// Share of the device height the reflection takes to fade out completely
+const REFLECTION_FADE_RATIO: f32 = 0.4;
+// Strongest perspective tilt; the receding edge never shrinks below 10% of its length
+const MAX_TILT: f32 = 0.9;
+// Bullet geometry as fractions of the subheadline size: text indent past the dot's left
+// edge, dot radius, and how far above the baseline the dot sits (about half the x-height)
+const BULLET_INDENT_RATIO: f32 = 0.85;
+const BULLET_DOT_RADIUS_RATIO: f32 = 0.14;
+const BULLET_DOT_RISE_RATIO: f32 = 0.26;
+
+/// Separately rendered compose layers, bottom to top. All layers share the
+/// canvas size; everything except the background is transparent where unused.
+pub struct ComposeLayers {
+    pub background: RgbaImage,
+    pub text: RgbaImage,
+    /// One set per device in draw order, each stacked fully above the last
+    pub devices: Vec<DeviceLayers>,
+}
+
+impl ComposeLayers {
+    /// Layers with their export names. The first device's layers are `frame`, `screenshot`
+    /// and `overlay`; device N's get a `.phone<N>` suffix, like its raw screenshot.
+    fn named(&self) -> Vec<(String, &RgbaImage)> {
+        let mut named = vec![
+            ("background".to_string(), &self.background),
+            ("text".to_string(), &self.text),
+        ];
+        for (index, device) in self.devices.iter().enumerate() {
+            let suffix = match index {
+                0 => String::new(),
+                n => format!(".phone{}", n),
+            };
+            named.extend([
+                (format!("frame{}", suffix), &device.frame),
+                (format!("screenshot{}", suffix), &device.screenshot),
+                (format!("overlay{}", suffix), &device.overlay),
+            ]);
+        }
+        named
+    }
+
+    /// Alpha-composite all layers into the final image
+    pub fn flatten(&self) -> RgbaImage {
+        let mut out = self.background.clone();
+        for (_, layer) in &self.named()[1..] {
+            blend_layer(&mut out, layer);
+        }
+        out
+    }
+
+    /// Write each layer as `<dir>/<name>.png`
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed creating {}", dir.display()))?;
+        for (name, layer) in self.named() {
+            let path = dir.join(format!("{}.png", name));
+            layer
+                .save(&path)
+                .with_context(|| format!("failed writing layer {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
 
+/// Compose a scene from one screenshot per device (see `SceneConfig::devices`), drawing
+/// straight onto `background`. Only a tilted or reflected device gets a scratch layer.
 pub fn compose_scene(
-    screenshot: &DynamicImage,
+    screenshots: &[DynamicImage],
     scene: &SceneConfig,
-    mut background: RgbaImage,
+    background: RgbaImage,
     config_dir: &Path,
+    fonts: &HashMap<FontWeight, PathBuf>,
+    devices: &HashMap<String, DeviceProfileConfig>,
 ) -> Result<RgbaImage> {
+    check_screenshot_count(screenshots, scene)?;
+    let duotone = Duotone::for_scene(scene)?;
+    // Keeping the screenshots out of the duotone needs them on a layer of their own
+    if let Some(duotone) = &duotone
+        && duotone.preserve_screen
+    {
+        return Ok(
+            compose_layers(screenshots, scene, background, config_dir, fonts, devices)?.flatten(),
+        );
+    }
+
+    let mut canvas = background;
+    // Rim lights are judged against the bare background, so sample it before drawing
+    let rim_backdrops: Vec<_> = scene
+        .devices()
+        .map(|(_, phone)| rim_backdrop(&canvas, phone))
+        .collect();
+
     if let Some(copy) = &scene.copy {
-        draw_copy(&mut background, copy, &scene.phone)?;
+        let headline = copy.headline.select(scene.background.seed);
+        draw_copy(
+            &mut canvas,
+            None,
+            copy,
+            headline,
+            scene.phone(),
+            config_dir,
+            fonts,
+        )?;
     }
 
-    let phone = &scene.phone;
-    if phone.width == 0 || phone.height == 0 {
-        bail!("scene '{}' has invalid phone size", scene.id);
+    let (canvas_w, canvas_h) = canvas.dimensions();
+    for ((screenshot, (_, phone)), rim_backdrop) in
+        screenshots.iter().zip(scene.devices()).zip(rim_backdrops)
+    {
+        if phone.reflection || device_transform(phone).is_some() {
+            let mut device = RgbaImage::new(canvas_w, canvas_h);
+            draw_device(
+                &mut device,
+                rim_backdrop,
+                screenshot,
+                phone,
+                &scene.id,
+                config_dir,
+                devices,
+            )?;
+            transform_device(&mut device, phone);
+            reflect_device(&mut device, phone);
+            blend_layer(&mut canvas, &device);
+        } else {
+            draw_device(
+                &mut canvas,
+                rim_backdrop,
+                screenshot,
+                phone,
+                &scene.id,
+                config_dir,
+                devices,
+            )?;
+        }
     }
 
-    let style = resolve_phone_style(phone);
-    let overlay = resolve_overlay_for_compose(scene, config_dir);
+    if let Some(duotone) = &duotone {
+        duotone.apply(&mut canvas);
+    }
+    Ok(canvas)
+}
 
-    // Only draw programmatic frame if no overlay is provided
-    if overlay.is_none() {
-        let frame_color = parse_hex_rgba(&style.frame_color)?;
+/// Compose a scene like [`compose_scene`], keeping the background, the copy and each
+/// device's frame, screenshot and overlay on layers of their own
+pub fn compose_layers(
+    screenshots: &[DynamicImage],
+    scene: &SceneConfig,
+    background: RgbaImage,
+    config_dir: &Path,
+    fonts: &HashMap<FontWeight, PathBuf>,
+    devices: &HashMap<String, DeviceProfileConfig>,
+) -> Result<ComposeLayers> {
+    check_screenshot_count(screenshots, scene)?;
 
-        let shadow_y = phone.y as i32 + style.shadow_offset_y;
-        fill_rounded_rect(
-            &mut background,
-            phone.x as i32,
-            shadow_y,
-            phone.width,
-            phone.height,
-            style.corner_radius,
-            Rgba([0, 0, 0, style.shadow_alpha]),
-        );
+    let (canvas_w, canvas_h) = background.dimensions();
+    let mut text_layer = RgbaImage::new(canvas_w, canvas_h);
 
-        fill_rounded_rect(
-            &mut background,
-            phone.x as i32,
-            phone.y as i32,
-            phone.width,
-            phone.height,
-            style.corner_radius,
-            frame_color,
-        );
-        draw_frame_tones(
-            &mut background,
-            phone.x as i32,
-            phone.y as i32,
-            phone.width,
-            phone.height,
-            style.corner_radius,
+    if let Some(copy) = &scene.copy {
+        let headline = copy.headline.select(scene.background.seed);
+        draw_copy(
+            &mut text_layer,
+            Some(&background),
+            copy,
+            headline,
+            scene.phone(),
+            config_dir,
+            fonts,
+        )?;
+    }
+
+    let duotone = Duotone::for_scene(scene)?;
+    let mut device_layers = Vec::with_capacity(screenshots.len());
+    for (screenshot, (_, phone)) in screenshots.iter().zip(scene.devices()) {
+        let mut layers = DeviceLayers::new(canvas_w, canvas_h);
+        draw_device(
+            &mut layers,
+            rim_backdrop(&background, phone),
+            screenshot,
+            phone,
+            &scene.id,
+            config_dir,
+            devices,
+        )?;
+        transform_device(&mut layers, phone);
+        reflect_device(&mut layers, phone);
+        if let Some(duotone) = &duotone {
+            duotone.apply_to_device(&mut layers);
+        }
+        device_layers.push(layers);
+    }
+
+    let mut background = background;
+    if let Some(duotone) = &duotone {
+        duotone.apply(&mut background);
+        duotone.apply(&mut text_layer);
+    }
+
+    Ok(ComposeLayers {
+        background,
+        text: text_layer,
+        devices: device_layers,
+    })
+}
+
+fn check_screenshot_count(screenshots: &[DynamicImage], scene: &SceneConfig) -> Result<()> {
+    let device_count = scene.devices().count();
+    if screenshots.len() != device_count {
+        bail!(
+            "scene '{}' has {} device(s) but {} screenshot(s)",
+            scene.id,
+            device_count,
+            screenshots.len()
         );
     }
+    Ok(())
+}
+
+/// Alpha-composite a full-canvas `layer` onto `image`
+fn blend_layer(image: &mut RgbaImage, layer: &RgbaImage) {
+    for (x, y, pixel) in layer.enumerate_pixels() {
+        if pixel[3] > 0 {
+            blend_pixel(image, x as i32, y as i32, *pixel);
+        }
+    }
+}
+
+/// The scene's `post.duotone` pass. Layers are mapped one by one, which matches mapping the
+/// flattened image: the mapping keeps alpha and is affine in color, so it commutes with
+/// blending.
+struct Duotone {
+    shadow: Rgba<u8>,
+    highlight: Rgba<u8>,
+    preserve_screen: bool,
+}
+
+impl Duotone {
+    fn for_scene(scene: &SceneConfig) -> Result<Option<Self>> {
+        let Some(post) = &scene.post else {
+            return Ok(None);
+        };
+        let Some([shadow, highlight]) = &post.duotone else {
+            return Ok(None);
+        };
+        let parse = |raw: &String| {
+            parse_hex_rgba(raw).with_context(|| {
+                format!(
+                    "scene '{}' has invalid post.duotone color '{}'",
+                    scene.id, raw
+                )
+            })
+        };
+        Ok(Some(Self {
+            shadow: parse(shadow)?,
+            highlight: parse(highlight)?,
+            preserve_screen: post.preserve_screen,
+        }))
+    }
+
+    fn apply(&self, image: &mut RgbaImage) {
+        duotone(image, self.shadow, self.highlight);
+    }
+
+    fn apply_to_device(&self, layers: &mut DeviceLayers) {
+        self.apply(&mut layers.frame);
+        self.apply(&mut layers.overlay);
+        if !self.preserve_screen {
+            self.apply(&mut layers.screenshot);
+        }
+    }
+}
+
+/// Frame, screenshot and overlay layers for a single device
+pub struct DeviceLayers {
+    pub frame: RgbaImage,
+    pub screenshot: RgbaImage,
+    pub overlay: RgbaImage,
+}
+
+impl DeviceLayers {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            frame: RgbaImage::new(width, height),
+            screenshot: RgbaImage::new(width, height),
+            overlay: RgbaImage::new(width, height),
+        }
+    }
+}
 
+/// Where a device is drawn: onto separate frame, screenshot and overlay layers, or all onto
+/// one image
+trait DeviceCanvas {
+    fn frame(&mut self) -> &mut RgbaImage;
+    fn screenshot(&mut self) -> &mut RgbaImage;
+    fn overlay(&mut self) -> &mut RgbaImage;
+    /// Each distinct image, bottom to top
+    fn images(&mut self) -> Vec<&mut RgbaImage>;
+}
+
+impl DeviceCanvas for DeviceLayers {
+    fn frame(&mut self) -> &mut RgbaImage {
+        &mut self.frame
+    }
+
+    fn screenshot(&mut self) -> &mut RgbaImage {
+        &mut self.screenshot
+    }
+
+    fn overlay(&mut self) -> &mut RgbaImage {
+        &mut self.overlay
+    }
+
+    fn images(&mut self) -> Vec<&mut RgbaImage> {
+        vec![&mut self.frame, &mut self.screenshot, &mut self.overlay]
+    }
+}
+
+impl DeviceCanvas for RgbaImage {
+    fn frame(&mut self) -> &mut RgbaImage {
+        self
+    }
+
+    fn screenshot(&mut self) -> &mut RgbaImage {
+        self
+    }
+
+    fn overlay(&mut self) -> &mut RgbaImage {
+        self
+    }
+
+    fn images(&mut self) -> Vec<&mut RgbaImage> {
+        vec![self]
+    }
+}
+
+/// Tilt and rotate every layer together so the frame, its shadow and the screenshot
+/// stay registered
+fn transform_device(canvas: &mut impl DeviceCanvas, phone: &PhoneConfig) {
+    let Some(transform) = device_transform(phone) else {
+        return;
+    };
+    for layer in canvas.images() {
+        *layer = warp_layer(layer, &transform);
+    }
+}
+
+/// Mirror the finished device below itself, fading out downward. The reflection goes
+/// into the frame layer so it stays under everything else the device draws.
+fn reflect_device(canvas: &mut impl DeviceCanvas, phone: &PhoneConfig) {
+    if !phone.reflection {
+        return;
+    }
+    let (canvas_w, canvas_h) = canvas.frame().dimensions();
+    let (left, top, right, bottom) = device_bounds(phone);
+    let left = left.clamp(0, canvas_w as i32);
+    let right = right.clamp(0, canvas_w as i32);
+    let top = top.clamp(0, canvas_h as i32);
+    let bottom = bottom.clamp(0, canvas_h as i32);
+    if right <= left || bottom <= top {
+        return;
+    }
+
+    let mut device = RgbaImage::new((right - left) as u32, (bottom - top) as u32);
+    for layer in canvas.images() {
+        for y in 0..device.height() {
+            for x in 0..device.width() {
+                let src = *layer.get_pixel(left as u32 + x, top as u32 + y);
+                if src[3] > 0 {
+                    blend_pixel(&mut device, x as i32, y as i32, src);
+                }
+            }
+        }
+    }
+
+    let opacity = phone.reflection_opacity.clamp(0.0, 1.0);
+    let fade = ((bottom - top) as f32 * REFLECTION_FADE_RATIO).max(1.0);
+    let frame = canvas.frame();
+    for row in 0..fade.ceil() as i32 {
+        let dest_y = bottom + phone.reflection_gap as i32 + row;
+        let src_y = device.height() as i32 - 1 - row;
+        if dest_y >= canvas_h as i32 || src_y < 0 {
+            break;
+        }
+        let strength = opacity * (1.0 - row as f32 / fade);
+        for x in 0..device.width() {
+            let pixel = device.get_pixel(x, src_y as u32);
+            let alpha = (pixel[3] as f32 * strength).round() as u8;
+            if alpha > 0 {
+                let color = Rgba([pixel[0], pixel[1], pixel[2], alpha]);
+                blend_pixel(frame, left + x as i32, dest_y, color);
+            }
+        }
+    }
+}
+
+/// Canvas rect the screenshot is fitted into, before any tilt or rotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScreenRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Taken from the overlay's transparent cutout rather than the style's insets
+    pub from_cutout: bool,
+}
+
+/// Where `phone`'s screenshot goes: inside the overlay's screen cutout when the overlay has
+/// one, otherwise inset from the phone rect by the resolved screen padding and border
+pub fn device_screen_rect(
+    phone: &PhoneConfig,
+    style: &ResolvedPhoneStyle,
+    overlay: Option<&ResolvedOverlay>,
+) -> Result<ScreenRect> {
     let overlay_screen = overlay
-        .as_ref()
         .map(|ov| detect_overlay_screen_region(&ov.path))
         .transpose()
-        .with_context(|| {
-            format!(
-                "scene '{}' failed detecting overlay screen region",
-                scene.id
-            )
-        })?
+        .context("failed detecting overlay screen region")?
         .flatten();
 
-    let (screen_x, screen_y, screen_w, screen_h) = if let Some(region) = overlay_screen {
+    if let Some(region) = overlay_screen {
         // Derive the display area from the actual transparent cutout in the overlay.
         let sx = phone.width as f32 / region.overlay_width as f32;
         let sy = phone.height as f32 / region.overlay_height as f32;
@@ -101,74 +480,182 @@ pub fn compose_scene(
         let mut screen_h = ((region.height as f32 * sy).round() as u32).max(1);
 
         // Keep the screenshot a few pixels inside the cutout to avoid a "glued" edge look.
-        let inset = OVERLAY_INNER_INSET_PX
-            .min(screen_w.saturating_sub(1) / 2)
-            .min(screen_h.saturating_sub(1) / 2);
+        let inset = OVERLAY_INNER_INSET_PX;
         if inset > 0 {
             screen_x = screen_x.saturating_add(inset);
             screen_y = screen_y.saturating_add(inset);
-            screen_w = screen_w.saturating_sub(inset.saturating_mul(2));
-            screen_h = screen_h.saturating_sub(inset.saturating_mul(2));
+            screen_w = screen_w.saturating_sub(inset.saturating_mul(2)).max(1);
+            screen_h = screen_h.saturating_sub(inset.saturating_mul(2)).max(1);
         }
 
-        (screen_x, screen_y, screen_w, screen_h)
-    } else {
-        // Fall back to model-based insets when no transparent overlay cutout is available.
-        let (inset_adjust_top, inset_adjust_side) = if overlay.is_some() {
-            use crate::config::PhoneModel;
-            match phone.model {
-                Some(PhoneModel::Iphone17ProMax) => (10, 5),
-                _ => (0, 0),
-            }
-        } else {
-            (0, 0)
-        };
+        return Ok(ScreenRect {
+            x: screen_x,
+            y: screen_y,
+            width: screen_w,
+            height: screen_h,
+            from_cutout: true,
+        });
+    }
+
+    // Fall back to model-based insets when no transparent overlay cutout is available.
+    let Some((inset_x, inset_y, width, height)) =
+        inset_screen_rect(phone, style, overlay.is_some())
+    else {
+        bail!("phone insets leave no space for screenshot");
+    };
+    Ok(ScreenRect {
+        x: phone.x.saturating_add(inset_x),
+        y: phone.y.saturating_add(inset_y),
+        width,
+        height,
+        from_cutout: false,
+    })
+}
+
+/// Perspective tilt followed by in-plane rotation, or `None` when the device is drawn flat
+fn device_transform(phone: &PhoneConfig) -> Option<Homography> {
+    let (x, y) = (phone.x as f64, phone.y as f64);
+    let (w, h) = (phone.width as f64, phone.height as f64);
+    let mut transform = Homography::IDENTITY;
+
+    if let Some(perspective) = phone.perspective
+        && (perspective.x != 0.0 || perspective.y != 0.0)
+    {
+        // Pull the receding edge's ends toward its middle by half the tilt each
+        let tilt_x = perspective.x.clamp(-MAX_TILT, MAX_TILT) as f64;
+        let tilt_y = perspective.y.clamp(-MAX_TILT, MAX_TILT) as f64;
+        let (left, right) = (h * (-tilt_x).max(0.0) / 2.0, h * tilt_x.max(0.0) / 2.0);
+        let (top, bottom) = (w * tilt_y.max(0.0) / 2.0, w * (-tilt_y).max(0.0) / 2.0);
+        let rect = [(x, y), (x + w, y), (x + w, y + h), (x, y + h)];
+        let quad = [
+            (x + top, y + left),
+            (x + w - top, y + right),
+            (x + w - bottom, y + h - right),
+            (x + bottom, y + h - left),
+        ];
+        transform = Homography::from_quads(rect, quad)?;
+    }
+
+    if phone.rotation_degrees != 0.0 {
+        let rotation =
+            Homography::rotation(x + w / 2.0, y + h / 2.0, phone.rotation_degrees as f64);
+        transform = rotation.then_after(&transform);
+    }
+
+    (transform != Homography::IDENTITY).then_some(transform)
+}
+
+/// Canvas-space box around the phone rect after any tilt or rotation, as
+/// (left, top, right, bottom) with exclusive right/bottom edges
+fn device_bounds(phone: &PhoneConfig) -> (i32, i32, i32, i32) {
+    let (x, y) = (phone.x as f64, phone.y as f64);
+    let (w, h) = (phone.width as f64, phone.height as f64);
+    let corners = [(x, y), (x + w, y), (x + w, y + h), (x, y + h)];
+    let transform = device_transform(phone).unwrap_or(Homography::IDENTITY);
+    let (mut left, mut top, mut right, mut bottom) = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+    for (cx, cy) in corners {
+        let (tx, ty) = transform.apply(cx, cy);
+        left = left.min(tx);
+        top = top.min(ty);
+        right = right.max(tx);
+        bottom = bottom.max(ty);
+    }
+    (
+        left.floor() as i32,
+        top.floor() as i32,
+        right.ceil() as i32,
+        bottom.ceil() as i32,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_device(
+    layers: &mut impl DeviceCanvas,
+    rim_backdrop: Option<Rgba<u8>>,
+    screenshot: &DynamicImage,
+    phone: &PhoneConfig,
+    scene_id: &str,
+    config_dir: &Path,
+    devices: &HashMap<String, DeviceProfileConfig>,
+) -> Result<()> {
+    if phone.width == 0 || phone.height == 0 {
+        bail!("scene '{}' has invalid phone size", scene_id);
+    }
+
+    let style = resolve_phone_style(phone, devices);
+    let model = phone.model.as_ref().and_then(ModelRef::built_in);
+    let overlay = resolve_overlay_for_compose(phone, config_dir);
+
+    if let Some(raw) = &phone.glow_color {
+        let glow_color = parse_hex_rgba(raw)
+            .with_context(|| format!("scene '{}' has invalid glow_color '{}'", scene_id, raw))?;
+        draw_glow(layers.frame(), phone, style.corner_radius, glow_color);
+    }
+
+    if let Some(surround) = rim_backdrop {
+        let frame_color = parse_hex_rgba(&style.frame_color)?;
+        draw_rim_light_if_needed(
+            layers.frame(),
+            surround,
+            phone,
+            style.corner_radius,
+            frame_color,
+            phone.min_frame_contrast,
+        );
+    }
+
+    // Only draw programmatic frame if no overlay is provided
+    if overlay.is_none() {
+        let frame_color = parse_hex_rgba(&style.frame_color)?;
 
-        let inset_left = style
-            .screen_padding
-            .left
-            .saturating_add(style.frame_border_width)
-            .saturating_sub(inset_adjust_side);
-        let inset_right = style
-            .screen_padding
-            .right
-            .saturating_add(style.frame_border_width)
-            .saturating_sub(inset_adjust_side);
-        let inset_top = style
-            .screen_padding
-            .top
-            .saturating_add(style.frame_border_width)
-            .saturating_sub(inset_adjust_top);
-        let inset_bottom = style
-            .screen_padding
-            .bottom
-            .saturating_add(style.frame_border_width);
-
-        let screen_w = phone
-            .width
-            .saturating_sub(inset_left.saturating_add(inset_right));
-        let screen_h = phone
-            .height
-            .saturating_sub(inset_top.saturating_add(inset_bottom));
-        if screen_w == 0 || screen_h == 0 {
-            bail!(
-                "scene '{}' phone insets leave no space for screenshot",
-                scene.id
+        if phone.shadow_blur == 0 && phone.shadow_spread == 0 {
+            fill_rounded_rect(
+                layers.frame(),
+                phone.x as i32,
+                phone.y as i32 + style.shadow_offset_y,
+                phone.width,
+                phone.height,
+                style.corner_radius,
+                Rgba([0, 0, 0, style.shadow_alpha]),
             );
+        } else {
+            draw_soft_shadow(layers.frame(), phone, &style);
         }
-        let screen_x = phone.x.saturating_add(inset_left);
-        let screen_y = phone.y.saturating_add(inset_top);
-        (screen_x, screen_y, screen_w, screen_h)
-    };
+
+        fill_rounded_rect(
+            layers.frame(),
+            phone.x as i32,
+            phone.y as i32,
+            phone.width,
+            phone.height,
+            style.corner_radius,
+            frame_color,
+        );
+        draw_frame_tones(
+            layers.frame(),
+            phone.x as i32,
+            phone.y as i32,
+            phone.width,
+            phone.height,
+            style.corner_radius,
+        );
+    }
+
+    let screen = device_screen_rect(phone, &style, overlay.as_ref())
+        .with_context(|| format!("scene '{}' has no room for its screenshot", scene_id))?;
+    let (screen_x, screen_y, screen_w, screen_h) =
+        (screen.x, screen.y, screen.width, screen.height);
 
     // When using overlay, use corner radius that fits within the frame's screen cutout
     // Each device model has a different frame geometry requiring a specific radius
     // Pro Max frames (1520x3068) have different geometry than Pro frames (1406x2822)
     let screenshot_radius = if overlay.is_some() {
         use crate::config::PhoneModel;
-        let ratio = match phone.model {
+        let ratio = match model {
             Some(PhoneModel::Iphone17Pro) => 0.145,
             Some(PhoneModel::Iphone17ProMax) => 0.155,
+            // iPad screens are much squarer than iPhone screens
+            Some(PhoneModel::IpadPro11 | PhoneModel::IpadPro13) => 0.06,
             _ => 0.145,
         };
         (phone.width as f32 * ratio).round() as u32
@@ -178,16 +665,21 @@ pub fn compose_scene(
             .saturating_sub(style.frame_border_width + 2)
     };
 
-    let fitted = resize_cover(screenshot, screen_w, screen_h);
+    let mut fitted = resize_cover(screenshot, screen_w, screen_h);
+    if let Some(status_bar) = &phone.status_bar
+        && status_bar.cover
+    {
+        cover_status_bar(&mut fitted);
+    }
     if let Some(ref ov) = overlay {
-        if overlay_screen.is_some() {
+        if screen.from_cutout {
             let overlay_mask = image::open(&ov.path)
                 .with_context(|| format!("failed opening overlay {}", ov.path.display()))?
                 .resize_exact(phone.width, phone.height, FilterType::Lanczos3)
                 .to_rgba8();
             let cutout_mask = build_inner_cutout_mask(&overlay_mask);
             blit_with_overlay_cutout(
-                &mut background,
+                layers.screenshot(),
                 &fitted,
                 screen_x as i32,
                 screen_y as i32,
@@ -199,7 +691,7 @@ pub fn compose_scene(
             );
         } else {
             blit_rounded(
-                &mut background,
+                layers.screenshot(),
                 &fitted,
                 screen_x as i32,
                 screen_y as i32,
@@ -208,7 +700,7 @@ pub fn compose_scene(
         }
     } else {
         blit_rounded(
-            &mut background,
+            layers.screenshot(),
             &fitted,
             screen_x as i32,
             screen_y as i32,
@@ -216,20 +708,52 @@ pub fn compose_scene(
         );
     }
 
+    if let Some(status_bar) = &phone.status_bar {
+        draw_status_bar(
+            layers.screenshot(),
+            &fitted,
+            screen_x as i32,
+            screen_y as i32,
+            status_bar,
+            // The island sits beside a landscape status bar rather than splitting it
+            style
+                .island
+                .filter(|_| phone.orientation == Orientation::Portrait),
+            config_dir,
+        )
+        .with_context(|| format!("scene '{}' failed drawing status bar", scene_id))?;
+    }
+
+    if style.home_indicator {
+        draw_home_indicator(
+            layers.screenshot(),
+            &fitted,
+            screen_x as i32,
+            screen_y as i32,
+        );
+    }
+
     if let Some(ref ov) = overlay {
+        let tint = match &phone.overlay_tint {
+            Some(raw) => Some(parse_hex_rgba(raw).with_context(|| {
+                format!("scene '{}' has invalid overlay_tint '{}'", scene_id, raw)
+            })?),
+            None => None,
+        };
         // Use the overlay PNG for the frame
         apply_phone_overlay(
-            &mut background,
+            layers.overlay(),
             &ov.path,
             phone.x as i32,
             phone.y as i32,
             phone.width,
             phone.height,
+            tint,
         )
         .with_context(|| {
             format!(
                 "scene '{}' failed applying {} overlay {}",
-                scene.id,
+                scene_id,
                 ov.source.label(),
                 ov.path.display()
             )
@@ -237,73 +761,523 @@ pub fn compose_scene(
     } else if let Some(island) = style.island {
         // Only draw programmatic dynamic island if no overlay
         draw_dynamic_island(
-            &mut background,
+            layers.overlay(),
             screen_x as i32,
             screen_y as i32,
             screen_w,
             screen_h,
             island,
+            phone.orientation,
+        );
+    }
+
+    Ok(())
+}
+
+/// Composite a QR code for `data` on a rounded white plate in the given canvas corner.
+/// `size` is the plate edge length in pixels, including the quiet zone.
+pub fn draw_qr_code(image: &mut RgbaImage, data: &str, corner: Corner, size: u32) -> Result<()> {
+    let code = QrCode::new(data.as_bytes())
+        .with_context(|| format!("failed to encode QR code for '{}'", data))?;
+    let modules = code.width() as u32;
+    let quiet = QR_QUIET_ZONE_MODULES;
+    let module_px = size / (modules + quiet * 2);
+    if module_px == 0 {
+        bail!(
+            "QR size {}px is too small for {} modules; use at least {}px",
+            size,
+            modules,
+            modules + quiet * 2
+        );
+    }
+
+    let plate = module_px * (modules + quiet * 2);
+    let margin = QR_CORNER_MARGIN_PX as i32;
+    let max_x = image.width() as i32 - plate as i32 - margin;
+    let max_y = image.height() as i32 - plate as i32 - margin;
+    let (plate_x, plate_y) = match corner {
+        Corner::TopLeft => (margin, margin),
+        Corner::TopRight => (max_x, margin),
+        Corner::BottomLeft => (margin, max_y),
+        Corner::BottomRight => (max_x, max_y),
+    };
+
+    fill_rounded_rect(
+        image,
+        plate_x,
+        plate_y + 6,
+        plate,
+        plate,
+        module_px * 2,
+        Rgba([0, 0, 0, 60]),
+    );
+    fill_rounded_rect(
+        image,
+        plate_x,
+        plate_y,
+        plate,
+        plate,
+        module_px * 2,
+        Rgba([255, 255, 255, 255]),
+    );
+
+    let colors = code.to_colors();
+    for (index, color) in colors.iter().enumerate() {
+        if *color != QrColor::Dark {
+            continue;
+        }
+        let mx = index as u32 % modules;
+        let my = index as u32 / modules;
+        fill_rounded_rect(
+            image,
+            plate_x + ((mx + quiet) * module_px) as i32,
+            plate_y + ((my + quiet) * module_px) as i32,
+            module_px,
+            module_px,
+            0,
+            Rgba([10, 12, 16, 255]),
         );
     }
 
-    Ok(background)
+    Ok(())
+}
+
+/// Draw a single line of text horizontally centered on `center_x`, with its top at `y`
+pub fn draw_centered_label(
+    image: &mut RgbaImage,
+    text: &str,
+    center_x: i32,
+    y: i32,
+    size: f32,
+    color: Rgba<u8>,
+) -> Result<()> {
+    let font = get_font(FontWeight::SemiBold, None, Path::new("."))?;
+    let scaled = font.as_scaled(PxScale::from(size));
+    let width = measure_text_width(text, &scaled);
+    draw_text_line(
+        image,
+        text,
+        center_x - (width / 2.0).round() as i32,
+        y,
+        &scaled,
+        TextFill::Solid(color),
+    );
+    Ok(())
+}
+
+/// Font file configured for `weight`: the copy's per-weight `fonts`, then its `font_path`,
+/// then the top-level `fonts` map. `None` means the embedded Geist weight.
+fn copy_font_path<'a>(
+    weight: FontWeight,
+    copy: &'a CopyConfig,
+    fonts: &'a HashMap<FontWeight, PathBuf>,
+) -> Option<&'a Path> {
+    copy.fonts
+        .get(&weight)
+        .or(copy.font_path.as_ref())
+        .or_else(|| fonts.get(&weight))
+        .map(PathBuf::as_path)
 }
 
-fn get_font(weight: FontWeight) -> Result<FontRef<'static>> {
+/// Load a font file, or the embedded Geist weight when `path` is `None`. Files are parsed
+/// once per process and shared, since every copy line of every scene asks for them again.
+fn get_font(weight: FontWeight, path: Option<&Path>, config_dir: &Path) -> Result<FontArc> {
+    static LOADED: OnceLock<Mutex<HashMap<PathBuf, FontArc>>> = OnceLock::new();
+
+    if let Some(path) = path {
+        let path = resolve_path(config_dir, path);
+        let mut loaded = LOADED
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(font) = loaded.get(&path) {
+            return Ok(font.clone());
+        }
+        let data = std::fs::read(&path)
+            .with_context(|| format!("failed reading font {}", path.display()))?;
+        let font = FontArc::try_from_vec(data)
+            .with_context(|| format!("failed to load font {}", path.display()))?;
+        loaded.insert(path, font.clone());
+        return Ok(font);
+    }
+
     let data = match weight {
         FontWeight::Regular => GEIST_REGULAR,
         FontWeight::Medium => GEIST_MEDIUM,
         FontWeight::SemiBold => GEIST_SEMIBOLD,
         FontWeight::Bold => GEIST_BOLD,
     };
-    FontRef::try_from_slice(data).context("failed to load embedded Geist font")
+    FontArc::try_from_slice(data).context("failed to load embedded Geist font")
+}
+
+/// Draw the copy onto `image`. `background` is what it's laid over, for `auto_contrast`;
+/// `None` when `image` already holds it.
+fn draw_copy(
+    image: &mut RgbaImage,
+    background: Option<&RgbaImage>,
+    copy: &CopyConfig,
+    headline: &str,
+    phone: &PhoneConfig,
+    config_dir: &Path,
+    fonts: &HashMap<FontWeight, PathBuf>,
+) -> Result<()> {
+    let shadow_color = match &copy.shadow_color {
+        Some(raw) => Some(
+            parse_hex_rgba(raw).with_context(|| format!("invalid copy shadow_color '{}'", raw))?,
+        ),
+        None => None,
+    };
+    let panel_color = match &copy.text_background_color {
+        Some(raw) => Some(
+            parse_hex_rgba(raw)
+                .with_context(|| format!("invalid copy text_background_color '{}'", raw))?,
+        ),
+        None => None,
+    };
+    if shadow_color.is_none() && panel_color.is_none() {
+        let backdrop = Backdrop {
+            image: background,
+            panel: None,
+        };
+        draw_copy_text(image, copy, headline, phone, config_dir, fonts, &backdrop)?;
+        return Ok(());
+    }
+
+    // Render the copy on its own so the panel and shadow can be laid underneath it
+    let mut text = RgbaImage::new(image.width(), image.height());
+    let backdrop = Backdrop {
+        image: Some(background.unwrap_or(image)),
+        panel: panel_color,
+    };
+    let block = draw_copy_text(
+        &mut text, copy, headline, phone, config_dir, fonts, &backdrop,
+    )?;
+    if let Some(color) = panel_color {
+        let pad = copy.text_background_padding;
+        fill_rounded_rect(
+            image,
+            block.x - pad as i32,
+            block.y - pad as i32,
+            block.width + pad * 2,
+            block.height + pad * 2,
+            pad,
+            color,
+        );
+    }
+    if let Some(color) = shadow_color {
+        draw_text_shadow(image, &text, copy, color);
+    }
+    blend_layer(image, &text);
+    Ok(())
+}
+
+/// Paint `shadow_color` through the offset (and optionally blurred) alpha of `text`
+fn draw_text_shadow(image: &mut RgbaImage, text: &RgbaImage, copy: &CopyConfig, color: Rgba<u8>) {
+    let (width, height) = text.dimensions();
+    let (w, h) = (width as usize, height as usize);
+    let mut alpha = vec![0.0f32; w * h];
+    for (x, y, pixel) in text.enumerate_pixels() {
+        let sx = x as i64 + copy.shadow_offset_x as i64;
+        let sy = y as i64 + copy.shadow_offset_y as i64;
+        if pixel[3] > 0 && sx >= 0 && sy >= 0 && (sx as usize) < w && (sy as usize) < h {
+            alpha[sy as usize * w + sx as usize] = pixel[3] as f32 / 255.0;
+        }
+    }
+
+    // Two box passes approximate a gaussian without its cost
+    for _ in 0..2 {
+        box_blur(&mut alpha, w, h, copy.shadow_blur as usize);
+    }
+
+    for (index, value) in alpha.iter().enumerate() {
+        let a = (value * color[3] as f32).round().clamp(0.0, 255.0) as u8;
+        if a > 0 {
+            let x = (index % w) as i32;
+            let y = (index / w) as i32;
+            blend_pixel(image, x, y, Rgba([color[0], color[1], color[2], a]));
+        }
+    }
+}
+
+/// Soft halo behind the phone: its rounded rect, grown by half the glow radius and
+/// blurred out over the rest
+fn draw_glow(image: &mut RgbaImage, phone: &PhoneConfig, corner_radius: u32, color: Rgba<u8>) {
+    let radius = phone.glow_radius;
+    if radius == 0 {
+        return;
+    }
+    let grow = radius / 2;
+    let mask = SoftMask::rounded_rect(
+        phone.width,
+        phone.height,
+        corner_radius,
+        grow as i32,
+        radius - grow,
+    );
+    mask.blend(image, phone.x as i32, phone.y as i32, color);
+}
+
+/// Soft drop shadow: the phone's rounded rect grown by `shadow_spread` and faded over
+/// `shadow_blur` pixels, shifted down by `shadow_offset_y`
+fn draw_soft_shadow(image: &mut RgbaImage, phone: &PhoneConfig, style: &ResolvedPhoneStyle) {
+    let mask = SoftMask::rounded_rect(
+        phone.width,
+        phone.height,
+        style.corner_radius,
+        phone.shadow_spread,
+        phone.shadow_blur / 2,
+    );
+    mask.blend(
+        image,
+        phone.x as i32,
+        phone.y as i32 + style.shadow_offset_y,
+        Rgba([0, 0, 0, style.shadow_alpha]),
+    );
+}
+
+/// Coverage of a blurred rounded rect, padded by `margin` on every side of the unblurred
+/// `width`x`height` rect it was built from
+struct SoftMask {
+    alpha: Vec<f32>,
+    width: usize,
+    margin: u32,
 }
 
-fn draw_copy(image: &mut RgbaImage, copy: &CopyConfig, phone: &PhoneConfig) -> Result<()> {
+impl SoftMask {
+    /// A `width`x`height` rounded rect grown by `spread` on every side (shrunk when
+    /// negative), softened by two box passes of radius `blur`
+    fn rounded_rect(width: u32, height: u32, corner_radius: u32, spread: i32, blur: u32) -> Self {
+        let margin = spread.max(0) as u32 + blur * 2;
+        let w = (width + margin * 2) as usize;
+        let h = (height + margin * 2) as usize;
+        let mut alpha = vec![0.0f32; w * h];
+        let rect_w = width as i32 + spread * 2;
+        let rect_h = height as i32 + spread * 2;
+        let radius = (corner_radius as i32 + spread).max(0);
+        let offset = margin as i32 - spread;
+        for y in 0..rect_h.max(0) {
+            for x in 0..rect_w.max(0) {
+                if inside_rounded_rect(x, y, rect_w, rect_h, radius) {
+                    alpha[(y + offset) as usize * w + (x + offset) as usize] = 1.0;
+                }
+            }
+        }
+
+        for _ in 0..2 {
+            box_blur(&mut alpha, w, h, blur as usize);
+        }
+        Self {
+            alpha,
+            width: w,
+            margin,
+        }
+    }
+
+    /// Blend `color`, its alpha scaled by the coverage, with the unblurred rect's top-left
+    /// corner at (`x`, `y`)
+    fn blend(&self, image: &mut RgbaImage, x: i32, y: i32, color: Rgba<u8>) {
+        let origin_x = x - self.margin as i32;
+        let origin_y = y - self.margin as i32;
+        for (index, value) in self.alpha.iter().enumerate() {
+            let a = (value * color[3] as f32).round().clamp(0.0, 255.0) as u8;
+            if a > 0 {
+                let x = origin_x + (index % self.width) as i32;
+                let y = origin_y + (index / self.width) as i32;
+                blend_pixel(image, x, y, Rgba([color[0], color[1], color[2], a]));
+            }
+        }
+    }
+}
+
+/// Bounds of the laid-out copy (logo included) on the canvas
+struct TextBlock {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+/// What the copy ends up on top of: the background, seen through the text panel if any
+struct Backdrop<'a> {
+    /// `None` when the copy is drawn straight onto its background
+    image: Option<&'a RgbaImage>,
+    panel: Option<Rgba<u8>>,
+}
+
+impl Backdrop<'_> {
+    /// Mean relative luminance of the backdrop under `block`, with `target` the image the
+    /// copy is drawn onto
+    fn luminance(&self, target: &RgbaImage, block: &TextBlock) -> f32 {
+        let image = self.image.unwrap_or(target);
+        let (width, height) = image.dimensions();
+        let x0 = block.x.clamp(0, width as i32) as u32;
+        let y0 = block.y.clamp(0, height as i32) as u32;
+        let x1 = (block.x + block.width as i32).clamp(0, width as i32) as u32;
+        let y1 = (block.y + block.height as i32).clamp(0, height as i32) as u32;
+        let count = (x1 - x0) as f32 * (y1 - y0) as f32;
+        let background = if count > 0.0 {
+            let sum: f32 = (y0..y1)
+                .flat_map(|y| (x0..x1).map(move |x| (x, y)))
+                .map(|(x, y)| relative_luminance(*image.get_pixel(x, y)))
+                .sum();
+            sum / count
+        } else {
+            0.0
+        };
+        match self.panel {
+            Some(panel) => {
+                let alpha = panel[3] as f32 / 255.0;
+                background * (1.0 - alpha) + relative_luminance(panel) * alpha
+            }
+            None => background,
+        }
+    }
+}
+
+fn draw_copy_text(
+    image: &mut RgbaImage,
+    copy: &CopyConfig,
+    headline: &str,
+    phone: &PhoneConfig,
+    config_dir: &Path,
+    fonts: &HashMap<FontWeight, PathBuf>,
+    backdrop: &Backdrop,
+) -> Result<TextBlock> {
     let color = parse_hex_rgba(&copy.color)?;
+    let stroke = match &copy.stroke_color {
+        Some(raw) if copy.stroke_width > 0 => {
+            let stroke_color = parse_hex_rgba(raw)
+                .with_context(|| format!("invalid copy stroke_color '{}'", raw))?;
+            Some((stroke_color, copy.stroke_width))
+        }
+        _ => None,
+    };
     let image_width = image.width();
     let image_height = image.height();
 
+    let line_height = copy.line_height.max(0.1);
+
     // Default max_width to 80% of image width for centered text
-    let max_width = copy
-        .max_width
-        .unwrap_or_else(|| (image_width as f32 * 0.8) as u32);
+    let max_width = copy.max_width.unwrap_or((image_width as f32 * 0.8) as u32);
 
     // Pre-calculate text dimensions to determine total height
-    let headline_font = get_font(copy.headline_weight)?;
+    let headline_font = get_font(
+        copy.headline_weight,
+        copy_font_path(copy.headline_weight, copy, fonts),
+        config_dir,
+    )?;
     let headline_scale = PxScale::from(copy.headline_size);
     let headline_scaled = headline_font.as_scaled(headline_scale);
-    let headline_lines = wrap_text_by_width(&copy.headline, &headline_scaled, max_width as f32);
-    let headline_line_height = (headline_scaled.height() * 1.2).ceil() as u32;
+    let headline = copy.transform.apply(headline);
+    let headline_lines = wrap_text_by_width(&headline, &headline_scaled, max_width as f32);
+    let headline_line_height = (headline_scaled.height() * line_height).ceil() as u32;
     let headline_total_height = headline_lines.len() as u32 * headline_line_height;
+    let headline_width = headline_lines
+        .iter()
+        .map(|line| measure_text_width(line, &headline_scaled))
+        .fold(0.0f32, f32::max);
+
+    let (subheadline_lines, subheadline_total_height, subheadline_width, subheadline_font_px) =
+        if !copy.subheadline.trim().is_empty() {
+            let subheadline_font = get_font(
+                copy.subheadline_weight,
+                copy_font_path(copy.subheadline_weight, copy, fonts),
+                config_dir,
+            )?;
+            let sub_scale = PxScale::from(copy.subheadline_size);
+            let sub_scaled = subheadline_font.as_scaled(sub_scale);
+            let subheadline = copy.transform.apply(&copy.subheadline);
+            let lines = wrap_text_by_width(&subheadline, &sub_scaled, max_width as f32);
+            let sub_line_height = (sub_scaled.height() * line_height).ceil() as u32;
+            let total = lines.len() as u32 * sub_line_height;
+            let width = lines
+                .iter()
+                .map(|line| measure_text_width(line, &sub_scaled))
+                .fold(0.0f32, f32::max);
+            (lines, total, width, sub_scaled.height())
+        } else {
+            (vec![], 0, 0.0, 0.0)
+        };
 
-    let (subheadline_lines, subheadline_total_height) = if !copy.subheadline.trim().is_empty() {
-        let subheadline_font = get_font(copy.subheadline_weight)?;
-        let sub_scale = PxScale::from(copy.subheadline_size);
-        let sub_scaled = subheadline_font.as_scaled(sub_scale);
-        let lines = wrap_text_by_width(&copy.subheadline, &sub_scaled, max_width as f32);
-        let line_height = (sub_scaled.height() * 1.2).ceil() as u32;
-        let total = lines.len() as u32 * line_height;
-        (lines, total)
+    // Bullets share the subheadline font; each wraps in the space right of its dot
+    let bullet_indent = (copy.subheadline_size * BULLET_INDENT_RATIO).ceil();
+    let bullets: Vec<&str> = copy
+        .bullets
+        .iter()
+        .map(|bullet| bullet.trim())
+        .filter(|bullet| !bullet.is_empty())
+        .collect();
+    let (bullet_lines, bullet_total_height, bullet_width, bullet_font_px) = if !bullets.is_empty() {
+        let bullet_font = get_font(
+            copy.subheadline_weight,
+            copy_font_path(copy.subheadline_weight, copy, fonts),
+            config_dir,
+        )?;
+        let bullet_scaled = bullet_font.as_scaled(PxScale::from(copy.subheadline_size));
+        let wrap_width = (max_width as f32 - bullet_indent).max(1.0);
+        let lines: Vec<Vec<String>> = bullets
+            .iter()
+            .map(|bullet| wrap_text_by_width(bullet, &bullet_scaled, wrap_width))
+            .collect();
+        let bullet_line_height = (bullet_scaled.height() * line_height).ceil() as u32;
+        let total = lines.iter().map(Vec::len).sum::<usize>() as u32 * bullet_line_height;
+        let width = lines
+            .iter()
+            .flatten()
+            .map(|line| bullet_indent + measure_text_width(line, &bullet_scaled))
+            .fold(0.0f32, f32::max);
+        (lines, total, width, bullet_scaled.height())
     } else {
-        (vec![], 0)
+        (vec![], 0, 0.0, 0.0)
     };
 
-    let total_text_height = headline_total_height
+    // The logo and its gap are stacked on top of the text so the whole lockup centers as one unit
+    let logo = match &copy.logo {
+        Some(logo) => Some((load_logo(logo, config_dir)?, logo.gap)),
+        None => None,
+    };
+    let logo_block_height = logo
+        .as_ref()
+        .map(|(img, gap)| img.height() + gap)
+        .unwrap_or(0);
+
+    let total_text_height = logo_block_height
+        + headline_total_height
         + if subheadline_total_height > 0 {
             copy.line_gap + subheadline_total_height
         } else {
             0
+        }
+        + if bullet_total_height > 0 {
+            copy.line_gap + bullet_total_height
+        } else {
+            0
         };
 
+    let last_font_height = if !bullet_lines.is_empty() {
+        bullet_font_px
+    } else if !subheadline_lines.is_empty() {
+        subheadline_font_px
+    } else if !headline_lines.is_empty() {
+        headline_scaled.height()
+    } else {
+        0.0
+    };
+    // Leading under the last line is spacing, not ink, so the block bounds leave it out.
+    // Positioning keeps the default line_height's share of it, so copy at the default lays
+    // out as it always has and only extra leading is kept from pushing the copy off center.
+    let block_height =
+        total_text_height.saturating_sub(trailing_leading(last_font_height, line_height));
+    let layout_height = block_height + trailing_leading(last_font_height, DEFAULT_LINE_HEIGHT);
+
     // Calculate base Y position based on TextPosition preset
-    let padding = 60u32; // Default padding from edges
+    let padding = copy.padding;
     let base_y = match copy.position {
         TextPosition::AbovePhone => {
             // Center text in the space above the phone
             let space_above = phone.y;
-            if space_above > total_text_height {
-                ((space_above - total_text_height) / 2) as i32
+            if space_above > layout_height {
+                ((space_above - layout_height) / 2) as i32
             } else {
                 padding as i32
             }
@@ -312,8 +1286,8 @@ fn draw_copy(image: &mut RgbaImage, copy: &CopyConfig, phone: &PhoneConfig) -> R
             // Center text in the space below the phone
             let phone_bottom = phone.y + phone.height;
             let space_below = image_height.saturating_sub(phone_bottom);
-            if space_below > total_text_height {
-                (phone_bottom + (space_below - total_text_height) / 2) as i32
+            if space_below > layout_height {
+                (phone_bottom + (space_below - layout_height) / 2) as i32
             } else {
                 (phone_bottom + padding) as i32
             }
@@ -321,53 +1295,175 @@ fn draw_copy(image: &mut RgbaImage, copy: &CopyConfig, phone: &PhoneConfig) -> R
         TextPosition::Top => padding as i32,
         TextPosition::Bottom => {
             (image_height
-                .saturating_sub(total_text_height)
+                .saturating_sub(layout_height)
                 .saturating_sub(padding)) as i32
         }
+        TextPosition::Center => (image_height.saturating_sub(layout_height) / 2) as i32,
     };
 
     // Apply user's y_offset adjustment
     let final_y = (base_y + copy.y_offset).max(0) as u32;
 
-    // Draw headline lines centered
+    let logo_width = logo.as_ref().map_or(0.0, |(img, _)| img.width() as f32);
+    let block_width = logo_width
+        .max(headline_width)
+        .max(subheadline_width)
+        .max(bullet_width);
+    let block = TextBlock {
+        x: aligned_x(copy, image_width, block_width),
+        y: final_y as i32,
+        width: block_width.ceil() as u32,
+        height: block_height,
+    };
+    let fill = match &copy.color_gradient {
+        Some([from, to]) => {
+            let from = parse_hex_rgba(from)
+                .with_context(|| format!("invalid copy color_gradient '{}'", from))?;
+            let to = parse_hex_rgba(to)
+                .with_context(|| format!("invalid copy color_gradient '{}'", to))?;
+            TextFill::gradient(from, to, copy.gradient_direction, &block)
+        }
+        None if copy.auto_contrast => {
+            TextFill::Solid(contrasting_text_color(backdrop.luminance(image, &block)))
+        }
+        None => TextFill::Solid(color),
+    };
+
     let mut current_y = final_y;
+    if let Some((logo_img, gap)) = &logo {
+        let x = aligned_x(copy, image_width, logo_img.width() as f32);
+        blit_rounded(image, logo_img, x, current_y as i32, 0);
+        current_y += logo_img.height() + gap;
+    }
+
+    // Draw headline lines
     for line in &headline_lines {
         let line_width = measure_text_width(line, &headline_scaled);
-        let x = ((image_width as f32 - line_width) / 2.0).max(0.0) as i32;
-        draw_text_line(image, line, x, current_y as i32, &headline_scaled, color);
+        let x = aligned_x(copy, image_width, line_width);
+        if let Some((stroke_color, width)) = stroke {
+            let y = current_y as i32;
+            draw_text_stroke(image, line, x, y, &headline_scaled, stroke_color, width);
+        }
+        draw_text_line(image, line, x, current_y as i32, &headline_scaled, fill);
         current_y += headline_line_height;
     }
 
-    // Draw subheadline lines centered
+    // Draw subheadline lines
     if !subheadline_lines.is_empty() {
         current_y += copy.line_gap;
-        let subheadline_font = get_font(copy.subheadline_weight)?;
+        let subheadline_font = get_font(
+            copy.subheadline_weight,
+            copy_font_path(copy.subheadline_weight, copy, fonts),
+            config_dir,
+        )?;
         let sub_scale = PxScale::from(copy.subheadline_size);
         let sub_scaled = subheadline_font.as_scaled(sub_scale);
-        let sub_line_height = (sub_scaled.height() * 1.2).ceil() as u32;
+        let sub_line_height = (sub_scaled.height() * line_height).ceil() as u32;
 
         for line in &subheadline_lines {
             let line_width = measure_text_width(line, &sub_scaled);
-            let x = ((image_width as f32 - line_width) / 2.0).max(0.0) as i32;
-            draw_text_line(image, line, x, current_y as i32, &sub_scaled, color);
+            let x = aligned_x(copy, image_width, line_width);
+            if let Some((stroke_color, width)) = stroke {
+                let y = current_y as i32;
+                draw_text_stroke(image, line, x, y, &sub_scaled, stroke_color, width);
+            }
+            draw_text_line(image, line, x, current_y as i32, &sub_scaled, fill);
             current_y += sub_line_height;
         }
     }
 
-    Ok(())
+    // Draw bullets: the list is aligned as one block so the dots line up in a column, and
+    // wrapped lines hang past the dot
+    if !bullet_lines.is_empty() {
+        current_y += copy.line_gap;
+        let bullet_font = get_font(
+            copy.subheadline_weight,
+            copy_font_path(copy.subheadline_weight, copy, fonts),
+            config_dir,
+        )?;
+        let bullet_scaled = bullet_font.as_scaled(PxScale::from(copy.subheadline_size));
+        let bullet_line_height = (bullet_scaled.height() * line_height).ceil() as u32;
+        let dot_radius = (copy.subheadline_size * BULLET_DOT_RADIUS_RATIO)
+            .round()
+            .max(1.0) as i32;
+        let dot_rise = copy.subheadline_size * BULLET_DOT_RISE_RATIO;
+
+        let x = aligned_x(copy, image_width, bullet_width);
+        for lines in &bullet_lines {
+            let cx = x + dot_radius;
+            let cy = (current_y as f32 + bullet_scaled.ascent() - dot_rise).round() as i32;
+            if let Some((stroke_color, width)) = stroke {
+                fill_circle(image, cx, cy, dot_radius + width as i32, stroke_color);
+            }
+            fill_circle(image, cx, cy, dot_radius, fill.color_at(cx, cy));
+
+            let text_x = x + bullet_indent as i32;
+            for line in lines {
+                if let Some((stroke_color, width)) = stroke {
+                    let y = current_y as i32;
+                    draw_text_stroke(image, line, text_x, y, &bullet_scaled, stroke_color, width);
+                }
+                draw_text_line(image, line, text_x, current_y as i32, &bullet_scaled, fill);
+                current_y += bullet_line_height;
+            }
+        }
+    }
+
+    Ok(block)
 }
 
-#[derive(Clone, Copy)]
-struct OverlayScreenRegion {
-    overlay_width: u32,
-    overlay_height: u32,
-    x: u32,
-    y: u32,
-    width: u32,
-    height: u32,
+/// Leading below the last line of a section whose font is `font_height` pixels tall
+fn trailing_leading(font_height: f32, line_height: f32) -> u32 {
+    ((font_height * line_height).ceil() as u32).saturating_sub(font_height.ceil() as u32)
+}
+
+/// Left edge of a line (or logo) `width` pixels wide under the copy's alignment
+fn aligned_x(copy: &CopyConfig, image_width: u32, width: f32) -> i32 {
+    let padding = copy.padding as f32;
+    let x = match copy.align {
+        TextAlign::Left => padding,
+        TextAlign::Center => (image_width as f32 - width) / 2.0,
+        TextAlign::Right => image_width as f32 - padding - width,
+    };
+    x.max(0.0) as i32
+}
+
+fn load_logo(logo: &LogoConfig, config_dir: &Path) -> Result<RgbaImage> {
+    let path = resolve_path(config_dir, &logo.path);
+    let source =
+        image::open(&path).with_context(|| format!("failed opening logo {}", path.display()))?;
+    if logo.height == 0 || source.height() == 0 {
+        bail!("logo {} has invalid height", path.display());
+    }
+
+    let width = ((source.width() as f32 * logo.height as f32 / source.height() as f32).round()
+        as u32)
+        .max(1);
+    Ok(source
+        .resize_exact(width, logo.height, FilterType::Lanczos3)
+        .to_rgba8())
+}
+
+fn resolve_path(config_dir: &Path, path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        config_dir.join(path)
+    }
 }
 
-fn detect_overlay_screen_region(overlay_path: &Path) -> Result<Option<OverlayScreenRegion>> {
+/// Largest enclosed fully-transparent area of an overlay, in overlay pixels
+#[derive(Debug, Clone, Copy)]
+pub struct OverlayScreenRegion {
+    pub overlay_width: u32,
+    pub overlay_height: u32,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+pub fn detect_overlay_screen_region(overlay_path: &Path) -> Result<Option<OverlayScreenRegion>> {
     let overlay = image::open(overlay_path)
         .with_context(|| format!("failed opening overlay {}", overlay_path.display()))?
         .to_rgba8();
@@ -541,7 +1637,7 @@ fn draw_text_line<F: Font>(
     start_x: i32,
     start_y: i32,
     font: &ab_glyph::PxScaleFont<&F>,
-    color: Rgba<u8>,
+    fill: TextFill,
 ) {
     let mut cursor_x = start_x as f32;
     let mut prev_glyph: Option<ab_glyph::GlyphId> = None;
@@ -563,6 +1659,7 @@ fn draw_text_line<F: Font>(
             outlined.draw(|gx, gy, coverage| {
                 let px = bounds.min.x as i32 + gx as i32;
                 let py = bounds.min.y as i32 + gy as i32;
+                let color = fill.color_at(px, py);
                 let alpha = (coverage * color[3] as f32).round().clamp(0.0, 255.0) as u8;
                 if alpha > 0 {
                     blend_pixel(image, px, py, Rgba([color[0], color[1], color[2], alpha]));
@@ -575,18 +1672,267 @@ fn draw_text_line<F: Font>(
     }
 }
 
-fn resize_cover(source: &DynamicImage, target_w: u32, target_h: u32) -> RgbaImage {
-    let (src_w, src_h) = source.dimensions();
-    let scale = (target_w as f32 / src_w as f32).max(target_h as f32 / src_h as f32);
-    let resized_w = ((src_w as f32 * scale).ceil() as u32).max(target_w);
-    let resized_h = ((src_h as f32 * scale).ceil() as u32).max(target_h);
+/// How glyph pixels are colored: one color, or a two-stop gradient across the copy block
+#[derive(Clone, Copy)]
+enum TextFill {
+    Solid(Rgba<u8>),
+    Gradient {
+        from: Rgba<u8>,
+        to: Rgba<u8>,
+        direction: GradientDirection,
+        start: f32,
+        length: f32,
+    },
+}
+
+impl TextFill {
+    fn gradient(
+        from: Rgba<u8>,
+        to: Rgba<u8>,
+        direction: GradientDirection,
+        block: &TextBlock,
+    ) -> Self {
+        let (start, length) = match direction {
+            GradientDirection::Vertical => (block.y as f32, block.height as f32),
+            GradientDirection::Horizontal => (block.x as f32, block.width as f32),
+        };
+        Self::Gradient {
+            from,
+            to,
+            direction,
+            start,
+            length: length.max(1.0),
+        }
+    }
+
+    fn color_at(&self, x: i32, y: i32) -> Rgba<u8> {
+        match *self {
+            Self::Solid(color) => color,
+            Self::Gradient {
+                from,
+                to,
+                direction,
+                start,
+                length,
+            } => {
+                let position = match direction {
+                    GradientDirection::Vertical => y,
+                    GradientDirection::Horizontal => x,
+                };
+                lerp_color(from, to, (position as f32 + 0.5 - start) / length)
+            }
+        }
+    }
+}
+
+/// Draw an outline `width` pixels wide around a line of text. The glyph coverage is
+/// dilated into a single mask first, so overlapping strokes never stack their alpha.
+fn draw_text_stroke<F: Font>(
+    image: &mut RgbaImage,
+    text: &str,
+    start_x: i32,
+    start_y: i32,
+    font: &ab_glyph::PxScaleFont<&F>,
+    color: Rgba<u8>,
+    width: u32,
+) {
+    let mut outlines = Vec::new();
+    let mut cursor_x = start_x as f32;
+    let mut prev_glyph: Option<ab_glyph::GlyphId> = None;
+    for ch in text.chars() {
+        let glyph_id = font.glyph_id(ch);
+        if let Some(prev) = prev_glyph {
+            cursor_x += font.kern(prev, glyph_id);
+        }
+        let glyph = glyph_id.with_scale_and_position(
+            font.scale(),
+            ab_glyph::point(cursor_x, start_y as f32 + font.ascent()),
+        );
+        if let Some(outlined) = font.outline_glyph(glyph) {
+            outlines.push(outlined);
+        }
+        cursor_x += font.h_advance(glyph_id);
+        prev_glyph = Some(glyph_id);
+    }
+    if outlines.is_empty() {
+        return;
+    }
+
+    // Glyph coverage in a padded local buffer covering the whole line
+    let pad = width as i32 + 1;
+    let min_x = outlines
+        .iter()
+        .map(|o| o.px_bounds().min.x as i32)
+        .min()
+        .unwrap_or(0)
+        - pad;
+    let min_y = outlines
+        .iter()
+        .map(|o| o.px_bounds().min.y as i32)
+        .min()
+        .unwrap_or(0)
+        - pad;
+    let max_x = outlines
+        .iter()
+        .map(|o| o.px_bounds().max.x.ceil() as i32)
+        .max()
+        .unwrap_or(0)
+        + pad;
+    let max_y = outlines
+        .iter()
+        .map(|o| o.px_bounds().max.y.ceil() as i32)
+        .max()
+        .unwrap_or(0)
+        + pad;
+    let mask_w = (max_x - min_x).max(1) as usize;
+    let mask_h = (max_y - min_y).max(1) as usize;
+    let mut coverage = vec![0.0f32; mask_w * mask_h];
+    for outlined in &outlines {
+        let bounds = outlined.px_bounds();
+        outlined.draw(|gx, gy, value| {
+            let mx = bounds.min.x as i32 + gx as i32 - min_x;
+            let my = bounds.min.y as i32 + gy as i32 - min_y;
+            if mx >= 0 && my >= 0 && (mx as usize) < mask_w && (my as usize) < mask_h {
+                let cell = &mut coverage[my as usize * mask_w + mx as usize];
+                *cell = cell.max(value);
+            }
+        });
+    }
+
+    // Disc-shaped max filter with a one-pixel soft edge
+    let radius = width as f32;
+    let reach = width as i32 + 1;
+    let mut offsets = Vec::new();
+    for dy in -reach..=reach {
+        for dx in -reach..=reach {
+            let weight = (radius + 0.5 - ((dx * dx + dy * dy) as f32).sqrt()).clamp(0.0, 1.0);
+            if weight > 0.0 {
+                offsets.push((dx, dy, weight));
+            }
+        }
+    }
+
+    for my in 0..mask_h as i32 {
+        for mx in 0..mask_w as i32 {
+            let mut amount = 0.0f32;
+            for &(dx, dy, weight) in &offsets {
+                let sx = mx + dx;
+                let sy = my + dy;
+                if sx < 0 || sy < 0 || sx as usize >= mask_w || sy as usize >= mask_h {
+                    continue;
+                }
+                amount = amount.max(coverage[sy as usize * mask_w + sx as usize] * weight);
+                if amount >= 1.0 {
+                    break;
+                }
+            }
+            let alpha = (amount * color[3] as f32).round().clamp(0.0, 255.0) as u8;
+            if alpha > 0 {
+                blend_pixel(
+                    image,
+                    min_x + mx,
+                    min_y + my,
+                    Rgba([color[0], color[1], color[2], alpha]),
+                );
+            }
+        }
+    }
+}
+
+/// Scale to fill the target while preserving aspect ratio, center-cropping the overflow
+pub fn resize_cover(source: &DynamicImage, target_w: u32, target_h: u32) -> RgbaImage {
+    let (src_w, src_h) = source.dimensions();
+    let scale = (target_w as f32 / src_w as f32).max(target_h as f32 / src_h as f32);
+    let resized_w = ((src_w as f32 * scale).ceil() as u32).max(target_w);
+    let resized_h = ((src_h as f32 * scale).ceil() as u32).max(target_h);
+
+    let resized = source
+        .resize_exact(resized_w, resized_h, FilterType::Lanczos3)
+        .to_rgba8();
+    let crop_x = (resized_w - target_w) / 2;
+    let crop_y = (resized_h - target_h) / 2;
+    crop_imm(&resized, crop_x, crop_y, target_w, target_h).to_image()
+}
+
+/// The background's average color just outside `phone`, which its rim light is judged
+/// against; `None` when the phone has no `auto_rim_light`
+fn rim_backdrop(background: &RgbaImage, phone: &PhoneConfig) -> Option<Rgba<u8>> {
+    if !phone.auto_rim_light {
+        return None;
+    }
+    average_surrounding_color(background, phone, RIM_SAMPLE_BAND_PX)
+}
+
+/// Outline the phone silhouette when the frame color is too close to `surround`, the
+/// background just outside the phone rect.
+fn draw_rim_light_if_needed(
+    image: &mut RgbaImage,
+    surround: Rgba<u8>,
+    phone: &PhoneConfig,
+    radius: u32,
+    frame_color: Rgba<u8>,
+    min_contrast: f32,
+) {
+    if contrast_ratio(frame_color, surround) >= min_contrast {
+        return;
+    }
+
+    // Light rim on dark surroundings, dark rim on light ones.
+    let rim = if relative_luminance(surround) < 0.4 {
+        Rgba([255, 255, 255, 110])
+    } else {
+        Rgba([0, 0, 0, 110])
+    };
+    let w = RIM_LIGHT_WIDTH_PX;
+    fill_rounded_rect(
+        image,
+        phone.x as i32 - w as i32,
+        phone.y as i32 - w as i32,
+        phone.width + w * 2,
+        phone.height + w * 2,
+        radius + w,
+        rim,
+    );
+}
+
+fn average_surrounding_color(
+    image: &RgbaImage,
+    phone: &PhoneConfig,
+    band: u32,
+) -> Option<Rgba<u8>> {
+    let x0 = phone.x.saturating_sub(band);
+    let y0 = phone.y.saturating_sub(band);
+    let x1 = (phone.x + phone.width + band).min(image.width());
+    let y1 = (phone.y + phone.height + band).min(image.height());
+
+    let mut sum = [0u64; 3];
+    let mut count = 0u64;
+    for y in y0..y1 {
+        for x in x0..x1 {
+            let inside_phone = x >= phone.x
+                && x < phone.x + phone.width
+                && y >= phone.y
+                && y < phone.y + phone.height;
+            if inside_phone {
+                continue;
+            }
+            let pixel = image.get_pixel(x, y);
+            for channel in 0..3 {
+                sum[channel] += pixel[channel] as u64;
+            }
+            count += 1;
+        }
+    }
 
-    let resized = source
-        .resize_exact(resized_w, resized_h, FilterType::Lanczos3)
-        .to_rgba8();
-    let crop_x = (resized_w - target_w) / 2;
-    let crop_y = (resized_h - target_h) / 2;
-    crop_imm(&resized, crop_x, crop_y, target_w, target_h).to_image()
+    if count == 0 {
+        return None;
+    }
+    Some(Rgba([
+        (sum[0] / count) as u8,
+        (sum[1] / count) as u8,
+        (sum[2] / count) as u8,
+        255,
+    ]))
 }
 
 fn draw_frame_tones(image: &mut RgbaImage, x: i32, y: i32, width: u32, height: u32, radius: u32) {
@@ -614,6 +1960,259 @@ fn draw_frame_tones(image: &mut RgbaImage, x: i32, y: i32, width: u32, height: u
     );
 }
 
+/// Draw the home indicator pill near the bottom of the screen, dark over bright
+/// content and light over dark content so it stays visible.
+fn draw_home_indicator(image: &mut RgbaImage, screen: &RgbaImage, screen_x: i32, screen_y: i32) {
+    let (screen_w, screen_h) = screen.dimensions();
+    // Sized from the short and long screen sides so landscape screens get the same bar
+    let (short_side, long_side) = (screen_w.min(screen_h), screen_w.max(screen_h));
+    let bar_w = ((short_side as f32 * HOME_INDICATOR_WIDTH_RATIO).round() as u32).max(24);
+    let bar_h = ((long_side as f32 * HOME_INDICATOR_HEIGHT_RATIO).round() as u32).max(4);
+    let bottom_gap = ((long_side as f32 * HOME_INDICATOR_BOTTOM_RATIO).round() as u32).max(4);
+    if bar_w >= screen_w || bar_h + bottom_gap >= screen_h {
+        return;
+    }
+    let bar_x = (screen_w - bar_w) / 2;
+    let bar_y = screen_h - bottom_gap - bar_h;
+
+    // Average luminance of the strip the bar sits on
+    let sample_top = bar_y.saturating_sub(bar_h);
+    let sample_bottom = (bar_y + bar_h * 2).min(screen_h);
+    let mut total = 0.0f32;
+    let mut count = 0u32;
+    for y in sample_top..sample_bottom {
+        for x in bar_x..bar_x + bar_w {
+            total += relative_luminance(*screen.get_pixel(x, y));
+            count += 1;
+        }
+    }
+    let luminance = if count > 0 { total / count as f32 } else { 0.0 };
+    let color = if luminance > 0.5 {
+        Rgba([0, 0, 0, 210])
+    } else {
+        Rgba([255, 255, 255, 220])
+    };
+
+    fill_rounded_rect(
+        image,
+        screen_x + bar_x as i32,
+        screen_y + bar_y as i32,
+        bar_w,
+        bar_h,
+        bar_h / 2,
+        color,
+    );
+}
+
+/// Points to pixels for a `width`x`height` screen, and the status bar height in pixels.
+/// Scaled from the short side so a landscape screen keeps portrait-sized glyphs.
+fn status_bar_metrics(width: u32, height: u32) -> (f32, u32) {
+    let pt = width.min(height) as f32 / STATUS_BAR_SCREEN_POINTS;
+    let bar_h = ((STATUS_BAR_HEIGHT_PT * pt).round() as u32).min(height);
+    (pt, bar_h)
+}
+
+/// Stretch the row under the status bar of a fitted `screen` upward to hide whatever the
+/// capture showed there
+fn cover_status_bar(screen: &mut RgbaImage) {
+    let (width, height) = screen.dimensions();
+    let (_, bar_h) = status_bar_metrics(width, height);
+    if bar_h == 0 || bar_h >= height {
+        return;
+    }
+    for x in 0..width {
+        let fill = *screen.get_pixel(x, bar_h);
+        for y in 0..bar_h {
+            screen.put_pixel(x, y, fill);
+        }
+    }
+}
+
+/// Draw the canonical marketing status bar (time, signal, Wi-Fi, battery) across the
+/// top of `screen`, the fitted screenshot at (`screen_x`, `screen_y`). Sizes are in points
+/// of a 402pt-wide iPhone screen.
+fn draw_status_bar(
+    image: &mut RgbaImage,
+    screen: &RgbaImage,
+    screen_x: i32,
+    screen_y: i32,
+    config: &StatusBarConfig,
+    island: Option<DynamicIslandSpec>,
+    config_dir: &Path,
+) -> Result<()> {
+    let (screen_w, screen_h) = screen.dimensions();
+    let (pt, bar_h) = status_bar_metrics(screen_w, screen_h);
+    if bar_h == 0 {
+        return Ok(());
+    }
+
+    let color = match &config.color {
+        Some(raw) => {
+            parse_hex_rgba(raw).with_context(|| format!("invalid status_bar color '{}'", raw))?
+        }
+        None => {
+            // Dark glyphs over bright content, light glyphs over dark content
+            let mut total = 0.0f32;
+            let mut count = 0u32;
+            for y in 0..bar_h {
+                for x in 0..screen_w {
+                    total += relative_luminance(*screen.get_pixel(x, y));
+                    count += 1;
+                }
+            }
+            if count > 0 && total / count as f32 > 0.5 {
+                Rgba([0, 0, 0, 255])
+            } else {
+                Rgba([255, 255, 255, 255])
+            }
+        }
+    };
+
+    // Content sits on the island's centerline and centers in the "ears" either side of it
+    let (center_y, ear_w) = match island {
+        Some(spec) => {
+            let island_w = screen_w as f32 * spec.width_ratio;
+            let island_h = screen_h as f32 * spec.height_ratio;
+            let top = screen_h as f32 * spec.y_offset_ratio;
+            (top + island_h / 2.0, (screen_w as f32 - island_w) / 2.0)
+        }
+        None => (bar_h as f32 / 2.0, screen_w as f32 * 0.4),
+    };
+    let center_y = screen_y as f32 + center_y;
+    let left_center = screen_x as f32 + ear_w * STATUS_BAR_EAR_CENTER;
+    let right_center = (screen_x + screen_w as i32) as f32 - ear_w * STATUS_BAR_EAR_CENTER;
+
+    let font = get_font(FontWeight::SemiBold, None, config_dir)?;
+    let scaled = font.as_scaled(PxScale::from(STATUS_BAR_TIME_PT * pt));
+    let time_w = measure_text_width(&config.time, &scaled);
+    // Center the cap height rather than the full line box
+    let cap_h = scaled.ascent() * 0.72;
+    let time_y = center_y - scaled.ascent() + cap_h / 2.0;
+    let time_x = left_center - time_w / 2.0;
+    draw_text_line(
+        image,
+        &config.time,
+        time_x.round() as i32,
+        time_y.round() as i32,
+        &scaled,
+        TextFill::Solid(color),
+    );
+
+    // Right cluster: signal bars, Wi-Fi fan, battery
+    let signal_w = 4.0 * 3.0 + 3.0 * 1.7;
+    let wifi_w = 15.5;
+    let battery_w = 27.5;
+    let gap = 5.5;
+    let cluster_w = (signal_w + wifi_w + battery_w + gap * 2.0) * pt;
+    let mut x = right_center - cluster_w / 2.0;
+
+    let bottom = center_y + 5.5 * pt;
+    for bar in 0..4 {
+        let height = (4.5 + bar as f32 * 2.3) * pt;
+        fill_rounded_rect(
+            image,
+            x.round() as i32,
+            (bottom - height).round() as i32,
+            (3.0 * pt).round() as u32,
+            height.round() as u32,
+            (pt).round() as u32,
+            color,
+        );
+        x += 4.7 * pt;
+    }
+    x += (gap - 1.7) * pt;
+
+    draw_wifi(image, x + wifi_w * pt / 2.0, bottom, pt, color);
+    x += (wifi_w + gap) * pt;
+
+    draw_battery(image, x, center_y, pt, config.battery, color);
+    Ok(())
+}
+
+/// Three-band Wi-Fi fan with its apex at (`cx`, `bottom`)
+fn draw_wifi(image: &mut RgbaImage, cx: f32, bottom: f32, pt: f32, color: Rgba<u8>) {
+    let bands = [(0.0, 3.4), (5.0, 7.6), (9.3, 11.9)];
+    let reach = 11.9 * pt;
+    let samples = [0.25f32, 0.75];
+    for y in (bottom - reach).floor() as i32..=bottom.ceil() as i32 {
+        for x in (cx - reach).floor() as i32..=(cx + reach).ceil() as i32 {
+            // 2x2 supersampling keeps the arcs smooth
+            let mut hits = 0;
+            for sy in samples {
+                for sx in samples {
+                    let dx = (x as f32 + sx - cx) / pt;
+                    let dy = (bottom - (y as f32 + sy)) / pt;
+                    if dy <= 0.0 || dx.abs() > dy {
+                        continue;
+                    }
+                    let d = (dx * dx + dy * dy).sqrt();
+                    if bands.iter().any(|&(inner, outer)| d >= inner && d <= outer) {
+                        hits += 1;
+                    }
+                }
+            }
+            if hits > 0 {
+                let alpha = (color[3] as u32 * hits / 4) as u8;
+                blend_pixel(image, x, y, Rgba([color[0], color[1], color[2], alpha]));
+            }
+        }
+    }
+}
+
+/// Battery outline with a fill proportional to `level` percent and a terminal nub
+fn draw_battery(image: &mut RgbaImage, x: f32, center_y: f32, pt: f32, level: u8, color: Rgba<u8>) {
+    let body_w = (25.0 * pt).round() as u32;
+    let body_h = (12.0 * pt).round() as u32;
+    let top = (center_y - body_h as f32 / 2.0).round() as i32;
+    let left = x.round() as i32;
+    let dim = Rgba([color[0], color[1], color[2], (color[3] as f32 * 0.4) as u8]);
+
+    // Outline: dim rounded body with the inside punched back out
+    let stroke = (pt).round().max(1.0) as u32;
+    let mut body = RgbaImage::new(body_w, body_h);
+    fill_rounded_rect(&mut body, 0, 0, body_w, body_h, (4.0 * pt) as u32, dim);
+    for (bx, by, pixel) in body.enumerate_pixels_mut() {
+        let inner_w = body_w.saturating_sub(stroke * 2) as i32;
+        let inner_h = body_h.saturating_sub(stroke * 2) as i32;
+        let ix = bx as i32 - stroke as i32;
+        let iy = by as i32 - stroke as i32;
+        if ix >= 0
+            && iy >= 0
+            && ix < inner_w
+            && iy < inner_h
+            && inside_rounded_rect(ix, iy, inner_w, inner_h, (3.0 * pt) as i32)
+        {
+            *pixel = Rgba([0, 0, 0, 0]);
+        }
+    }
+    blit_rounded(image, &body, left, top, 0);
+
+    let inset = (2.0 * pt).round() as u32;
+    let fill_w = body_w.saturating_sub(inset * 2) as f32 * level.min(100) as f32 / 100.0;
+    if fill_w >= 1.0 {
+        fill_rounded_rect(
+            image,
+            left + inset as i32,
+            top + inset as i32,
+            fill_w.round() as u32,
+            body_h.saturating_sub(inset * 2),
+            (2.5 * pt) as u32,
+            color,
+        );
+    }
+
+    let nub_h = (4.0 * pt).round() as u32;
+    fill_rounded_rect(
+        image,
+        left + body_w as i32 + (pt).round() as i32,
+        (center_y - nub_h as f32 / 2.0).round() as i32,
+        (1.5 * pt).round().max(1.0) as u32,
+        nub_h,
+        (pt) as u32,
+        dim,
+    );
+}
+
 fn draw_dynamic_island(
     image: &mut RgbaImage,
     screen_x: i32,
@@ -621,15 +2220,25 @@ fn draw_dynamic_island(
     screen_w: u32,
     screen_h: u32,
     spec: DynamicIslandSpec,
+    orientation: Orientation,
 ) {
-    let island_w = ((screen_w as f32 * spec.width_ratio).round() as u32)
+    // Spec ratios describe the portrait screen; landscape swaps the axes
+    let (portrait_w, portrait_h) = match orientation {
+        Orientation::Portrait => (screen_w, screen_h),
+        Orientation::Landscape => (screen_h, screen_w),
+    };
+    let long = ((portrait_w as f32 * spec.width_ratio).round() as u32)
         .max(48)
-        .min(screen_w.saturating_sub(4));
-    let island_h = ((screen_h as f32 * spec.height_ratio).round() as u32)
+        .min(portrait_w.saturating_sub(4));
+    let short = ((portrait_h as f32 * spec.height_ratio).round() as u32)
         .max(18)
-        .min(screen_h.saturating_sub(2));
-    let island_x = screen_x + ((screen_w.saturating_sub(island_w) / 2) as i32);
-    let island_y = screen_y + ((screen_h as f32 * spec.y_offset_ratio).round() as i32);
+        .min(portrait_h.saturating_sub(2));
+    let offset = (portrait_h as f32 * spec.y_offset_ratio).round() as i32;
+    let centered = (portrait_w.saturating_sub(long) / 2) as i32;
+    let (island_x, island_y, island_w, island_h) = match orientation {
+        Orientation::Portrait => (screen_x + centered, screen_y + offset, long, short),
+        Orientation::Landscape => (screen_x + offset, screen_y + centered, short, long),
+    };
 
     fill_rounded_rect(
         image,
@@ -637,7 +2246,7 @@ fn draw_dynamic_island(
         island_y,
         island_w,
         island_h,
-        island_h / 2,
+        short / 2,
         Rgba([0, 0, 0, 255]),
     );
     fill_rounded_rect(
@@ -646,15 +2255,22 @@ fn draw_dynamic_island(
         island_y + 1,
         island_w.saturating_sub(2),
         island_h.saturating_sub(2),
-        island_h / 2,
+        short / 2,
         Rgba([8, 8, 9, 255]),
     );
 
-    let lens_size = ((island_h as f32 * spec.lens_size_ratio).round() as u32)
+    // The lens sits at the right end in portrait, which a counterclockwise turn moves to the top
+    let lens_size = ((short as f32 * spec.lens_size_ratio).round() as u32)
         .max(4)
-        .min(island_h.saturating_sub(4));
-    let lens_x = island_x + island_w as i32 - lens_size as i32 - (island_h as i32 / 3);
-    let lens_y = island_y + (island_h.saturating_sub(lens_size) / 2) as i32;
+        .min(short.saturating_sub(4));
+    let lens_inset = (short.saturating_sub(lens_size) / 2) as i32;
+    let (lens_x, lens_y) = match orientation {
+        Orientation::Portrait => (
+            island_x + island_w as i32 - lens_size as i32 - (short as i32 / 3),
+            island_y + lens_inset,
+        ),
+        Orientation::Landscape => (island_x + lens_inset, island_y + short as i32 / 3),
+    };
     let lens_r = (lens_size / 2) as i32;
     fill_circle(
         image,
@@ -679,6 +2295,7 @@ fn apply_phone_overlay(
     y: i32,
     width: u32,
     height: u32,
+    tint: Option<Rgba<u8>>,
 ) -> Result<()> {
     let overlay = image::open(overlay_path)
         .with_context(|| format!("failed opening overlay {}", overlay_path.display()))?
@@ -687,11 +2304,17 @@ fn apply_phone_overlay(
 
     for yy in 0..overlay.height() as i32 {
         for xx in 0..overlay.width() as i32 {
-            let pixel = overlay.get_pixel(xx as u32, yy as u32);
+            let mut pixel = *overlay.get_pixel(xx as u32, yy as u32);
             if pixel[3] == 0 {
                 continue;
             }
-            blend_pixel(image, x + xx, y + yy, *pixel);
+            if let Some(tint) = tint {
+                for channel in 0..3 {
+                    pixel[channel] =
+                        ((pixel[channel] as u16 * tint[channel] as u16 + 127) / 255) as u8;
+                }
+            }
+            blend_pixel(image, x + xx, y + yy, pixel);
         }
     }
 
@@ -712,10 +2335,11 @@ fn fill_rounded_rect(
 
     for yy in 0..h {
         for xx in 0..w {
-            if !inside_rounded_rect(xx, yy, w, h, radius as i32) {
+            let coverage = rounded_rect_coverage(xx, yy, w, h, radius as i32);
+            if coverage <= 0.0 {
                 continue;
             }
-            blend_pixel(image, x + xx, y + yy, color);
+            blend_pixel(image, x + xx, y + yy, with_coverage(color, coverage));
         }
     }
 }
@@ -725,15 +2349,17 @@ fn blit_rounded(image: &mut RgbaImage, src: &RgbaImage, x: i32, y: i32, radius:
     let h = src.height() as i32;
     for yy in 0..h {
         for xx in 0..w {
-            if !inside_rounded_rect(xx, yy, w, h, radius as i32) {
+            let coverage = rounded_rect_coverage(xx, yy, w, h, radius as i32);
+            if coverage <= 0.0 {
                 continue;
             }
             let pixel = src.get_pixel(xx as u32, yy as u32);
-            blend_pixel(image, x + xx, y + yy, *pixel);
+            blend_pixel(image, x + xx, y + yy, with_coverage(*pixel, coverage));
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn blit_with_overlay_cutout(
     image: &mut RgbaImage,
     src: &RgbaImage,
@@ -831,7 +2457,61 @@ fn build_inner_cutout_mask(overlay: &RgbaImage) -> Vec<bool> {
 
     // Fill a one-pixel dark semi-transparent fringe around the cutout so
     // anti-aliased overlay pixels blend over screenshot (not background).
-    for _ in 0..OVERLAY_SEMITRANSPARENT_EXPAND_STEPS {
+    let inside = expand_into_dark_fringe(
+        overlay,
+        inside,
+        &outside,
+        OVERLAY_SEMITRANSPARENT_EXPAND_STEPS,
+    );
+
+    if OVERLAY_CUTOUT_GUARD_PX <= 0 {
+        return inside;
+    }
+
+    let mut eroded = vec![false; w * h];
+    for y in 0..h {
+        for x in 0..w {
+            let idx = y * w + x;
+            if !inside[idx] {
+                continue;
+            }
+            let mut ok = true;
+            for dy in -OVERLAY_CUTOUT_GUARD_PX..=OVERLAY_CUTOUT_GUARD_PX {
+                for dx in -OVERLAY_CUTOUT_GUARD_PX..=OVERLAY_CUTOUT_GUARD_PX {
+                    let nx = x as i32 + dx;
+                    let ny = y as i32 + dy;
+                    if nx < 0 || ny < 0 || nx >= w as i32 || ny >= h as i32 {
+                        ok = false;
+                        break;
+                    }
+                    let nidx = ny as usize * w + nx as usize;
+                    if !inside[nidx] {
+                        ok = false;
+                        break;
+                    }
+                }
+                if !ok {
+                    break;
+                }
+            }
+            eroded[idx] = ok;
+        }
+    }
+
+    eroded
+}
+
+/// Grow `inside` by `steps` rings of dark semi-transparent overlay pixels that border it
+/// but not the area outside the frame
+fn expand_into_dark_fringe(
+    overlay: &RgbaImage,
+    mut inside: Vec<bool>,
+    outside: &[bool],
+    steps: usize,
+) -> Vec<bool> {
+    let w = overlay.width() as usize;
+    let h = overlay.height() as usize;
+    for _ in 0..steps {
         let prev_inside = inside.clone();
         let mut expanded = prev_inside.clone();
         for y in 0..h {
@@ -871,42 +2551,7 @@ fn build_inner_cutout_mask(overlay: &RgbaImage) -> Vec<bool> {
         }
         inside = expanded;
     }
-
-    if OVERLAY_CUTOUT_GUARD_PX <= 0 {
-        return inside;
-    }
-
-    let mut eroded = vec![false; w * h];
-    for y in 0..h {
-        for x in 0..w {
-            let idx = y * w + x;
-            if !inside[idx] {
-                continue;
-            }
-            let mut ok = true;
-            for dy in -OVERLAY_CUTOUT_GUARD_PX..=OVERLAY_CUTOUT_GUARD_PX {
-                for dx in -OVERLAY_CUTOUT_GUARD_PX..=OVERLAY_CUTOUT_GUARD_PX {
-                    let nx = x as i32 + dx;
-                    let ny = y as i32 + dy;
-                    if nx < 0 || ny < 0 || nx >= w as i32 || ny >= h as i32 {
-                        ok = false;
-                        break;
-                    }
-                    let nidx = ny as usize * w + nx as usize;
-                    if !inside[nidx] {
-                        ok = false;
-                        break;
-                    }
-                }
-                if !ok {
-                    break;
-                }
-            }
-            eroded[idx] = ok;
-        }
-    }
-
-    eroded
+    inside
 }
 
 fn is_dark_semisolid(overlay: &RgbaImage, x: u32, y: u32) -> bool {
@@ -956,6 +2601,34 @@ fn inside_rounded_rect(px: i32, py: i32, w: i32, h: i32, radius: i32) -> bool {
     dx * dx + dy * dy <= r * r
 }
 
+/// Share of pixel (`px`, `py`) inside a `w`x`h` rounded rect, antialiased along the corner
+/// arcs only; straight edges are pixel-aligned and stay fully covered
+fn rounded_rect_coverage(px: i32, py: i32, w: i32, h: i32, radius: i32) -> f32 {
+    if radius <= 0 {
+        return 1.0;
+    }
+    let r = radius.min(w / 2).min(h / 2);
+    if (px >= r && px < w - r) || (py >= r && py < h - r) {
+        return 1.0;
+    }
+
+    // Distance from the pixel center to the corner circle's center
+    let r = r as f32;
+    let cx = if (px as f32) < r { r } else { w as f32 - r };
+    let cy = if (py as f32) < r { r } else { h as f32 - r };
+    let dx = px as f32 + 0.5 - cx;
+    let dy = py as f32 + 0.5 - cy;
+    (r - (dx * dx + dy * dy).sqrt() + 0.5).clamp(0.0, 1.0)
+}
+
+fn with_coverage(pixel: Rgba<u8>, coverage: f32) -> Rgba<u8> {
+    if coverage >= 1.0 {
+        return pixel;
+    }
+    let alpha = (pixel[3] as f32 * coverage).round() as u8;
+    Rgba([pixel[0], pixel[1], pixel[2], alpha])
+}
+
 fn blend_pixel(image: &mut RgbaImage, x: i32, y: i32, src: Rgba<u8>) {
     if x < 0 || y < 0 {
         return;
@@ -966,20 +2639,353 @@ fn blend_pixel(image: &mut RgbaImage, x: i32, y: i32, src: Rgba<u8>) {
         return;
     }
 
+    // Source-over compositing; on an opaque destination this is a plain alpha mix,
+    // and on transparent layers the coverage is preserved in the output alpha.
     let dst = image.get_pixel(x, y);
-    let alpha = src[3] as f32 / 255.0;
-    let inv = 1.0 - alpha;
-    let out = Rgba([
-        (src[0] as f32 * alpha + dst[0] as f32 * inv)
-            .round()
-            .clamp(0.0, 255.0) as u8,
-        (src[1] as f32 * alpha + dst[1] as f32 * inv)
-            .round()
-            .clamp(0.0, 255.0) as u8,
-        (src[2] as f32 * alpha + dst[2] as f32 * inv)
+    let src_a = src[3] as f32 / 255.0;
+    let dst_a = dst[3] as f32 / 255.0 * (1.0 - src_a);
+    let out_a = src_a + dst_a;
+    if out_a <= 0.0 {
+        return;
+    }
+    let mix = |channel: usize| -> u8 {
+        ((src[channel] as f32 * src_a + dst[channel] as f32 * dst_a) / out_a)
             .round()
-            .clamp(0.0, 255.0) as u8,
-        255,
+            .clamp(0.0, 255.0) as u8
+    };
+    let out = Rgba([
+        mix(0),
+        mix(1),
+        mix(2),
+        (out_a * 255.0).round().clamp(0.0, 255.0) as u8,
     ]);
     image.put_pixel(x, y, out);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CANVAS: (u32, u32) = (1000, 2000);
+
+    fn phone(y: u32, height: u32) -> PhoneConfig {
+        serde_yaml::from_str(&format!(
+            "{{ x: 200, y: {}, width: 600, height: {} }}",
+            y, height
+        ))
+        .expect("phone")
+    }
+
+    /// Draw `copy` onto a transparent canvas, as compose does for the text layer
+    fn render_copy(copy: &CopyConfig, phone: &PhoneConfig, config_dir: &Path) -> RgbaImage {
+        let background = RgbaImage::new(CANVAS.0, CANVAS.1);
+        let mut layer = RgbaImage::new(CANVAS.0, CANVAS.1);
+        let headline = copy.headline.select(0).to_string();
+        draw_copy(
+            &mut layer,
+            Some(&background),
+            copy,
+            &headline,
+            phone,
+            config_dir,
+            &HashMap::new(),
+        )
+        .expect("draw copy");
+        layer
+    }
+
+    /// First and last rows holding a pixel that passes `keep`
+    fn rows(image: &RgbaImage, keep: impl Fn(&Rgba<u8>) -> bool) -> (u32, u32) {
+        let hits: Vec<u32> = image
+            .enumerate_pixels()
+            .filter(|(_, _, pixel)| keep(pixel))
+            .map(|(_, y, _)| y)
+            .collect();
+        (
+            *hits.iter().min().expect("ink"),
+            *hits.iter().max().expect("ink"),
+        )
+    }
+
+    // Green text and a red logo, so each can be found on its own
+    fn is_text(pixel: &Rgba<u8>) -> bool {
+        pixel[3] > 0 && pixel[1] > pixel[0]
+    }
+
+    fn is_logo(pixel: &Rgba<u8>) -> bool {
+        pixel[3] == 255 && pixel[0] > 200 && pixel[1] < 50
+    }
+
+    /// Lay out `copy` on a blank canvas the way compose does, returning the ink and bounds
+    fn layout_copy(copy: &CopyConfig, phone: &PhoneConfig) -> (RgbaImage, TextBlock) {
+        let blank = RgbaImage::new(CANVAS.0, CANVAS.1);
+        let backdrop = Backdrop {
+            image: Some(&blank),
+            panel: None,
+        };
+        let mut layer = RgbaImage::new(CANVAS.0, CANVAS.1);
+        let block = draw_copy_text(
+            &mut layer,
+            copy,
+            copy.headline.select(0),
+            phone,
+            Path::new("."),
+            &HashMap::new(),
+            &backdrop,
+        )
+        .expect("draw copy");
+        (layer, block)
+    }
+
+    fn two_line_copy(position: &str, extra: &str) -> CopyConfig {
+        serde_yaml::from_str(&format!(
+            r##"{{ headline: "Plan your whole week", subheadline: "In one place", color: "#00FF00", max_width: 500, position: {}{} }}"##,
+            position, extra
+        ))
+        .expect("copy")
+    }
+
+    #[test]
+    fn default_line_height_keeps_the_original_copy_layout() {
+        let beside = phone(700, 600);
+        // Where the copy sat before line_height could be set; leaving it unset must not move it
+        for (position, top) in [
+            ("above_phone", 88),
+            ("below_phone", 1388),
+            ("top", 60),
+            ("bottom", 1416),
+            ("center", 738),
+        ] {
+            let (_, block) = layout_copy(&two_line_copy(position, ""), &beside);
+            assert_eq!(block.y, top, "{}", position);
+        }
+
+        // Copy pinned to an edge, or with no room beside the phone, keeps `padding` from it
+        let (_, block) = layout_copy(&two_line_copy("top", ", padding: 24"), &beside);
+        assert_eq!(block.y, 24);
+        let (_, block) = layout_copy(
+            &two_line_copy("above_phone", ", padding: 24"),
+            &phone(40, 1800),
+        );
+        assert_eq!(block.y, 24);
+    }
+
+    #[test]
+    fn extra_line_height_keeps_copy_in_place_beside_the_phone() {
+        let phone = phone(800, 400);
+        let phone_bottom = phone.y + phone.height;
+        for (position, space) in [
+            ("above_phone", (0, phone.y)),
+            ("below_phone", (phone_bottom, CANVAS.1)),
+        ] {
+            let mut layouts = Vec::new();
+            for line_height in [1.2, 1.4, 1.6] {
+                let extra = format!(", line_height: {}", line_height);
+                let (layer, block) = layout_copy(&two_line_copy(position, &extra), &phone);
+                let (top, bottom) = (block.y as u32, block.y as u32 + block.height);
+                let (ink_top, ink_bottom) = rows(&layer, is_text);
+                assert!(ink_top >= top && ink_bottom <= bottom);
+                // Free space above and below the block
+                layouts.push((top - space.0, space.1 - bottom, block.height));
+            }
+
+            // Only the default's leading stays reserved under the block, so the gaps differ
+            // by the same amount however far apart the lines are
+            let offset = |(above, below, _): (u32, u32, u32)| below as i64 - above as i64;
+            for layout in &layouts[1..] {
+                assert!(
+                    offset(*layout).abs_diff(offset(layouts[0])) <= 1,
+                    "{}: {:?}",
+                    position,
+                    layouts
+                );
+            }
+            // Every line but the last gains leading, so the block grows with it
+            assert!(
+                layouts[1].2 > layouts[0].2 && layouts[2].2 > layouts[1].2,
+                "{}: {:?}",
+                position,
+                layouts
+            );
+        }
+    }
+
+    #[test]
+    fn landscape_island_sits_on_the_left_edge_centered_vertically() {
+        let spec = DynamicIslandSpec {
+            width_ratio: 0.31,
+            height_ratio: 0.046,
+            y_offset_ratio: 0.020,
+            lens_size_ratio: 0.36,
+        };
+        let (screen_x, screen_y, screen_w, screen_h) = (100, 300, 800, 400);
+        let mut image = RgbaImage::new(1000, 1000);
+        draw_dynamic_island(
+            &mut image,
+            screen_x,
+            screen_y,
+            screen_w,
+            screen_h,
+            spec,
+            Orientation::Landscape,
+        );
+
+        let painted: Vec<(u32, u32)> = image
+            .enumerate_pixels()
+            .filter(|(_, _, pixel)| pixel[3] > 0)
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        let left = painted.iter().map(|&(x, _)| x).min().expect("island drawn");
+        let right = painted.iter().map(|&(x, _)| x).max().expect("island drawn");
+        let top = painted.iter().map(|&(_, y)| y).min().expect("island drawn");
+        let bottom = painted.iter().map(|&(_, y)| y).max().expect("island drawn");
+
+        // The portrait top edge (800px long once turned) is now the left edge
+        let offset = (screen_w as f32 * spec.y_offset_ratio).round() as u32;
+        assert_eq!(left, screen_x as u32 + offset);
+        // Centered: the gaps above and below match within a pixel
+        let gap_above = top - screen_y as u32;
+        let gap_below = screen_y as u32 + screen_h - 1 - bottom;
+        assert!(gap_above.abs_diff(gap_below) <= 1);
+        // Turned on its side, the pill runs down the screen's short edge
+        let long = (screen_h as f32 * spec.width_ratio).round() as u32;
+        assert_eq!(bottom - top + 1, long);
+        assert!(right - left < bottom - top);
+    }
+
+    #[test]
+    fn bullets_draw_a_dot_and_hang_wrapped_lines_past_it() {
+        let copy: CopyConfig = serde_yaml::from_str(
+            r##"{ headline: "Home", bullets: ["Meals", "Track all the meals at home"], color: "#00FF00", align: left, padding: 40, subheadline_size: 50, max_width: 420, position: top }"##,
+        )
+        .expect("copy");
+        let (layer, _) = layout_copy(&copy, &phone(700, 600));
+
+        // Split the copy into its lines by the blank rows between them
+        let inked = |x: u32, y: u32| layer.get_pixel(x, y)[3] > 0;
+        let mut bands: Vec<(u32, u32)> = Vec::new();
+        for y in 0..CANVAS.1 {
+            if (0..CANVAS.0).any(|x| inked(x, y)) {
+                match bands.last_mut() {
+                    Some((_, end)) if *end + 1 == y => *end = y,
+                    _ => bands.push((y, y)),
+                }
+            }
+        }
+        // The headline, the first bullet, then the second bullet's two lines
+        assert_eq!(bands.len(), 4, "{:?}", bands);
+        let columns = |(top, bottom): (u32, u32), xs: std::ops::Range<u32>| {
+            xs.filter(|&x| (top..=bottom).any(|y| inked(x, y)))
+                .collect::<Vec<_>>()
+        };
+
+        // The dot sits at the left padding, and the text starts one indent past it
+        let radius = (50.0 * BULLET_DOT_RADIUS_RATIO).round() as u32;
+        let indent = (50.0 * BULLET_INDENT_RATIO).ceil() as u32;
+        let dot_center = 40 + radius;
+        for band in [bands[1], bands[2]] {
+            assert!(!columns(band, dot_center..dot_center + 1).is_empty());
+            assert!(columns(band, 40 + 2 * radius + 1..40 + indent - 4).is_empty());
+            assert!(!columns(band, 40 + indent - 4..40 + indent + 8).is_empty());
+        }
+        // The wrapped second line has no dot and hangs at the text's indent
+        let wrapped = columns(bands[3], 0..CANVAS.0);
+        let first_text = columns(bands[2], 40 + 2 * radius + 1..CANVAS.0);
+        assert!(wrapped[0] >= 40 + indent - 4, "{:?}", wrapped.first());
+        assert!(wrapped[0].abs_diff(first_text[0]) <= 4);
+    }
+
+    #[test]
+    fn blend_pixel_composites_partial_alpha_source_over() {
+        let mut image = RgbaImage::from_pixel(2, 1, Rgba([0, 0, 255, 128]));
+        image.put_pixel(1, 0, Rgba([0, 0, 0, 0]));
+        let src = Rgba([255, 0, 0, 128]);
+        blend_pixel(&mut image, 0, 0, src);
+        blend_pixel(&mut image, 1, 0, src);
+
+        // Half red over half blue: alpha 0.5 + 0.5 * 0.5, color weighted by each share of it
+        assert_eq!(*image.get_pixel(0, 0), Rgba([170, 0, 85, 192]));
+        // Onto a transparent layer the source is kept as-is rather than darkened
+        assert_eq!(*image.get_pixel(1, 0), src);
+    }
+
+    #[test]
+    fn overlay_tint_multiplies_color_and_keeps_alpha() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let overlay_path = temp.path().join("frame.png");
+        RgbaImage::from_pixel(4, 4, Rgba([200, 100, 50, 128]))
+            .save(&overlay_path)
+            .expect("write overlay");
+        let apply = |tint: Option<Rgba<u8>>| {
+            let mut image = RgbaImage::new(4, 4);
+            apply_phone_overlay(&mut image, &overlay_path, 0, 0, 4, 4, tint).expect("overlay");
+            image
+        };
+
+        let untinted = apply(None);
+        assert_eq!(*untinted.get_pixel(1, 1), Rgba([200, 100, 50, 128]));
+        // White multiplies by one, so it leaves the frame as drawn
+        assert_eq!(apply(Some(Rgba([255, 255, 255, 255]))), untinted);
+        // Each channel scales by tint / 255; alpha comes from the overlay alone
+        let tinted = apply(Some(Rgba([128, 255, 0, 255])));
+        assert_eq!(*tinted.get_pixel(1, 1), Rgba([100, 100, 0, 128]));
+    }
+
+    #[test]
+    fn soft_mask_feathers_symmetrically_across_the_rect_edge() {
+        let blur = 4;
+        let mask = SoftMask::rounded_rect(40, 40, 0, 0, blur);
+        let margin = mask.margin as usize;
+        let row = &mask.alpha[(margin + 20) * mask.width..][..mask.width];
+
+        // Two box passes of radius 4 spread the edge over 8 pixels on each side
+        assert!(row[0] < 0.02, "{}", row[0]);
+        assert!((row[margin + 2 * blur as usize] - 1.0).abs() < 1e-5);
+        assert!(row[..=margin + 8].windows(2).all(|pair| pair[0] <= pair[1]));
+        // The falloff mirrors around the edge, so the rect keeps its size once blurred
+        for step in 0..8 {
+            let sum = row[margin - 1 - step] + row[margin + step];
+            assert!((sum - 1.0).abs() < 1e-5, "{step}: {sum}");
+        }
+    }
+
+    #[test]
+    fn logo_moves_the_text_block_as_one_unit() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        RgbaImage::from_pixel(60, 100, Rgba([255, 0, 0, 255]))
+            .save(temp.path().join("logo.png"))
+            .expect("write logo");
+        let phone = phone(1200, 700);
+        let copy = |position: &str, logo: bool| -> CopyConfig {
+            let logo = if logo {
+                "logo: { path: logo.png, height: 100, gap: 40 }"
+            } else {
+                ""
+            };
+            serde_yaml::from_str(&format!(
+                "{{ headline: Plan, color: \"#00FF00\", position: {}, {} }}",
+                position, logo
+            ))
+            .expect("copy")
+        };
+
+        for (position, shift) in [("above_phone", 70), ("top", 140)] {
+            let plain = render_copy(&copy(position, false), &phone, temp.path());
+            let branded = render_copy(&copy(position, true), &phone, temp.path());
+            let (text_top, text_bottom) = rows(&plain, is_text);
+            let (logo_top, logo_bottom) = rows(&branded, is_logo);
+
+            // Centering above the phone splits the 140px lockup across both sides; `top`
+            // keeps the block's top edge and pushes the text down by all of it
+            assert_eq!(
+                rows(&branded, is_text),
+                (text_top + shift, text_bottom + shift),
+                "{}",
+                position
+            );
+            assert_eq!(logo_bottom - logo_top + 1, 100);
+            assert!(logo_bottom + 40 < text_top + shift, "{}", position);
+            assert!(text_bottom + shift < phone.y, "{}", position);
+        }
+    }
+}
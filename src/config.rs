@@ -1,35 +1,350 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
 
+use crate::devices::DEVICE_LISTINGS;
+use crate::frames::model_slug;
+use crate::presets::{SizePreset, resolve_size};
+
 #[derive(Debug, Deserialize)]
 pub struct Config {
     #[serde(default = "default_output_dir")]
     pub output_dir: PathBuf,
+    /// Font files that replace the embedded Geist font for specific weights
+    #[serde(default)]
+    pub fonts: HashMap<FontWeight, PathBuf>,
+    /// Custom device profiles keyed by slug; `phone.model` can name one instead of a
+    /// built-in model
+    #[serde(default)]
+    pub devices: HashMap<String, DeviceProfileConfig>,
     pub scenes: Vec<SceneConfig>,
 }
 
 impl Config {
     pub fn from_path(path: &Path) -> Result<Self> {
+        Self::load(path).map(|(config, _)| config)
+    }
+
+    /// Load the config along with the YAML it was built from, after variable substitution
+    /// and with `defaults` merged into the scenes
+    pub(crate) fn load(path: &Path) -> Result<(Self, serde_yaml::Value)> {
         let raw = fs::read_to_string(path)
             .with_context(|| format!("failed to read config file: {}", path.display()))?;
-        let parsed: Self = serde_yaml::from_str(&raw)
+        let mut value: serde_yaml::Value = serde_yaml::from_str(&raw)
             .with_context(|| format!("failed to parse yaml: {}", path.display()))?;
-        Ok(parsed)
+
+        // Variables fill in parsed string values, so their text is never read as YAML and
+        // references in comments are ignored
+        let vars =
+            read_vars(&value).with_context(|| format!("invalid `vars` in {}", path.display()))?;
+        let lookup = |name: &str| std::env::var(name).ok().or_else(|| vars.get(name).cloned());
+        let substituted = substitute_value_vars(&mut value, "", &lookup)
+            .with_context(|| format!("failed to substitute variables in {}", path.display()))?;
+        // Deserializing text rather than the value keeps plain scalars readable as strings or
+        // numbers alike, as the user wrote them
+        let raw = if substituted {
+            serde_yaml::to_string(&value)?
+        } else {
+            raw
+        };
+
+        // Check for the scenes key up front so a missing list doesn't surface as a raw serde error
+        if value.get("scenes").is_none() {
+            bail!(
+                "config {} has no `scenes` list; {}",
+                path.display(),
+                SCAFFOLD_HINT
+            );
+        }
+
+        // Parsing the text keeps line numbers in errors, so only go through the merged
+        // value when there are defaults to apply
+        let parsed: Self = match value.get("defaults").cloned() {
+            Some(defaults) => {
+                apply_defaults(&mut value, &defaults)
+                    .with_context(|| format!("invalid `defaults` in {}", path.display()))?;
+                serde_yaml::from_value(value.clone())
+            }
+            None => serde_yaml::from_str(&raw),
+        }
+        .with_context(|| format!("failed to parse yaml: {}", path.display()))?;
+        if parsed.scenes.is_empty() {
+            bail!(
+                "config {} has an empty `scenes` list; {}",
+                path.display(),
+                SCAFFOLD_HINT
+            );
+        }
+        for scene in &parsed.scenes {
+            for (_, phone) in scene.devices() {
+                if let Some(ModelRef::Custom(slug)) = &phone.model
+                    && !parsed.devices.contains_key(slug)
+                {
+                    bail!(
+                        "scene '{}' uses unknown phone model '{}'; use a built-in model ({}) or define it under `devices`",
+                        scene.id,
+                        slug,
+                        DEVICE_LISTINGS
+                            .iter()
+                            .map(|device| device.slug)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                }
+            }
+        }
+        Ok((parsed, value))
     }
 }
 
-#[derive(Debug, Deserialize)]
+/// Merge the top-level `defaults:` mapping into every scene: keys a scene sets win, and
+/// nested mappings (`phone`, `background`, `output`, ...) merge key by key
+fn apply_defaults(value: &mut serde_yaml::Value, defaults: &serde_yaml::Value) -> Result<()> {
+    if !defaults.is_mapping() {
+        bail!("expected a mapping of scene keys");
+    }
+    // A `phones`-only scene has no main device for `capture`/`phone` defaults to complete
+    let mut device_defaults = defaults.clone();
+    if let Some(mapping) = device_defaults.as_mapping_mut() {
+        mapping.remove("capture");
+        mapping.remove("phone");
+    }
+    if let Some(scenes) = value.get_mut("scenes").and_then(|s| s.as_sequence_mut()) {
+        for scene in scenes {
+            let phones_only = scene.get("phones").is_some()
+                && scene.get("capture").is_none()
+                && scene.get("phone").is_none();
+            if phones_only {
+                merge_missing(scene, &device_defaults);
+            } else {
+                merge_missing(scene, defaults);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn merge_missing(target: &mut serde_yaml::Value, defaults: &serde_yaml::Value) {
+    let (Some(target), Some(defaults)) = (target.as_mapping_mut(), defaults.as_mapping()) else {
+        return;
+    };
+    // A scene's own `size` preset would lose to default `width`/`height`, which override it
+    let has_size = target.contains_key("size");
+    for (key, default) in defaults {
+        if has_size && (key == "width" || key == "height") {
+            continue;
+        }
+        match target.get_mut(key) {
+            Some(existing) => merge_missing(existing, default),
+            None => {
+                target.insert(key.clone(), default.clone());
+            }
+        }
+    }
+}
+
+/// The optional top-level `vars:` map
+fn read_vars(value: &serde_yaml::Value) -> Result<HashMap<String, String>> {
+    let Some(vars) = value.get("vars") else {
+        return Ok(HashMap::new());
+    };
+    let vars: HashMap<String, serde_yaml::Value> = serde_yaml::from_value(vars.clone())
+        .context("expected a map of variable names to values")?;
+    vars.into_iter()
+        .map(|(name, value)| {
+            let text = match value {
+                serde_yaml::Value::String(text) => text,
+                serde_yaml::Value::Number(number) => number.to_string(),
+                serde_yaml::Value::Bool(flag) => flag.to_string(),
+                _ => bail!("variable `{}` must be a string, number or boolean", name),
+            };
+            Ok((name, text))
+        })
+        .collect()
+}
+
+/// Substitute variables in every string value under `value`, `at` being its key path;
+/// returns whether anything changed. A value that is nothing but a reference to a number or
+/// boolean takes that type, so `width: ${WIDTH}` still reads as a number.
+fn substitute_value_vars(
+    value: &mut serde_yaml::Value,
+    at: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<bool> {
+    let mut changed = false;
+    match value {
+        serde_yaml::Value::String(text) if text.contains("${") => {
+            let whole_reference = text.starts_with("${") && text.find('}') == Some(text.len() - 1);
+            let replaced = substitute_vars(text, lookup).with_context(|| format!("at `{}`", at))?;
+            *value = match serde_yaml::from_str(&replaced) {
+                Ok(serde_yaml::Value::Number(number))
+                    if whole_reference && number.to_string() == replaced =>
+                {
+                    serde_yaml::Value::Number(number)
+                }
+                Ok(serde_yaml::Value::Bool(flag))
+                    if whole_reference && flag.to_string() == replaced =>
+                {
+                    serde_yaml::Value::Bool(flag)
+                }
+                _ => serde_yaml::Value::String(replaced),
+            };
+            changed = true;
+        }
+        serde_yaml::Value::Sequence(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                changed |= substitute_value_vars(item, &format!("{}[{}]", at, index), lookup)?;
+            }
+        }
+        serde_yaml::Value::Mapping(mapping) => {
+            for (key, item) in mapping.iter_mut() {
+                let key = key.as_str().unwrap_or("?");
+                let at = if at.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}.{}", at, key)
+                };
+                changed |= substitute_value_vars(item, &at, lookup)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(changed)
+}
+
+/// Replace every `${NAME}` in `text` with `lookup(NAME)`; `$${` stays a literal `${`
+fn substitute_vars(text: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        if rest[..start].ends_with('$') {
+            out.push_str(&rest[..start - 1]);
+            out.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start + 2..].find('}') else {
+            bail!("unterminated `${{` (expected `${{NAME}}`)");
+        };
+        let name = &rest[start + 2..start + 2 + len];
+        match lookup(name) {
+            Some(value) => out.push_str(&value),
+            None => bail!(
+                "undefined variable `{}`; set it in the environment or under `vars`",
+                name
+            ),
+        }
+        rest = &rest[start + 2 + len + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+const SCAFFOLD_HINT: &str =
+    "add at least one scene (see screenforge.yaml in the repository for a complete example)";
+
+/// A scene takes its devices from `capture` plus `phone`, from a `phones` list, or from both
+/// (the `capture`/`phone` pair is drawn first)
+#[derive(Debug, Deserialize, Clone)]
+#[serde(try_from = "SceneConfigInput")]
 pub struct SceneConfig {
     pub id: String,
-    pub capture: CaptureConfig,
     pub output: OutputConfig,
     pub background: BackgroundConfig,
-    pub phone: PhoneConfig,
-    #[serde(default)]
+    /// Every device in draw order, each drawn in front of the last; never empty
+    pub phones: Vec<PhoneInstance>,
+    /// Whether `phones[0]` came from the scene's own `capture`/`phone` keys
+    pub main_phone: bool,
     pub copy: Option<CopyConfig>,
+    /// Effects applied to the whole composition; none when unset
+    pub post: Option<PostConfig>,
+}
+
+impl SceneConfig {
+    /// Every device in draw order as `(capture, phone)` pairs
+    pub fn devices(&self) -> impl Iterator<Item = (&CaptureConfig, &PhoneConfig)> {
+        self.phones
+            .iter()
+            .map(|instance| (&instance.capture, &instance.phone))
+    }
+
+    /// The first device, which text layout and the manifest's phone model follow
+    pub fn phone(&self) -> &PhoneConfig {
+        &self.phones[0].phone
+    }
+
+    /// How the config refers to device `index`: `phone` for the scene's own key, otherwise
+    /// its position in `phones`
+    pub fn device_label(&self, index: usize) -> String {
+        match (self.main_phone, index) {
+            (true, 0) => "phone".to_string(),
+            (true, n) => format!("phones[{}]", n - 1),
+            (false, n) => format!("phones[{}]", n),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SceneConfigInput {
+    id: String,
+    #[serde(default)]
+    capture: Option<CaptureConfig>,
+    output: OutputConfig,
+    background: BackgroundConfig,
+    #[serde(default)]
+    phone: Option<PhoneConfig>,
+    #[serde(default)]
+    phones: Vec<PhoneInstance>,
+    #[serde(default)]
+    copy: Option<CopyConfig>,
+    #[serde(default)]
+    post: Option<PostConfig>,
+}
+
+impl TryFrom<SceneConfigInput> for SceneConfig {
+    type Error = String;
+
+    fn try_from(input: SceneConfigInput) -> std::result::Result<Self, Self::Error> {
+        let main = match (input.capture, input.phone) {
+            (Some(capture), Some(phone)) => Some(PhoneInstance { capture, phone }),
+            (None, None) => None,
+            (Some(_), None) => {
+                return Err(format!("scene '{}' has `capture` but no `phone`", input.id));
+            }
+            (None, Some(_)) => {
+                return Err(format!("scene '{}' has `phone` but no `capture`", input.id));
+            }
+        };
+        if main.is_none() && input.phones.is_empty() {
+            return Err(format!(
+                "scene '{}' needs `capture` and `phone`, or a `phones` list",
+                input.id
+            ));
+        }
+        Ok(Self {
+            id: input.id,
+            output: input.output,
+            background: input.background,
+            main_phone: main.is_some(),
+            phones: main.into_iter().chain(input.phones).collect(),
+            copy: input.copy,
+            post: input.post,
+        })
+    }
+}
+
+/// A device in a scene: a capture source plus the usual phone placement keys
+#[derive(Debug, Deserialize, Clone)]
+pub struct PhoneInstance {
+    pub capture: CaptureConfig,
+    #[serde(flatten)]
+    pub phone: PhoneConfig,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
@@ -38,26 +353,216 @@ pub enum PhoneModel {
     Iphone17Pro,
     #[serde(rename = "iphone_17_pro_max")]
     Iphone17ProMax,
+    #[serde(rename = "ipad_pro_11")]
+    IpadPro11,
+    #[serde(rename = "ipad_pro_13")]
+    IpadPro13,
 }
 
-#[derive(Debug, Deserialize)]
+/// `phone.model`: a built-in model, or the slug of a profile under the top-level `devices`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ModelRef {
+    BuiltIn(PhoneModel),
+    Custom(String),
+}
+
+impl ModelRef {
+    pub fn built_in(&self) -> Option<PhoneModel> {
+        match self {
+            Self::BuiltIn(model) => Some(*model),
+            Self::Custom(_) => None,
+        }
+    }
+
+    /// Key into `devices` and stem of the auto-loaded overlay file
+    pub fn slug(&self) -> &str {
+        match self {
+            Self::BuiltIn(model) => model_slug(*model),
+            Self::Custom(slug) => slug,
+        }
+    }
+}
+
+impl From<PhoneModel> for ModelRef {
+    fn from(model: PhoneModel) -> Self {
+        Self::BuiltIn(model)
+    }
+}
+
+/// Frame geometry for a device without a built-in profile. Keys a scene's `phone` sets to
+/// something other than the default still win over the profile.
+#[derive(Debug, Deserialize, Clone)]
+pub struct DeviceProfileConfig {
+    /// Pixels (`88`) or a percentage of the phone's shorter side (`"22%"`)
+    #[serde(default = "default_corner_radius")]
+    pub corner_radius: CornerRadius,
+    #[serde(default)]
+    pub screen_padding: Insets,
+    #[serde(default = "default_frame_color")]
+    pub frame_color: String,
+    #[serde(default = "default_frame_border_width")]
+    pub frame_border_width: u32,
+    #[serde(default = "default_shadow_offset_y")]
+    pub shadow_offset_y: i32,
+    #[serde(default = "default_shadow_alpha")]
+    pub shadow_alpha: u8,
+    /// Dynamic Island cutout; none when unset
+    #[serde(default)]
+    pub island: Option<IslandConfig>,
+    /// Draw a home indicator by default (default: on when the device has an island)
+    #[serde(default)]
+    pub home_indicator: Option<bool>,
+}
+
+/// Island geometry as fractions of the screen: `width_ratio` of its width, `height_ratio` and
+/// `y_offset_ratio` of its height
+#[derive(Debug, Deserialize, Clone, Copy)]
+pub struct IslandConfig {
+    #[serde(default = "default_island_width_ratio")]
+    pub width_ratio: f32,
+    #[serde(default = "default_island_height_ratio")]
+    pub height_ratio: f32,
+    #[serde(default = "default_island_y_offset_ratio")]
+    pub y_offset_ratio: f32,
+    /// Camera lens diameter as a fraction of the island height
+    #[serde(default = "default_island_lens_size_ratio")]
+    pub lens_size_ratio: f32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
 #[serde(tag = "adapter", rename_all = "snake_case")]
 pub enum CaptureConfig {
     File {
         path: PathBuf,
+        /// What to do when the source image has transparent pixels
+        #[serde(default)]
+        alpha: AlphaMode,
+        /// Color transparent pixels are flattened onto (default: #FFFFFF)
+        #[serde(default = "default_alpha_color")]
+        alpha_color: String,
+    },
+    /// Crop a rectangle out of a larger source image (e.g. a spritesheet)
+    Region {
+        path: PathBuf,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        #[serde(default)]
+        alpha: AlphaMode,
+        #[serde(default = "default_alpha_color")]
+        alpha_color: String,
     },
     Simctl {
         device: String,
         #[serde(default = "default_settle_ms")]
         settle_ms: u64,
     },
+    /// Screenshot an Android device or emulator through `adb`
+    Adb {
+        /// Device serial as listed by `adb devices` (e.g. `emulator-5554`)
+        serial: String,
+        #[serde(default = "default_settle_ms")]
+        settle_ms: u64,
+    },
+    /// Run your own capture script; `{output}` in `args` is replaced with the path the
+    /// PNG must be written to. Runs in the config directory.
+    Command {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+        /// Wait before running the command (default: 0)
+        #[serde(default)]
+        settle_ms: u64,
+    },
 }
 
-#[derive(Debug, Deserialize)]
+/// Handling for transparency in file-based captures
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AlphaMode {
+    /// Composite transparent pixels onto `alpha_color` so the screenshot is opaque
+    #[default]
+    Flatten,
+    /// Keep transparency and report a run warning
+    Warn,
+    /// Keep transparency silently (the background shows through)
+    Preserve,
+}
+
+/// `width`/`height` may be replaced by a `size` preset such as `app-store-6-9`
+#[derive(Debug, Deserialize, Clone)]
+#[serde(try_from = "OutputConfigInput")]
 pub struct OutputConfig {
     pub filename: String,
     pub width: u32,
     pub height: u32,
+    /// Extra target sizes rendered from this scene into `final/<name>/<filename>`. When set,
+    /// `width`/`height` are only the size the layout was designed at and are not rendered.
+    pub variants: Vec<OutputVariant>,
+}
+
+/// One display size for a multi-resolution scene
+#[derive(Debug, Deserialize, Clone)]
+#[serde(try_from = "OutputVariantInput")]
+pub struct OutputVariant {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Deserialize)]
+struct OutputConfigInput {
+    filename: String,
+    #[serde(default)]
+    size: Option<SizePreset>,
+    #[serde(default)]
+    width: Option<u32>,
+    #[serde(default)]
+    height: Option<u32>,
+    #[serde(default)]
+    variants: Vec<OutputVariant>,
+}
+
+impl TryFrom<OutputConfigInput> for OutputConfig {
+    type Error = String;
+
+    fn try_from(input: OutputConfigInput) -> std::result::Result<Self, Self::Error> {
+        let (width, height) = resolve_size(input.size, input.width, input.height)
+            .map_err(|err| format!("output {}: {}", input.filename, err))?;
+        Ok(Self {
+            filename: input.filename,
+            width,
+            height,
+            variants: input.variants,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct OutputVariantInput {
+    name: String,
+    #[serde(default)]
+    size: Option<SizePreset>,
+    #[serde(default)]
+    width: Option<u32>,
+    #[serde(default)]
+    height: Option<u32>,
+}
+
+impl TryFrom<OutputVariantInput> for OutputVariant {
+    type Error = String;
+
+    fn try_from(input: OutputVariantInput) -> std::result::Result<Self, Self::Error> {
+        let (width, height) = resolve_size(input.size, input.width, input.height)
+            .map_err(|err| format!("output variant {}: {}", input.name, err))?;
+        Ok(Self {
+            name: input.name,
+            width,
+            height,
+        })
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -74,6 +579,140 @@ pub struct BackgroundConfig {
     /// Strategy for generating palette from extracted colors
     #[serde(default)]
     pub auto_strategy: AutoColorStrategy,
+    /// Optional radial vignette applied on top of any template
+    #[serde(default)]
+    pub vignette: Option<VignetteConfig>,
+    /// Map mesh corners (top-left, top-right, bottom-left, bottom-right) and stripe colors
+    /// to the colors in list order instead of picking them by seed
+    #[serde(default)]
+    pub deterministic_layout: bool,
+    /// PNG/JPEG backdrop scaled to cover the canvas, relative to the config file.
+    /// When set it replaces `template`.
+    #[serde(default)]
+    pub image: Option<PathBuf>,
+    /// Options specific to the mesh template
+    #[serde(default)]
+    pub mesh: MeshConfig,
+    /// Options specific to the stripes template
+    #[serde(default)]
+    pub stripes: StripesConfig,
+    /// Options specific to the radial template
+    #[serde(default)]
+    pub radial: RadialConfig,
+    /// Options specific to the blurred_screenshot template
+    #[serde(default)]
+    pub blur: BlurConfig,
+    /// Scale for the noise grain in mesh, stripes and radial templates; 0 disables it (default: 1.0)
+    #[serde(default = "default_grain")]
+    pub grain: f32,
+    /// Direction of the linear template in degrees: 0 = left to right, 90 = top to bottom (default: 90)
+    #[serde(default = "default_gradient_angle")]
+    pub angle: f32,
+    /// Start from a fully transparent canvas instead of rendering `template`/`image`
+    #[serde(default)]
+    pub transparent: bool,
+    /// Blend template colors in linear light instead of sRGB, avoiding dark bands between
+    /// saturated colors
+    #[serde(default)]
+    pub linear_blend: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct RadialConfig {
+    /// Horizontal focal point, 0 = left edge, 1 = right edge (default: 0.5)
+    #[serde(default = "default_radial_center")]
+    pub center_x: f32,
+    /// Vertical focal point, 0 = top edge, 1 = bottom edge (default: 0.5)
+    #[serde(default = "default_radial_center")]
+    pub center_y: f32,
+}
+
+impl Default for RadialConfig {
+    fn default() -> Self {
+        Self {
+            center_x: default_radial_center(),
+            center_y: default_radial_center(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct BlurConfig {
+    /// Gaussian blur strength in output pixels (default: 48)
+    #[serde(default = "default_blur_sigma")]
+    pub sigma: f32,
+}
+
+impl Default for BlurConfig {
+    fn default() -> Self {
+        Self {
+            sigma: default_blur_sigma(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MeshConfig {
+    /// Scale for the built-in edge darkening; 0 removes it (default: 1.0)
+    #[serde(default = "default_mesh_vignette")]
+    pub vignette: f32,
+}
+
+impl Default for MeshConfig {
+    fn default() -> Self {
+        Self {
+            vignette: default_mesh_vignette(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct StripesConfig {
+    /// Stripe direction: 0 = horizontal, 90 = vertical (default: the classic 45° diagonal)
+    #[serde(default)]
+    pub angle_degrees: Option<f32>,
+    /// Optional wide diagonal band drawn over the base stripes
+    #[serde(default)]
+    pub accent_beam: Option<AccentBeam>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AccentBeam {
+    /// Index into the background colors list
+    pub color: usize,
+    /// Band width in pixels (default: 260)
+    #[serde(default = "default_beam_width")]
+    pub width: u32,
+    /// Angle in degrees from horizontal (default: 60)
+    #[serde(default = "default_beam_angle")]
+    pub angle: f32,
+    /// Where the band crosses the vertical center line, 0 = left edge, 1 = right edge (default: 0.5)
+    #[serde(default = "default_beam_position")]
+    pub position: f32,
+}
+
+/// Whole-image effects run after the scene is composed
+#[derive(Debug, Deserialize, Clone)]
+pub struct PostConfig {
+    /// `[shadow, highlight]`: every pixel's luma picks a color on the gradient between the
+    /// two, alpha untouched
+    #[serde(default)]
+    pub duotone: Option<[String; 2]>,
+    /// Keep the phone screens in their original colors (default: false)
+    #[serde(default)]
+    pub preserve_screen: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct VignetteConfig {
+    /// How far edge pixels are pulled toward the vignette color, 0-1 (default: 0.45)
+    #[serde(default = "default_vignette_strength")]
+    pub strength: f32,
+    /// Normalized distance from center where the falloff begins, 0-1 (default: 0.55)
+    #[serde(default = "default_vignette_radius")]
+    pub radius: f32,
+    #[serde(default = "default_vignette_color")]
+    pub color: String,
 }
 
 #[derive(Debug, Deserialize, Clone, Copy, Default)]
@@ -90,24 +729,49 @@ pub enum AutoColorStrategy {
     Triadic,
 }
 
-#[derive(Debug, Deserialize, Clone, Copy, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum BackgroundTemplate {
     #[default]
     Mesh,
     Stripes,
+    Radial,
+    /// Flat fill with the first color
+    Solid,
+    /// Noise-free gradient between the first two colors along `angle`
+    Linear,
+    /// Evenly spaced dot grid in the last color over a soft gradient
+    Dots,
+    /// The scene's own screenshot scaled to cover the canvas and blurred (frosted glass)
+    BlurredScreenshot,
 }
 
-#[derive(Debug, Deserialize)]
+impl BackgroundTemplate {
+    /// Config spelling of the template, e.g. `mesh`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Mesh => "mesh",
+            Self::Stripes => "stripes",
+            Self::Radial => "radial",
+            Self::Solid => "solid",
+            Self::Linear => "linear",
+            Self::Dots => "dots",
+            Self::BlurredScreenshot => "blurred_screenshot",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
 pub struct PhoneConfig {
     #[serde(default)]
-    pub model: Option<PhoneModel>,
+    pub model: Option<ModelRef>,
     pub x: u32,
     pub y: u32,
     pub width: u32,
     pub height: u32,
+    /// Pixels (`88`) or a percentage of the phone's shorter side (`"22%"`)
     #[serde(default = "default_corner_radius")]
-    pub corner_radius: u32,
+    pub corner_radius: CornerRadius,
     #[serde(default)]
     pub screen_padding: Insets,
     #[serde(default = "default_frame_color")]
@@ -118,11 +782,147 @@ pub struct PhoneConfig {
     pub shadow_offset_y: i32,
     #[serde(default = "default_shadow_alpha")]
     pub shadow_alpha: u8,
+    /// How far the shadow's edge fades out in pixels, like a CSS `box-shadow` blur radius;
+    /// 0 keeps the hard-edged shadow
+    #[serde(default)]
+    pub shadow_blur: u32,
+    /// Grow the shadow past the phone rect by this many pixels on every side (negative
+    /// shrinks it)
+    #[serde(default)]
+    pub shadow_spread: i32,
     #[serde(default)]
     pub overlay: Option<PathBuf>,
+    /// Color multiplied into the overlay's RGB, alpha untouched, so one frame PNG can be
+    /// recolored per scene; white leaves it as-is
+    #[serde(default)]
+    pub overlay_tint: Option<String>,
+    /// Draw a home indicator pill at the bottom of the screen (default: on for models
+    /// with a Dynamic Island, off otherwise)
+    #[serde(default)]
+    pub home_indicator: Option<bool>,
+    /// Draw a subtle rim around the phone when the frame blends into the background
+    #[serde(default)]
+    pub auto_rim_light: bool,
+    /// Minimum frame/background contrast ratio before the rim is drawn (default: 1.6)
+    #[serde(default = "default_min_frame_contrast")]
+    pub min_frame_contrast: f32,
+    /// Clockwise rotation of the whole device (frame, shadow and screenshot) about its center
+    #[serde(default)]
+    pub rotation_degrees: f32,
+    /// 3D tilt applied to the whole device before `rotation_degrees`
+    #[serde(default)]
+    pub perspective: Option<Perspective>,
+    /// Synthetic status bar drawn over the top of the screen
+    #[serde(default)]
+    pub status_bar: Option<StatusBarConfig>,
+    /// Colored halo behind the device (alpha honored); no glow when unset
+    #[serde(default)]
+    pub glow_color: Option<String>,
+    /// How far the glow spreads past the device edge in pixels (default: 60)
+    #[serde(default = "default_glow_radius")]
+    pub glow_radius: u32,
+    /// Mirror the device below itself, fading out like a glossy floor
+    #[serde(default)]
+    pub reflection: bool,
+    /// Opacity of the reflection where it meets the device (default: 0.25)
+    #[serde(default = "default_reflection_opacity")]
+    pub reflection_opacity: f32,
+    /// Gap between the device's bottom edge and its reflection in pixels (default: 8)
+    #[serde(default = "default_reflection_gap")]
+    pub reflection_gap: u32,
+    /// Landscape turns the device a quarter turn counterclockwise: the island moves to the
+    /// left edge and model insets rotate with it. `width`/`height` stay the on-canvas box.
+    #[serde(default)]
+    pub orientation: Orientation,
 }
 
-#[derive(Debug, Deserialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Orientation {
+    #[default]
+    Portrait,
+    Landscape,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct StatusBarConfig {
+    /// Clock text (default: 9:41)
+    #[serde(default = "default_status_bar_time")]
+    pub time: String,
+    /// Battery charge in percent (default: 100)
+    #[serde(default = "default_status_bar_battery")]
+    pub battery: u8,
+    /// Glyph color; picked from the screen's brightness when unset
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Paint over the captured status bar before drawing (default: true)
+    #[serde(default = "default_status_bar_cover")]
+    pub cover: bool,
+}
+
+/// Tilt factors: each is how much shorter the receding edge gets (0.2 = 20% shorter)
+#[derive(Debug, Deserialize, Clone, Copy, Default)]
+pub struct Perspective {
+    /// Positive pushes the right edge away, negative the left
+    #[serde(default)]
+    pub x: f32,
+    /// Positive pushes the top edge away, negative the bottom
+    #[serde(default)]
+    pub y: f32,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(try_from = "CornerRadiusInput")]
+pub enum CornerRadius {
+    Pixels(u32),
+    /// Percentage of the shorter phone side, capped at 50 (a full pill)
+    Percent(f32),
+}
+
+impl CornerRadius {
+    pub fn to_pixels(self, width: u32, height: u32) -> u32 {
+        match self {
+            Self::Pixels(px) => px,
+            Self::Percent(percent) => {
+                let shorter = width.min(height) as f32;
+                (shorter * percent.clamp(0.0, 50.0) / 100.0).round() as u32
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CornerRadiusInput {
+    Number(u32),
+    Text(String),
+}
+
+impl TryFrom<CornerRadiusInput> for CornerRadius {
+    type Error = String;
+
+    fn try_from(input: CornerRadiusInput) -> std::result::Result<Self, Self::Error> {
+        let text = match input {
+            CornerRadiusInput::Number(px) => return Ok(Self::Pixels(px)),
+            CornerRadiusInput::Text(text) => text,
+        };
+        let trimmed = text.trim();
+        let parsed = if let Some(percent) = trimmed.strip_suffix('%') {
+            percent.trim().parse::<f32>().ok().map(Self::Percent)
+        } else {
+            let px = trimmed.strip_suffix("px").unwrap_or(trimmed);
+            px.trim().parse::<u32>().ok().map(Self::Pixels)
+        };
+        parsed.ok_or_else(|| {
+            format!(
+                "invalid corner_radius '{}' (expected pixels like 88 or a percentage like \"22%\")",
+                text
+            )
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
 pub struct Insets {
     pub top: u32,
     pub right: u32,
@@ -141,7 +941,7 @@ impl Default for Insets {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, Copy, Default)]
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum FontWeight {
     Regular,
@@ -163,15 +963,95 @@ pub enum TextPosition {
     Top,
     /// Text at bottom of canvas (with padding)
     Bottom,
+    /// Text block centered vertically in the full canvas, ignoring the phone
+    Center,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GradientDirection {
+    /// First color at the top of the copy block, second at the bottom
+    #[default]
+    Vertical,
+    /// First color at the left edge of the copy block, second at the right
+    Horizontal,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TextAlign {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+/// Case change applied to the headline and subheadline before layout
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TextTransform {
+    #[default]
+    None,
+    Uppercase,
+    Lowercase,
+    /// Capitalize the first letter of every word, leaving the rest as written
+    Titlecase,
+}
+
+impl TextTransform {
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            Self::None => text.to_string(),
+            Self::Uppercase => text.to_uppercase(),
+            Self::Lowercase => text.to_lowercase(),
+            Self::Titlecase => {
+                let mut out = String::with_capacity(text.len());
+                let mut word_start = true;
+                for ch in text.chars() {
+                    if word_start {
+                        out.extend(ch.to_uppercase());
+                    } else {
+                        out.push(ch);
+                    }
+                    word_start = ch.is_whitespace();
+                }
+                out
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+#[derive(Debug, Deserialize, Clone)]
 pub struct CopyConfig {
-    pub headline: String,
+    /// A single headline, or a list of variants picked deterministically from the background seed
+    pub headline: Headline,
     #[serde(default)]
     pub subheadline: String,
+    /// Feature bullet points drawn below the subheadline in its font, each behind a dot
+    #[serde(default)]
+    pub bullets: Vec<String>,
     #[serde(default = "default_copy_color")]
     pub color: String,
+    /// Replace `color` with black or white, whichever contrasts more with the background
+    /// under the copy block
+    #[serde(default)]
+    pub auto_contrast: bool,
+    /// Two-stop gradient fill for the text, overriding `color` when set
+    #[serde(default)]
+    pub color_gradient: Option<[String; 2]>,
+    /// Axis the gradient runs along across the copy block (default: vertical)
+    #[serde(default)]
+    pub gradient_direction: GradientDirection,
     /// Vertical position preset (default: above_phone)
     #[serde(default)]
     pub position: TextPosition,
@@ -190,11 +1070,97 @@ pub struct CopyConfig {
     /// Font weight for subheadline (default: regular)
     #[serde(default = "default_subheadline_weight")]
     pub subheadline_weight: FontWeight,
+    /// Font file used for every weight of this copy block (overrides the top-level `fonts`)
+    #[serde(default)]
+    pub font_path: Option<PathBuf>,
+    /// Per-weight font files for this copy block; these win over `font_path`
+    #[serde(default)]
+    pub fonts: HashMap<FontWeight, PathBuf>,
     #[serde(default = "default_line_gap")]
     pub line_gap: u32,
+    /// Line height as a multiple of the font height (default: 1.2)
+    #[serde(default = "default_line_height")]
+    pub line_height: f32,
     /// Maximum width for text wrapping (default: auto based on image width)
     #[serde(default)]
     pub max_width: Option<u32>,
+    /// Horizontal alignment of each line (default: center)
+    #[serde(default)]
+    pub align: TextAlign,
+    /// Case change for the headline and subheadline, e.g. `uppercase` (default: none)
+    #[serde(default)]
+    pub transform: TextTransform,
+    /// Distance from the canvas edge for left/right aligned text, and for `top`/`bottom`
+    /// copy or copy that doesn't fit beside the phone, in pixels (default: 60)
+    #[serde(default = "default_text_padding")]
+    pub padding: u32,
+    /// Outline color drawn behind the text; no outline when unset
+    #[serde(default)]
+    pub stroke_color: Option<String>,
+    /// Outline thickness in pixels (default: 3)
+    #[serde(default = "default_stroke_width")]
+    pub stroke_width: u32,
+    /// Drop shadow color behind the copy (alpha honored); no shadow when unset
+    #[serde(default)]
+    pub shadow_color: Option<String>,
+    /// Horizontal shadow offset in pixels (default: 0)
+    #[serde(default)]
+    pub shadow_offset_x: i32,
+    /// Vertical shadow offset in pixels (default: 6)
+    #[serde(default = "default_text_shadow_offset_y")]
+    pub shadow_offset_y: i32,
+    /// Box blur radius in pixels; 0 gives a crisp hard shadow (default: 8)
+    #[serde(default = "default_text_shadow_blur")]
+    pub shadow_blur: u32,
+    /// Rounded panel painted behind the copy block (alpha honored); no panel when unset
+    #[serde(default)]
+    pub text_background_color: Option<String>,
+    /// Space between the copy and the panel edge, also used as its corner radius (default: 24)
+    #[serde(default = "default_text_background_padding")]
+    pub text_background_padding: u32,
+    /// Optional logo image centered above the headline
+    #[serde(default)]
+    pub logo: Option<LogoConfig>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Headline {
+    Single(String),
+    Variants(Vec<String>),
+}
+
+impl Headline {
+    /// All headline texts, in config order
+    pub fn variants(&self) -> Vec<&str> {
+        match self {
+            Self::Single(text) => vec![text.as_str()],
+            Self::Variants(texts) => texts.iter().map(String::as_str).collect(),
+        }
+    }
+
+    /// Pick one headline reproducibly for the given seed
+    pub fn select(&self, seed: u64) -> &str {
+        match self {
+            Self::Single(text) => text,
+            Self::Variants(texts) if texts.is_empty() => "",
+            Self::Variants(texts) => {
+                let mut rng = ChaCha8Rng::seed_from_u64(seed);
+                &texts[rng.gen_range(0..texts.len())]
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct LogoConfig {
+    pub path: PathBuf,
+    /// Rendered logo height in pixels; width follows the image aspect ratio (default: 96)
+    #[serde(default = "default_logo_height")]
+    pub height: u32,
+    /// Gap between the logo and the first headline line in pixels (default: 32)
+    #[serde(default = "default_logo_gap")]
+    pub gap: u32,
 }
 
 fn default_output_dir() -> PathBuf {
@@ -214,12 +1180,76 @@ fn default_palette() -> Vec<String> {
     ]
 }
 
+fn default_beam_width() -> u32 {
+    260
+}
+
+fn default_beam_angle() -> f32 {
+    60.0
+}
+
+fn default_beam_position() -> f32 {
+    0.5
+}
+
+fn default_radial_center() -> f32 {
+    0.5
+}
+
+fn default_blur_sigma() -> f32 {
+    48.0
+}
+
+fn default_mesh_vignette() -> f32 {
+    1.0
+}
+
+fn default_grain() -> f32 {
+    1.0
+}
+
+fn default_gradient_angle() -> f32 {
+    90.0
+}
+
+fn default_vignette_strength() -> f32 {
+    0.45
+}
+
+fn default_vignette_radius() -> f32 {
+    0.55
+}
+
+fn default_vignette_color() -> String {
+    "#000000".to_string()
+}
+
 fn default_settle_ms() -> u64 {
     800
 }
 
-fn default_corner_radius() -> u32 {
-    88
+fn default_alpha_color() -> String {
+    "#FFFFFF".to_string()
+}
+
+fn default_corner_radius() -> CornerRadius {
+    CornerRadius::Pixels(88)
+}
+
+fn default_island_width_ratio() -> f32 {
+    0.31
+}
+
+fn default_island_height_ratio() -> f32 {
+    0.046
+}
+
+fn default_island_y_offset_ratio() -> f32 {
+    0.020
+}
+
+fn default_island_lens_size_ratio() -> f32 {
+    0.36
 }
 
 fn default_frame_color() -> String {
@@ -238,6 +1268,10 @@ fn default_shadow_alpha() -> u8 {
     74
 }
 
+fn default_min_frame_contrast() -> f32 {
+    1.6
+}
+
 fn default_copy_color() -> String {
     "#F4F8FF".to_string()
 }
@@ -257,3 +1291,351 @@ fn default_subheadline_weight() -> FontWeight {
 fn default_line_gap() -> u32 {
     24
 }
+
+fn default_glow_radius() -> u32 {
+    60
+}
+
+fn default_reflection_opacity() -> f32 {
+    0.25
+}
+
+fn default_reflection_gap() -> u32 {
+    8
+}
+
+fn default_status_bar_time() -> String {
+    "9:41".to_string()
+}
+
+fn default_status_bar_battery() -> u8 {
+    100
+}
+
+fn default_status_bar_cover() -> bool {
+    true
+}
+
+/// Copy line spacing when `line_height` is unset, as a multiple of the font height
+pub(crate) const DEFAULT_LINE_HEIGHT: f32 = 1.2;
+
+fn default_line_height() -> f32 {
+    DEFAULT_LINE_HEIGHT
+}
+
+fn default_text_padding() -> u32 {
+    60
+}
+
+fn default_stroke_width() -> u32 {
+    3
+}
+
+fn default_text_shadow_offset_y() -> i32 {
+    6
+}
+
+fn default_text_shadow_blur() -> u32 {
+    8
+}
+
+fn default_text_background_padding() -> u32 {
+    24
+}
+
+fn default_logo_height() -> u32 {
+    96
+}
+
+fn default_logo_gap() -> u32 {
+    32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::tempdir;
+
+    #[test]
+    fn from_path_reports_missing_scenes_key() {
+        let temp = tempdir().expect("tempdir");
+        let config_path = temp.path().join("screenforge.yaml");
+        fs::write(&config_path, "output_dir: ./output\n").expect("write config");
+
+        let err = Config::from_path(&config_path).expect_err("missing scenes should fail");
+        assert!(err.to_string().contains("has no `scenes` list"));
+    }
+
+    #[test]
+    fn substitutes_variables_with_escapes() {
+        let lookup = |name: &str| (name == "LOCALE").then(|| "de".to_string());
+        let out = substitute_vars("dir: out/${LOCALE}\nprice: $${LOCALE}\n", lookup).unwrap();
+        assert_eq!(out, "dir: out/de\nprice: ${LOCALE}\n");
+
+        let err = substitute_vars("b: ${MISSING}", lookup).unwrap_err();
+        assert!(err.to_string().contains("undefined variable `MISSING`"));
+        assert!(substitute_vars("a: ${OPEN", lookup).is_err());
+
+        let mut value: serde_yaml::Value =
+            serde_yaml::from_str("scenes:\n  - id: one\n    copy: { headline: \"${MISSING}\" }\n")
+                .unwrap();
+        let err = substitute_value_vars(&mut value, "", &lookup).unwrap_err();
+        assert!(format!("{:#}", err).contains("at `scenes[0].copy.headline`"));
+    }
+
+    #[test]
+    fn from_path_resolves_vars() {
+        let temp = tempdir().expect("tempdir");
+        let config_path = temp.path().join("screenforge.yaml");
+        let yaml = r#"
+vars:
+  locale: fr
+  headline: "Planifiez: votre semaine"
+output_dir: ./output/${locale}
+scenes:
+  - id: hero-${locale}
+    capture: { adapter: file, path: ./raw.png }
+    output: { filename: hero.png, width: 100, height: 200 }
+    background: {}
+    phone: { x: 0, y: 0, width: 50, height: 100 }
+    copy:
+      headline: "${headline}"
+"#;
+        fs::write(&config_path, yaml).expect("write config");
+
+        let config = Config::from_path(&config_path).expect("config loads");
+        assert_eq!(config.output_dir, PathBuf::from("./output/fr"));
+        assert_eq!(config.scenes[0].id, "hero-fr");
+        let copy = config.scenes[0].copy.as_ref().expect("copy");
+        assert_eq!(copy.headline.select(0), "Planifiez: votre semaine");
+    }
+
+    #[test]
+    fn vars_fill_parsed_values_and_skip_comments() {
+        let temp = tempdir().expect("tempdir");
+        let config_path = temp.path().join("screenforge.yaml");
+        let yaml = r#"
+vars:
+  headline: "Plan: #1 for teams"
+  width: 100
+# ${SCREENFORGE_UNSET} only appears in comments
+output_dir: ./output # ${SCREENFORGE_UNSET}
+scenes:
+  - id: hero
+    capture: { adapter: file, path: ./raw.png }
+    output:
+      filename: hero.png
+      width: ${width}
+      height: 200
+    background: {}
+    phone: { x: 0, y: 0, width: 50, height: 100 }
+    copy:
+      headline: ${headline}
+"#;
+        fs::write(&config_path, yaml).expect("write config");
+
+        // The value's `: ` and `#` stay text instead of starting a mapping or a comment
+        let config = Config::from_path(&config_path).expect("config loads");
+        let copy = config.scenes[0].copy.as_ref().expect("copy");
+        assert_eq!(copy.headline.select(0), "Plan: #1 for teams");
+        assert_eq!(config.scenes[0].output.width, 100);
+    }
+
+    #[test]
+    fn scenes_inherit_defaults() {
+        let temp = tempdir().expect("tempdir");
+        let config_path = temp.path().join("screenforge.yaml");
+        let yaml = r#"
+defaults:
+  output: { width: 100, height: 200 }
+  background: { template: stripes, seed: 9 }
+  phone: { x: 10, y: 20, width: 50, height: 100 }
+scenes:
+  - id: one
+    capture: { adapter: file, path: ./one.png }
+    output: { filename: one.png }
+    background: { seed: 3 }
+  - id: two
+    capture: { adapter: file, path: ./two.png }
+    output: { filename: two.png, size: app-store-6-7 }
+    phone: { x: 0 }
+"#;
+        fs::write(&config_path, yaml).expect("write config");
+
+        let config = Config::from_path(&config_path).expect("config loads");
+        let (one, two) = (&config.scenes[0], &config.scenes[1]);
+        assert_eq!((one.output.width, one.output.height), (100, 200));
+        assert!(matches!(
+            one.background.template,
+            BackgroundTemplate::Stripes
+        ));
+        assert_eq!(one.background.seed, 3);
+        assert_eq!((one.phone().x, one.phone().y), (10, 20));
+        assert_eq!((two.output.width, two.output.height), (1290, 2796));
+        assert_eq!((two.phone().x, two.phone().width), (0, 50));
+    }
+
+    #[test]
+    fn from_path_reports_empty_scenes_list() {
+        let temp = tempdir().expect("tempdir");
+        let config_path = temp.path().join("screenforge.yaml");
+        fs::write(&config_path, "output_dir: ./output\nscenes: []\n").expect("write config");
+
+        let err = Config::from_path(&config_path).expect_err("empty scenes should fail");
+        assert!(err.to_string().contains("has an empty `scenes` list"));
+    }
+
+    #[test]
+    fn custom_device_models_must_be_defined() {
+        let temp = tempdir().expect("tempdir");
+        let config_path = temp.path().join("screenforge.yaml");
+        let scene = r#"
+scenes:
+  - id: tab
+    capture: { adapter: file, path: ./tab.png }
+    output: { filename: tab.png, width: 100, height: 200 }
+    background: {}
+    phone: { model: pixel_tab, x: 0, y: 0, width: 50, height: 100 }
+"#;
+        fs::write(&config_path, scene).expect("write config");
+        let err = Config::from_path(&config_path).expect_err("undefined model should fail");
+        assert!(err.to_string().contains("unknown phone model 'pixel_tab'"));
+
+        let defined = format!(
+            "devices:\n  pixel_tab:\n    corner_radius: \"8%\"\n{}",
+            scene
+        );
+        fs::write(&config_path, defined).expect("write config");
+        let config = Config::from_path(&config_path).expect("config");
+        let model = config.scenes[0].phone().model.as_ref().expect("model");
+        assert_eq!(model, &ModelRef::Custom("pixel_tab".to_string()));
+        assert_eq!(
+            config.devices["pixel_tab"].corner_radius,
+            CornerRadius::Percent(8.0)
+        );
+    }
+
+    #[test]
+    fn extra_phones_follow_the_main_phone() {
+        let scene: SceneConfig = serde_yaml::from_str(
+            r#"
+id: duo
+capture:
+  adapter: file
+  path: ./before.png
+output:
+  filename: duo.png
+  width: 1284
+  height: 2778
+background: {}
+phone:
+  x: 0
+  y: 0
+  width: 100
+  height: 200
+phones:
+  - capture:
+      adapter: file
+      path: ./after.png
+    model: iphone_17_pro_max
+    x: 50
+    y: 10
+    width: 120
+    height: 240
+    corner_radius: "10%"
+"#,
+        )
+        .expect("scene");
+
+        let devices: Vec<_> = scene.devices().collect();
+        assert_eq!(devices.len(), 2);
+        let (capture, phone) = devices[1];
+        assert!(matches!(capture, CaptureConfig::File { path, .. } if path.ends_with("after.png")));
+        assert_eq!(
+            phone.model,
+            Some(ModelRef::BuiltIn(PhoneModel::Iphone17ProMax))
+        );
+        assert_eq!((phone.x, phone.width), (50, 120));
+        assert_eq!(phone.corner_radius, CornerRadius::Percent(10.0));
+    }
+
+    #[test]
+    fn scenes_may_list_only_phones() {
+        let temp = tempdir().expect("tempdir");
+        let config_path = temp.path().join("screenforge.yaml");
+        let yaml = r#"
+defaults:
+  background: {}
+  phone: { x: 0, y: 0, width: 50, height: 100 }
+scenes:
+  - id: pair
+    output: { filename: pair.png, width: 200, height: 200 }
+    phones:
+      - { capture: { adapter: file, path: ./a.png }, x: 0, y: 0, width: 80, height: 160 }
+      - { capture: { adapter: file, path: ./b.png }, x: 100, y: 0, width: 80, height: 160 }
+"#;
+        fs::write(&config_path, yaml).expect("write config");
+
+        let config = Config::from_path(&config_path).expect("config loads");
+        let scene = &config.scenes[0];
+        // The `phone` default has no capture to pair with, so it is not a third device
+        assert_eq!(scene.devices().count(), 2);
+        assert!(!scene.main_phone);
+        assert_eq!(scene.phone().width, 80);
+        assert_eq!(scene.device_label(1), "phones[1]");
+
+        let half: Result<SceneConfig, _> = serde_yaml::from_str(
+            r#"
+id: half
+capture: { adapter: file, path: ./a.png }
+output: { filename: half.png, width: 200, height: 200 }
+background: {}
+"#,
+        );
+        let err = half.expect_err("capture without phone").to_string();
+        assert!(err.contains("has `capture` but no `phone`"), "{}", err);
+    }
+
+    #[test]
+    fn corner_radius_accepts_pixels_and_percentages() {
+        let px: CornerRadius = serde_yaml::from_str("88").expect("pixels");
+        assert_eq!(px, CornerRadius::Pixels(88));
+        let suffixed: CornerRadius = serde_yaml::from_str("\"40px\"").expect("px suffix");
+        assert_eq!(suffixed, CornerRadius::Pixels(40));
+
+        let percent: CornerRadius = serde_yaml::from_str("\"22%\"").expect("percent");
+        assert_eq!(percent.to_pixels(1000, 2000), 220);
+        let pill: CornerRadius = serde_yaml::from_str("\"80%\"").expect("capped percent");
+        assert_eq!(pill.to_pixels(1000, 2000), 500);
+
+        assert!(serde_yaml::from_str::<CornerRadius>("\"round\"").is_err());
+    }
+
+    #[test]
+    fn headline_variants_select_reproducibly() {
+        let headline: Headline =
+            serde_yaml::from_str("[\"Plan faster\", \"Ship sooner\", \"Sleep better\"]")
+                .expect("parse headline list");
+        assert_eq!(headline.variants().len(), 3);
+        assert_eq!(headline.select(7), headline.select(7));
+        assert!(headline.variants().contains(&headline.select(7)));
+
+        let single: Headline = serde_yaml::from_str("\"Just one\"").expect("parse headline");
+        assert_eq!(single.select(7), "Just one");
+    }
+
+    #[test]
+    fn text_transforms_are_unicode_aware() {
+        assert_eq!(
+            TextTransform::Uppercase.apply("straße café"),
+            "STRASSE CAFÉ"
+        );
+        assert_eq!(TextTransform::Lowercase.apply("ÉTÉ Plan"), "été plan");
+        assert_eq!(
+            TextTransform::Titlecase.apply("plan your  week with ÉCOLE"),
+            "Plan Your  Week With ÉCOLE"
+        );
+        assert_eq!(TextTransform::None.apply("as Written"), "as Written");
+    }
+}
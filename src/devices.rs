@@ -1,4 +1,10 @@
-use crate::config::{Insets, PhoneConfig, PhoneModel};
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::config::{
+    CornerRadius, DeviceProfileConfig, Insets, ModelRef, Orientation, PhoneConfig, PhoneModel,
+};
 
 const DEFAULT_CORNER_RADIUS: u32 = 88;
 const DEFAULT_INSETS: Insets = Insets {
@@ -12,6 +18,7 @@ const DEFAULT_FRAME_BORDER_WIDTH: u32 = 8;
 const DEFAULT_SHADOW_OFFSET_Y: i32 = 18;
 const DEFAULT_SHADOW_ALPHA: u8 = 74;
 
+#[derive(Debug, Serialize)]
 pub struct ResolvedPhoneStyle {
     pub corner_radius: u32,
     pub screen_padding: Insets,
@@ -20,9 +27,10 @@ pub struct ResolvedPhoneStyle {
     pub shadow_offset_y: i32,
     pub shadow_alpha: u8,
     pub island: Option<DynamicIslandSpec>,
+    pub home_indicator: bool,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize)]
 pub struct DynamicIslandSpec {
     pub width_ratio: f32,
     pub height_ratio: f32,
@@ -31,53 +39,103 @@ pub struct DynamicIslandSpec {
 }
 
 struct DeviceProfile {
-    corner_radius: u32,
+    corner_radius: CornerRadius,
     screen_padding: Insets,
-    frame_color: &'static str,
+    frame_color: String,
     frame_border_width: u32,
     shadow_offset_y: i32,
     shadow_alpha: u8,
     island: Option<DynamicIslandSpec>,
+    home_indicator: bool,
+}
+
+impl From<&DeviceProfileConfig> for DeviceProfile {
+    fn from(config: &DeviceProfileConfig) -> Self {
+        let island = config.island.map(|island| DynamicIslandSpec {
+            width_ratio: island.width_ratio,
+            height_ratio: island.height_ratio,
+            y_offset_ratio: island.y_offset_ratio,
+            lens_size_ratio: island.lens_size_ratio,
+        });
+        Self {
+            corner_radius: config.corner_radius,
+            screen_padding: config.screen_padding,
+            frame_color: config.frame_color.clone(),
+            frame_border_width: config.frame_border_width,
+            shadow_offset_y: config.shadow_offset_y,
+            shadow_alpha: config.shadow_alpha,
+            island,
+            home_indicator: config.home_indicator.unwrap_or(island.is_some()),
+        }
+    }
 }
 
 pub struct DeviceListing {
+    pub model: PhoneModel,
     pub slug: &'static str,
     pub display_name: &'static str,
 }
 
-pub const DEVICE_LISTINGS: [DeviceListing; 2] = [
+pub const DEVICE_LISTINGS: [DeviceListing; 4] = [
     DeviceListing {
+        model: PhoneModel::Iphone17Pro,
         slug: "iphone_17_pro",
         display_name: "iPhone 17 Pro",
     },
     DeviceListing {
+        model: PhoneModel::Iphone17ProMax,
         slug: "iphone_17_pro_max",
         display_name: "iPhone 17 Pro Max",
     },
+    DeviceListing {
+        model: PhoneModel::IpadPro11,
+        slug: "ipad_pro_11",
+        display_name: "iPad Pro 11-inch",
+    },
+    DeviceListing {
+        model: PhoneModel::IpadPro13,
+        slug: "ipad_pro_13",
+        display_name: "iPad Pro 13-inch",
+    },
 ];
 
-pub fn resolve_phone_style(phone: &PhoneConfig) -> ResolvedPhoneStyle {
+/// Per-model look for `phone`: a profile from the config's `devices` map wins over the
+/// built-in one of the same slug
+pub fn resolve_phone_style(
+    phone: &PhoneConfig,
+    devices: &HashMap<String, DeviceProfileConfig>,
+) -> ResolvedPhoneStyle {
     let mut style = ResolvedPhoneStyle {
-        corner_radius: phone.corner_radius,
+        corner_radius: phone.corner_radius.to_pixels(phone.width, phone.height),
         screen_padding: phone.screen_padding,
         frame_color: phone.frame_color.clone(),
         frame_border_width: phone.frame_border_width,
         shadow_offset_y: phone.shadow_offset_y,
         shadow_alpha: phone.shadow_alpha,
         island: None,
+        home_indicator: phone.home_indicator.unwrap_or(false),
     };
 
-    if let Some(model) = phone.model {
-        let profile = profile_for(model);
-        style.corner_radius = choose_u32(
-            phone.corner_radius,
-            DEFAULT_CORNER_RADIUS,
-            profile.corner_radius,
+    let profile = phone.model.as_ref().and_then(|model| {
+        devices
+            .get(model.slug())
+            .map(DeviceProfile::from)
+            .or_else(|| model.built_in().map(profile_for))
+    });
+    if let Some(profile) = profile {
+        if phone.corner_radius == CornerRadius::Pixels(DEFAULT_CORNER_RADIUS) {
+            style.corner_radius = profile.corner_radius.to_pixels(phone.width, phone.height);
+        }
+        let profile_padding = match phone.orientation {
+            Orientation::Portrait => profile.screen_padding,
+            Orientation::Landscape => rotate_insets(profile.screen_padding),
+        };
+        style.screen_padding = choose_insets(phone.screen_padding, DEFAULT_INSETS, profile_padding);
+        style.frame_color = choose_color(
+            &phone.frame_color,
+            DEFAULT_FRAME_COLOR,
+            &profile.frame_color,
         );
-        style.screen_padding =
-            choose_insets(phone.screen_padding, DEFAULT_INSETS, profile.screen_padding);
-        style.frame_color =
-            choose_color(&phone.frame_color, DEFAULT_FRAME_COLOR, profile.frame_color);
         style.frame_border_width = choose_u32(
             phone.frame_border_width,
             DEFAULT_FRAME_BORDER_WIDTH,
@@ -94,22 +152,49 @@ pub fn resolve_phone_style(phone: &PhoneConfig) -> ResolvedPhoneStyle {
             profile.shadow_alpha,
         );
         style.island = profile.island;
+        style.home_indicator = phone.home_indicator.unwrap_or(profile.home_indicator);
     }
 
     style
 }
 
+/// Screen area inside the phone rect as `(x, y, width, height)` relative to the phone's
+/// top-left corner: the style's screen padding plus the frame border. `None` when the
+/// insets leave no room for a screenshot.
+pub fn inset_screen_rect(
+    phone: &PhoneConfig,
+    style: &ResolvedPhoneStyle,
+    with_overlay: bool,
+) -> Option<(u32, u32, u32, u32)> {
+    // Model overlay artwork sits slightly inside the procedural frame on Pro Max
+    let (adjust_top, adjust_side) = match phone.model.as_ref().and_then(ModelRef::built_in) {
+        Some(PhoneModel::Iphone17ProMax) if with_overlay => (10, 5),
+        _ => (0, 0),
+    };
+
+    let border = style.frame_border_width;
+    let padding = style.screen_padding;
+    let left = (padding.left + border).saturating_sub(adjust_side);
+    let right = (padding.right + border).saturating_sub(adjust_side);
+    let top = (padding.top + border).saturating_sub(adjust_top);
+    let bottom = padding.bottom + border;
+
+    let width = phone.width.saturating_sub(left + right);
+    let height = phone.height.saturating_sub(top + bottom);
+    (width > 0 && height > 0).then_some((left, top, width, height))
+}
+
 fn profile_for(model: PhoneModel) -> DeviceProfile {
     match model {
         PhoneModel::Iphone17Pro => DeviceProfile {
-            corner_radius: 122,
+            corner_radius: CornerRadius::Pixels(122),
             screen_padding: Insets {
                 top: 54,
                 right: 28,
                 bottom: 40,
                 left: 28,
             },
-            frame_color: "#686F78",
+            frame_color: "#686F78".to_string(),
             frame_border_width: 13,
             shadow_offset_y: 25,
             shadow_alpha: 84,
@@ -119,16 +204,18 @@ fn profile_for(model: PhoneModel) -> DeviceProfile {
                 y_offset_ratio: 0.020,
                 lens_size_ratio: 0.36,
             }),
+            // Island-equipped models have no home button, so they show the indicator
+            home_indicator: true,
         },
         PhoneModel::Iphone17ProMax => DeviceProfile {
-            corner_radius: 130,
+            corner_radius: CornerRadius::Pixels(130),
             screen_padding: Insets {
                 top: 54,
                 right: 28,
                 bottom: 40,
                 left: 28,
             },
-            frame_color: "#666D76",
+            frame_color: "#666D76".to_string(),
             frame_border_width: 13,
             shadow_offset_y: 26,
             shadow_alpha: 85,
@@ -138,6 +225,39 @@ fn profile_for(model: PhoneModel) -> DeviceProfile {
                 y_offset_ratio: 0.020,
                 lens_size_ratio: 0.35,
             }),
+            home_indicator: true,
+        },
+        // iPads use even bezels and Face ID in the bezel, so there is no island, but they
+        // still have no home button
+        PhoneModel::IpadPro11 => DeviceProfile {
+            corner_radius: CornerRadius::Pixels(140),
+            screen_padding: Insets {
+                top: 44,
+                right: 44,
+                bottom: 44,
+                left: 44,
+            },
+            frame_color: "#3A3D42".to_string(),
+            frame_border_width: 14,
+            shadow_offset_y: 28,
+            shadow_alpha: 86,
+            island: None,
+            home_indicator: true,
+        },
+        PhoneModel::IpadPro13 => DeviceProfile {
+            corner_radius: CornerRadius::Pixels(150),
+            screen_padding: Insets {
+                top: 46,
+                right: 46,
+                bottom: 46,
+                left: 46,
+            },
+            frame_color: "#3A3D42".to_string(),
+            frame_border_width: 14,
+            shadow_offset_y: 30,
+            shadow_alpha: 88,
+            island: None,
+            home_indicator: true,
         },
     }
 }
@@ -175,6 +295,16 @@ fn choose_insets(input: Insets, default_value: Insets, device_value: Insets) ->
     }
 }
 
+/// Portrait insets after a quarter turn counterclockwise: the top edge becomes the left
+fn rotate_insets(insets: Insets) -> Insets {
+    Insets {
+        top: insets.right,
+        right: insets.bottom,
+        bottom: insets.left,
+        left: insets.top,
+    }
+}
+
 fn choose_color(input: &str, default_value: &str, device_value: &str) -> String {
     if input.eq_ignore_ascii_case(default_value) {
         device_value.to_string()
@@ -182,3 +312,41 @@ fn choose_color(input: &str, default_value: &str, device_value: &str) -> String
         input.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sides(insets: Insets) -> [u32; 4] {
+        [insets.top, insets.right, insets.bottom, insets.left]
+    }
+
+    #[test]
+    fn rotate_insets_turns_portrait_sides_counterclockwise() {
+        let portrait = Insets {
+            top: 1,
+            right: 2,
+            bottom: 3,
+            left: 4,
+        };
+        // The portrait top (where the island sits) ends up on the left
+        assert_eq!(sides(rotate_insets(portrait)), [2, 3, 4, 1]);
+        let full_turn = (0..4).fold(portrait, |insets, _| rotate_insets(insets));
+        assert_eq!(sides(full_turn), sides(portrait));
+    }
+
+    #[test]
+    fn landscape_phones_rotate_the_profile_padding() {
+        let phone = |orientation: &str| -> PhoneConfig {
+            serde_yaml::from_str(&format!(
+                "{{ model: iphone_17_pro, x: 0, y: 0, width: 800, height: 800, orientation: {} }}",
+                orientation
+            ))
+            .expect("phone")
+        };
+        let devices = HashMap::new();
+        let portrait = resolve_phone_style(&phone("portrait"), &devices).screen_padding;
+        let landscape = resolve_phone_style(&phone("landscape"), &devices).screen_padding;
+        assert_eq!(sides(landscape), sides(rotate_insets(portrait)));
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::compose::device_screen_rect;
+use crate::config::{Config, DeviceProfileConfig, Orientation, PhoneConfig};
+use crate::devices::{ResolvedPhoneStyle, resolve_phone_style};
+use crate::frames::resolve_overlay_for_compose;
+use crate::pipeline::expand_sizes;
+
+/// What compose decides for each device of each rendered scene, for the `explain` command
+#[derive(Debug, Serialize)]
+pub struct Explanation {
+    pub scenes: Vec<SceneExplanation>,
+}
+
+/// One rendered image; scenes with `output.variants` appear once per size
+#[derive(Debug, Serialize)]
+pub struct SceneExplanation {
+    pub scene_id: String,
+    pub filename: String,
+    pub width: u32,
+    pub height: u32,
+    pub devices: Vec<DeviceExplanation>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeviceExplanation {
+    /// `phone` for the scene's own device, `phones[N]` for entries of its `phones` list
+    pub label: String,
+    pub model: Option<String>,
+    /// Where the style defaults came from: `devices` (the config's map), `built_in`, or
+    /// `None` when the phone has no known model
+    pub profile: Option<&'static str>,
+    pub orientation: Orientation,
+    pub rect: Rect,
+    pub style: ResolvedPhoneStyle,
+    pub overlay: Option<OverlayExplanation>,
+    pub screen: Option<ScreenExplanation>,
+    /// Why `screen` could not be computed; rendering this scene would fail the same way
+    pub problem: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OverlayExplanation {
+    pub path: PathBuf,
+    /// `explicit` (the phone's `overlay` key) or `model_default` (`assets/frames/<model>.png`)
+    pub source: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScreenExplanation {
+    #[serde(flatten)]
+    pub rect: Rect,
+    /// `overlay_cutout` when fitted to the overlay's transparent screen, otherwise `insets`
+    pub source: &'static str,
+}
+
+/// Load a config and resolve every device's style, overlay and screen rect the way `run`
+/// would, without capturing or rendering. Overlays are read to find their screen cutout.
+pub fn explain_config(config_path: &Path) -> Result<Explanation> {
+    let config = Config::from_path(config_path)?;
+    let config_dir = config_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let mut scenes = Vec::new();
+    for scene in &config.scenes {
+        for sized in expand_sizes(scene)? {
+            let devices = sized
+                .devices()
+                .enumerate()
+                .map(|(index, (_, phone))| {
+                    explain_device(
+                        sized.device_label(index),
+                        phone,
+                        &config_dir,
+                        &config.devices,
+                    )
+                })
+                .collect();
+            scenes.push(SceneExplanation {
+                scene_id: sized.id,
+                filename: sized.output.filename,
+                width: sized.output.width,
+                height: sized.output.height,
+                devices,
+            });
+        }
+    }
+    Ok(Explanation { scenes })
+}
+
+fn explain_device(
+    label: String,
+    phone: &PhoneConfig,
+    config_dir: &Path,
+    devices: &HashMap<String, DeviceProfileConfig>,
+) -> DeviceExplanation {
+    let style = resolve_phone_style(phone, devices);
+    let overlay = resolve_overlay_for_compose(phone, config_dir);
+    let (screen, problem) = match device_screen_rect(phone, &style, overlay.as_ref()) {
+        Ok(screen) => (
+            Some(ScreenExplanation {
+                rect: Rect {
+                    x: screen.x,
+                    y: screen.y,
+                    width: screen.width,
+                    height: screen.height,
+                },
+                source: if screen.from_cutout {
+                    "overlay_cutout"
+                } else {
+                    "insets"
+                },
+            }),
+            None,
+        ),
+        Err(err) => (None, Some(format!("{:#}", err))),
+    };
+
+    let profile = phone.model.as_ref().and_then(|model| {
+        if devices.contains_key(model.slug()) {
+            Some("devices")
+        } else {
+            model.built_in().map(|_| "built_in")
+        }
+    });
+
+    DeviceExplanation {
+        label,
+        model: phone.model.as_ref().map(|model| model.slug().to_string()),
+        profile,
+        orientation: phone.orientation,
+        rect: Rect {
+            x: phone.x,
+            y: phone.y,
+            width: phone.width,
+            height: phone.height,
+        },
+        style,
+        overlay: overlay.map(|overlay| OverlayExplanation {
+            path: overlay.path,
+            source: overlay.source.label(),
+        }),
+        screen,
+        problem,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    #[test]
+    fn explains_profile_style_and_screen_rect_per_size() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let config_path = temp.path().join("screenforge.yaml");
+        let yaml = r##"
+devices:
+  iphone_17_pro:
+    corner_radius: 100
+    screen_padding: { top: 40, right: 20, bottom: 30, left: 20 }
+    frame_color: "#222222"
+    frame_border_width: 10
+scenes:
+  - id: home
+    capture: { adapter: file, path: ./home.png }
+    output: { filename: home.png, width: 1000, height: 2000 }
+    background: {}
+    phone: { model: iphone_17_pro, x: 100, y: 200, width: 800, height: 1600 }
+  - id: list
+    capture: { adapter: file, path: ./list.png }
+    output:
+      filename: list.png
+      width: 1000
+      height: 2000
+      variants: [{ name: small, width: 500, height: 1000 }, { name: large, width: 2000, height: 4000 }]
+    background: {}
+    phone: { x: 100, y: 200, width: 800, height: 1600 }
+"##;
+        fs::write(&config_path, yaml).expect("write config");
+
+        let explanation = explain_config(&config_path).expect("explains");
+        let ids: Vec<_> = explanation
+            .scenes
+            .iter()
+            .map(|scene| scene.scene_id.as_str())
+            .collect();
+        assert_eq!(ids, ["home", "list-small", "list-large"]);
+
+        let device = &explanation.scenes[0].devices[0];
+        assert_eq!(device.label, "phone");
+        assert_eq!(device.profile, Some("devices"));
+        assert!(device.overlay.is_none());
+        assert_eq!(device.style.frame_color, "#222222");
+        let screen = device.screen.as_ref().expect("screen rect");
+        assert_eq!(screen.source, "insets");
+        // Profile padding plus its border on each side
+        assert_eq!(
+            (
+                screen.rect.x,
+                screen.rect.y,
+                screen.rect.width,
+                screen.rect.height
+            ),
+            (100 + 30, 200 + 50, 800 - 60, 1600 - 90)
+        );
+        assert_eq!(explanation.scenes[1].devices[0].profile, None);
+    }
+}
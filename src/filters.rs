@@ -0,0 +1,207 @@
+use image::{Rgba, RgbaImage};
+use rayon::prelude::*;
+
+use crate::color::lerp_color;
+
+/// Number of box passes used to approximate a gaussian; three is visually indistinguishable
+const BOX_PASSES: usize = 3;
+
+/// Blur `image` with a gaussian of standard deviation `sigma` pixels.
+///
+/// The gaussian is approximated with three box blurs, so the cost does not grow with
+/// `sigma`. Channels are blurred premultiplied so transparent pixels do not bleed their
+/// color, and edges repeat the border pixel instead of fading to black.
+pub fn gaussian_blur(image: &RgbaImage, sigma: f32) -> RgbaImage {
+    let (width, height) = image.dimensions();
+    if sigma <= 0.0 || width == 0 || height == 0 {
+        return image.clone();
+    }
+    let (width, height) = (width as usize, height as usize);
+
+    let mut planes: [Vec<f32>; 4] = std::array::from_fn(|_| vec![0.0; width * height]);
+    for (i, pixel) in image.pixels().enumerate() {
+        let alpha = pixel[3] as f32 / 255.0;
+        for channel in 0..3 {
+            planes[channel][i] = pixel[channel] as f32 * alpha;
+        }
+        planes[3][i] = pixel[3] as f32;
+    }
+
+    let radii = box_radii(sigma);
+    planes.par_iter_mut().for_each(|plane| {
+        let mut scratch = vec![0.0; width * height];
+        for &radius in &radii {
+            blur_rows(plane, &mut scratch, width, radius);
+            blur_columns(&scratch, plane, width, height, radius);
+        }
+    });
+
+    let mut out = RgbaImage::new(width as u32, height as u32);
+    for (i, pixel) in out.pixels_mut().enumerate() {
+        let alpha = planes[3][i];
+        let unpremultiply = if alpha > 0.0 { 255.0 / alpha } else { 0.0 };
+        for channel in 0..3 {
+            pixel[channel] = (planes[channel][i] * unpremultiply)
+                .round()
+                .clamp(0.0, 255.0) as u8;
+        }
+        pixel[3] = alpha.round().clamp(0.0, 255.0) as u8;
+    }
+    out
+}
+
+/// Box blur a single-channel `width`x`height` plane in place, one horizontal and one
+/// vertical pass of radius `radius`. Like [`gaussian_blur`], reads past the edges repeat
+/// the border sample, so callers that need coverage to fade out pad the plane first.
+pub fn box_blur(values: &mut [f32], width: usize, height: usize, radius: usize) {
+    if radius == 0 || width == 0 || height == 0 {
+        return;
+    }
+    let mut scratch = vec![0.0; width * height];
+    blur_rows(values, &mut scratch, width, radius);
+    blur_columns(&scratch, values, width, height, radius);
+}
+
+/// Replace each pixel's color with the point on the `shadow`→`highlight` gradient given by
+/// its Rec. 709 luma. Alpha is kept, so mapping layers before or after blending them agrees.
+pub fn duotone(image: &mut RgbaImage, shadow: Rgba<u8>, highlight: Rgba<u8>) {
+    for pixel in image.pixels_mut() {
+        let luma = (0.2126 * pixel[0] as f32 + 0.7152 * pixel[1] as f32 + 0.0722 * pixel[2] as f32)
+            / 255.0;
+        let mapped = lerp_color(shadow, highlight, luma);
+        *pixel = Rgba([mapped[0], mapped[1], mapped[2], pixel[3]]);
+    }
+}
+
+/// Box radii whose successive passes match a gaussian of `sigma` (the "ideal averaging
+/// filter width" construction: `m` passes of the smaller box, the rest one size larger)
+fn box_radii(sigma: f32) -> [usize; BOX_PASSES] {
+    let passes = BOX_PASSES as f32;
+    let ideal = (12.0 * sigma * sigma / passes + 1.0).sqrt();
+    let mut lower = ideal.floor() as i32;
+    if lower % 2 == 0 {
+        lower -= 1;
+    }
+    let lower = lower.max(1);
+    let upper = lower + 2;
+    let lower_f = lower as f32;
+    let small_passes = ((12.0 * sigma * sigma
+        - passes * lower_f * lower_f
+        - 4.0 * passes * lower_f
+        - 3.0 * passes)
+        / (-4.0 * lower_f - 4.0))
+        .round() as usize;
+    std::array::from_fn(|pass| {
+        let size = if pass < small_passes { lower } else { upper };
+        (size as usize - 1) / 2
+    })
+}
+
+fn blur_rows(src: &[f32], dst: &mut [f32], width: usize, radius: usize) {
+    for (line, out) in src.chunks_exact(width).zip(dst.chunks_exact_mut(width)) {
+        box_line(line, out, radius);
+    }
+}
+
+fn blur_columns(src: &[f32], dst: &mut [f32], width: usize, height: usize, radius: usize) {
+    let mut line = vec![0.0; height];
+    let mut out = vec![0.0; height];
+    for x in 0..width {
+        for (y, value) in line.iter_mut().enumerate() {
+            *value = src[y * width + x];
+        }
+        box_line(&line, &mut out, radius);
+        for (y, value) in out.iter().enumerate() {
+            dst[y * width + x] = *value;
+        }
+    }
+}
+
+/// Running-sum box filter over one line, clamping reads to the first and last sample
+fn box_line(line: &[f32], out: &mut [f32], radius: usize) {
+    let last = line.len() as isize - 1;
+    let at = |i: isize| line[i.clamp(0, last) as usize];
+    let window = (2 * radius + 1) as f32;
+    let radius = radius as isize;
+
+    let mut sum: f32 = (-radius..=radius).map(at).sum();
+    for (i, value) in out.iter_mut().enumerate() {
+        *value = sum / window;
+        let i = i as isize;
+        sum += at(i + radius + 1) - at(i - radius);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blur_spreads_a_point_symmetrically_and_keeps_flat_areas() {
+        let mut image = RgbaImage::from_pixel(41, 41, Rgba([0, 0, 0, 255]));
+        image.put_pixel(20, 20, Rgba([255, 255, 255, 255]));
+        let blurred = gaussian_blur(&image, 3.0);
+
+        let center = blurred.get_pixel(20, 20)[0];
+        assert!(center > 0 && center < 255);
+        assert_eq!(blurred.get_pixel(17, 20), blurred.get_pixel(23, 20));
+        assert_eq!(blurred.get_pixel(20, 17), blurred.get_pixel(20, 23));
+        assert!(blurred.get_pixel(17, 20)[0] < center);
+        assert_eq!(*blurred.get_pixel(0, 0), Rgba([0, 0, 0, 255]));
+
+        let flat = RgbaImage::from_pixel(9, 5, Rgba([40, 90, 200, 255]));
+        assert_eq!(gaussian_blur(&flat, 12.0), flat);
+    }
+
+    #[test]
+    fn transparent_pixels_do_not_tint_their_neighbours() {
+        let mut image = RgbaImage::from_pixel(16, 1, Rgba([255, 0, 0, 0]));
+        for x in 0..8 {
+            image.put_pixel(x, 0, Rgba([0, 0, 255, 255]));
+        }
+        let blurred = gaussian_blur(&image, 2.0);
+        let edge = blurred.get_pixel(8, 0);
+        assert!(edge[3] > 0 && edge[3] < 255);
+        assert_eq!(edge[0], 0);
+    }
+
+    #[test]
+    fn box_blur_averages_both_axes_and_repeats_edges() {
+        let mut plane = vec![0.0; 5 * 5];
+        plane[2 * 5 + 2] = 9.0;
+        box_blur(&mut plane, 5, 5, 1);
+        for y in 0..5 {
+            for x in 0..5 {
+                let expected = if (1..=3).contains(&x) && (1..=3).contains(&y) {
+                    1.0
+                } else {
+                    0.0
+                };
+                assert!((plane[y * 5 + x] - expected).abs() < 1e-6, "({x}, {y})");
+            }
+        }
+
+        let mut edge = vec![1.0, 0.0, 0.0];
+        box_blur(&mut edge, 3, 1, 1);
+        assert!((edge[0] - 2.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn duotone_maps_luma_onto_the_two_colors() {
+        let shadow = Rgba([20, 0, 60, 255]);
+        let highlight = Rgba([255, 200, 100, 255]);
+        let mut image = RgbaImage::from_vec(
+            3,
+            1,
+            vec![0, 0, 0, 255, 255, 255, 255, 128, 128, 128, 128, 40],
+        )
+        .expect("pixels");
+        duotone(&mut image, shadow, highlight);
+
+        assert_eq!(*image.get_pixel(0, 0), shadow);
+        assert_eq!(*image.get_pixel(1, 0), Rgba([255, 200, 100, 128]));
+        let mid = image.get_pixel(2, 0);
+        assert_eq!(mid[3], 40);
+        assert!(mid[0] > shadow[0] && mid[0] < highlight[0]);
+    }
+}
@@ -1,11 +1,22 @@
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use anyhow::{Context, Result};
+use image::{DynamicImage, ImageFormat};
+use serde::Serialize;
 
-use crate::config::{Config, PhoneModel, SceneConfig};
+use crate::compose::detect_overlay_screen_region;
+use crate::config::{
+    Config, DeviceProfileConfig, Orientation, PhoneConfig, PhoneModel, SceneConfig,
+};
+use crate::devices::{DEVICE_LISTINGS, inset_screen_rect, resolve_phone_style};
 
 const DEFAULT_FRAMES_DIR: &str = "assets/frames";
+// Cutout edges may sit this far (fraction of the phone side) from the profile's screen
+// edges; bundled overlays have slightly thicker bezels than the procedural frame
+const CUTOUT_ALIGN_TOLERANCE: f32 = 0.04;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OverlaySource {
@@ -28,16 +39,19 @@ pub struct ResolvedOverlay {
     pub source: OverlaySource,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct VerifyIssue {
     pub scene_id: String,
     pub level: VerifyLevel,
     pub message: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Serialized as its `label()`, matching the text output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum VerifyLevel {
+    #[serde(rename = "WARN")]
     Warning,
+    #[serde(rename = "ERROR")]
     Error,
 }
 
@@ -50,7 +64,7 @@ impl VerifyLevel {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct VerifySummary {
     pub scene_count: usize,
     pub checked_overlays: usize,
@@ -65,13 +79,15 @@ impl VerifySummary {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct ImportSummary {
     pub source: PathBuf,
     pub destination: PathBuf,
     pub imported: usize,
     pub skipped: usize,
     pub notes: Vec<String>,
+    /// Nothing was written; `imported` counts the files that would have been
+    pub dry_run: bool,
 }
 
 #[derive(Debug)]
@@ -83,15 +99,24 @@ pub struct ConvertSummary {
     pub notes: Vec<String>,
 }
 
-pub fn import_frames(source: &Path, destination: &Path, overwrite: bool) -> Result<ImportSummary> {
+/// Validate and copy overlays into `destination`. With `dry_run` every check still runs and
+/// the summary describes what would happen, but nothing is written.
+pub fn import_frames(
+    source: &Path,
+    destination: &Path,
+    overwrite: bool,
+    dry_run: bool,
+) -> Result<ImportSummary> {
     let source_metadata = fs::metadata(source)
         .with_context(|| format!("failed reading source {}", source.display()))?;
     if !source_metadata.is_dir() {
         anyhow::bail!("source is not a directory: {}", source.display());
     }
 
-    fs::create_dir_all(destination)
-        .with_context(|| format!("failed creating {}", destination.display()))?;
+    if !dry_run {
+        fs::create_dir_all(destination)
+            .with_context(|| format!("failed creating {}", destination.display()))?;
+    }
 
     let mut entries = fs::read_dir(source)
         .with_context(|| format!("failed reading source {}", source.display()))?
@@ -102,6 +127,9 @@ pub fn import_frames(source: &Path, destination: &Path, overwrite: bool) -> Resu
     let mut imported = 0usize;
     let mut skipped = 0usize;
     let mut notes = Vec::new();
+    // Destinations claimed earlier in this import, so a dry run sees the same collisions
+    // between sources sharing a slug as a real one
+    let mut claimed = HashSet::new();
 
     for entry in entries {
         let file_type = entry
@@ -112,10 +140,10 @@ pub fn import_frames(source: &Path, destination: &Path, overwrite: bool) -> Resu
         }
 
         let src_path = entry.path();
-        if !is_png_file(&src_path) {
+        if !is_image_file(&src_path) {
             skipped += 1;
             notes.push(format!(
-                "skip {}: only .png files are supported",
+                "skip {}: not a supported image format",
                 src_path.display()
             ));
             continue;
@@ -135,26 +163,25 @@ pub fn import_frames(source: &Path, destination: &Path, overwrite: bool) -> Resu
             continue;
         }
 
-        match read_overlay_meta(&src_path) {
-            Ok(meta) => {
-                if !meta.has_transparency {
-                    skipped += 1;
-                    notes.push(format!(
-                        "skip {}: overlay has no transparency (frame must include transparent cutout)",
-                        src_path.display()
-                    ));
-                    continue;
-                }
-            }
+        let image = match decode_overlay(&src_path) {
+            Ok(image) => image,
             Err(err) => {
                 skipped += 1;
                 notes.push(format!("skip {}: {}", src_path.display(), err));
                 continue;
             }
+        };
+        if !overlay_meta(&image).has_transparency {
+            skipped += 1;
+            notes.push(format!(
+                "skip {}: overlay has no transparency (frame must include transparent cutout)",
+                src_path.display()
+            ));
+            continue;
         }
 
         let dest_path = destination.join(format!("{}.png", slug));
-        if dest_path.exists() && !overwrite {
+        if (dest_path.exists() || claimed.contains(&dest_path)) && !overwrite {
             skipped += 1;
             notes.push(format!(
                 "skip {}: destination exists (use --overwrite to replace)",
@@ -162,14 +189,37 @@ pub fn import_frames(source: &Path, destination: &Path, overwrite: bool) -> Resu
             ));
             continue;
         }
+        claimed.insert(dest_path.clone());
 
-        fs::copy(&src_path, &dest_path).with_context(|| {
-            format!(
-                "failed copying {} -> {}",
+        if dry_run {
+            imported += 1;
+            notes.push(format!(
+                "would import {} -> {}",
                 src_path.display(),
                 dest_path.display()
-            )
-        })?;
+            ));
+            continue;
+        }
+
+        if is_png_file(&src_path) {
+            fs::copy(&src_path, &dest_path).with_context(|| {
+                format!(
+                    "failed copying {} -> {}",
+                    src_path.display(),
+                    dest_path.display()
+                )
+            })?;
+        } else {
+            normalize_to_png(image)
+                .save_with_format(&dest_path, ImageFormat::Png)
+                .with_context(|| {
+                    format!(
+                        "failed converting {} -> {}",
+                        src_path.display(),
+                        dest_path.display()
+                    )
+                })?;
+        }
         imported += 1;
         notes.push(format!(
             "import {} -> {}",
@@ -184,9 +234,96 @@ pub fn import_frames(source: &Path, destination: &Path, overwrite: bool) -> Resu
         imported,
         skipped,
         notes,
+        dry_run,
     })
 }
 
+/// A named set of transparent overlay PNGs hosted under one base URL
+pub struct FramePack {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub base_url: &'static str,
+    pub files: &'static [&'static str],
+}
+
+pub const FRAME_PACKS: [FramePack; 1] = [FramePack {
+    name: "iphone-17",
+    description: "iPhone 17 Pro and Pro Max overlays",
+    base_url: "https://raw.githubusercontent.com/nclandrei/screenforge/main/assets/frames",
+    files: &["iphone_17_pro.png", "iphone_17_pro_max.png"],
+}];
+
+/// Download a frame pack (registry name or base URL) and import it like `import_frames`.
+///
+/// A URL pack is expected to serve `<url>/<model_slug>.png` for every built-in model.
+/// Files that fail to download are reported as skipped rather than aborting the fetch.
+pub fn fetch_frames(pack: &str, destination: &Path, overwrite: bool) -> Result<ImportSummary> {
+    let (base_url, files) = resolve_frame_pack(pack)?;
+    let staging = tempfile::tempdir().context("failed creating download directory")?;
+
+    let mut skipped = 0usize;
+    let mut notes = Vec::new();
+    for file in &files {
+        let url = format!("{}/{}", base_url.trim_end_matches('/'), file);
+        let output = Command::new("curl")
+            .args([
+                "--fail",
+                "--silent",
+                "--show-error",
+                "--location",
+                "--output",
+            ])
+            .arg(staging.path().join(file))
+            .arg(&url)
+            .output()
+            .context("failed to execute curl (is it installed?)")?;
+        if !output.status.success() {
+            skipped += 1;
+            notes.push(format!(
+                "skip {}: download failed: {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+    }
+
+    let mut summary = import_frames(staging.path(), destination, overwrite, false)?;
+    // Report downloaded files by URL rather than by their temporary staging path
+    let staging_prefix = staging.path().display().to_string();
+    let base = base_url.trim_end_matches('/');
+    notes.extend(
+        summary
+            .notes
+            .iter()
+            .map(|note| note.replace(&staging_prefix, base)),
+    );
+    summary.source = PathBuf::from(&base_url);
+    summary.skipped += skipped;
+    summary.notes = notes;
+    Ok(summary)
+}
+
+fn resolve_frame_pack(pack: &str) -> Result<(String, Vec<String>)> {
+    if pack.starts_with("https://") || pack.starts_with("http://") {
+        let files = DEVICE_LISTINGS
+            .iter()
+            .map(|device| format!("{}.png", device.slug))
+            .collect();
+        return Ok((pack.to_string(), files));
+    }
+
+    let Some(known) = FRAME_PACKS.iter().find(|known| known.name == pack) else {
+        let names: Vec<_> = FRAME_PACKS.iter().map(|known| known.name).collect();
+        anyhow::bail!(
+            "unknown frame pack '{}' (known packs: {}; or pass a base URL)",
+            pack,
+            names.join(", ")
+        );
+    };
+    let files = known.files.iter().map(|file| file.to_string()).collect();
+    Ok((known.base_url.to_string(), files))
+}
+
 /// Convert mockup frames (white screen area) to overlay frames (transparent screen area).
 ///
 /// This function takes PNG images where the phone screen is filled with white/near-white
@@ -339,96 +476,181 @@ pub fn verify_overlays(config_path: &Path) -> Result<VerifySummary> {
     };
 
     for scene in &config.scenes {
-        let Some(overlay) = resolve_overlay_for_verify(scene, &config_dir) else {
+        let Some(issues) = scene_overlay_issues(scene, &config_dir, &config.devices) else {
             continue;
         };
         summary.checked_overlays += 1;
+        for issue in issues {
+            push_issue(&mut summary, issue.scene_id, issue.level, issue.message);
+        }
+    }
 
-        if !overlay.path.exists() {
-            match overlay.source {
-                OverlaySource::Explicit => {
-                    push_issue(
-                        &mut summary,
-                        scene.id.clone(),
-                        VerifyLevel::Error,
-                        format!("overlay not found: {}", overlay.path.display()),
-                    );
-                }
-                OverlaySource::ModelDefault => {
-                    push_issue(
-                        &mut summary,
-                        scene.id.clone(),
-                        VerifyLevel::Warning,
-                        format!("no auto overlay for model at {}", overlay.path.display()),
-                    );
-                }
-            }
-            continue;
+    Ok(summary)
+}
+
+/// Check the overlays a scene would use, one per device. Returns `None` when no
+/// device has an overlay candidate (no explicit path and no model).
+pub fn scene_overlay_issues(
+    scene: &SceneConfig,
+    config_dir: &Path,
+    devices: &HashMap<String, DeviceProfileConfig>,
+) -> Option<Vec<VerifyIssue>> {
+    let mut checked = false;
+    let mut issues = Vec::new();
+    for (_, phone) in scene.devices() {
+        if let Some(device_issues) = phone_overlay_issues(&scene.id, phone, config_dir, devices) {
+            checked = true;
+            issues.extend(device_issues);
         }
+    }
+    checked.then_some(issues)
+}
 
-        if !is_png_file(&overlay.path) {
-            push_issue(
-                &mut summary,
-                scene.id.clone(),
+fn phone_overlay_issues(
+    scene_id: &str,
+    phone: &PhoneConfig,
+    config_dir: &Path,
+    devices: &HashMap<String, DeviceProfileConfig>,
+) -> Option<Vec<VerifyIssue>> {
+    let overlay = resolve_overlay_for_verify(phone, config_dir)?;
+    let mut issues = Vec::new();
+    let mut report = |level: VerifyLevel, message: String| {
+        issues.push(VerifyIssue {
+            scene_id: scene_id.to_string(),
+            level,
+            message,
+        });
+    };
+
+    if !overlay.path.exists() {
+        match overlay.source {
+            OverlaySource::Explicit => report(
+                VerifyLevel::Error,
+                format!("overlay not found: {}", overlay.path.display()),
+            ),
+            OverlaySource::ModelDefault => report(
                 VerifyLevel::Warning,
-                format!("overlay should be a PNG: {}", overlay.path.display()),
-            );
+                format!("no auto overlay for model at {}", overlay.path.display()),
+            ),
         }
+        return Some(issues);
+    }
 
-        match read_overlay_meta(&overlay.path) {
-            Ok(meta) => {
-                if !meta.has_transparency {
-                    push_issue(
-                        &mut summary,
-                        scene.id.clone(),
-                        VerifyLevel::Error,
-                        format!("overlay has no transparency: {}", overlay.path.display()),
-                    );
-                }
+    if !is_png_file(&overlay.path) {
+        report(
+            VerifyLevel::Warning,
+            format!("overlay should be a PNG: {}", overlay.path.display()),
+        );
+    }
 
-                if meta.width != scene.phone.width || meta.height != scene.phone.height {
-                    push_issue(
-                        &mut summary,
-                        scene.id.clone(),
-                        VerifyLevel::Warning,
-                        format!(
-                            "overlay size {}x{} does not match phone rect {}x{} ({}).",
-                            meta.width,
-                            meta.height,
-                            scene.phone.width,
-                            scene.phone.height,
-                            overlay.path.display()
-                        ),
-                    );
-                }
-            }
-            Err(err) => {
-                push_issue(
-                    &mut summary,
-                    scene.id.clone(),
+    match read_overlay_meta(&overlay.path) {
+        Ok(meta) => {
+            if !meta.has_transparency {
+                report(
                     VerifyLevel::Error,
-                    format!("failed reading overlay {}: {}", overlay.path.display(), err),
+                    format!("overlay has no transparency: {}", overlay.path.display()),
                 );
             }
+
+            if meta.width != phone.width || meta.height != phone.height {
+                report(
+                    VerifyLevel::Warning,
+                    format!(
+                        "overlay size {}x{} does not match phone rect {}x{} ({}).",
+                        meta.width,
+                        meta.height,
+                        phone.width,
+                        phone.height,
+                        overlay.path.display()
+                    ),
+                );
+            }
+
+            if meta.has_transparency
+                && let Some(message) = cutout_misalignment(phone, &overlay.path, devices)
+            {
+                report(VerifyLevel::Warning, message);
+            }
         }
+        Err(err) => report(
+            VerifyLevel::Error,
+            format!("failed reading overlay {}: {}", overlay.path.display(), err),
+        ),
     }
 
-    Ok(summary)
+    Some(issues)
+}
+
+/// Compare the overlay's transparent screen cutout, scaled to the phone rect, with the
+/// screen area compose would use from the device profile insets
+fn cutout_misalignment(
+    phone: &PhoneConfig,
+    path: &Path,
+    devices: &HashMap<String, DeviceProfileConfig>,
+) -> Option<String> {
+    let region = match detect_overlay_screen_region(path) {
+        Ok(Some(region)) => region,
+        Ok(None) => {
+            return Some(format!(
+                "overlay has no enclosed transparent screen cutout; the screenshot falls back to profile insets ({})",
+                path.display()
+            ));
+        }
+        Err(err) => return Some(format!("{:#}", err)),
+    };
+    let style = resolve_phone_style(phone, devices);
+    let (x, y, width, height) = inset_screen_rect(phone, &style, true)?;
+
+    let sx = phone.width as f32 / region.overlay_width as f32;
+    let sy = phone.height as f32 / region.overlay_height as f32;
+    let cutout = [
+        region.x as f32 * sx,
+        region.y as f32 * sy,
+        (region.x + region.width) as f32 * sx,
+        (region.y + region.height) as f32 * sy,
+    ];
+    let expected = [x, y, x + width, y + height].map(|edge| edge as f32);
+    let limits = [phone.width, phone.height, phone.width, phone.height]
+        .map(|side| side as f32 * CUTOUT_ALIGN_TOLERANCE);
+
+    let misaligned = cutout
+        .iter()
+        .zip(expected)
+        .zip(limits)
+        .any(|((actual, expected), limit)| (actual - expected).abs() > limit);
+    misaligned.then(|| {
+        format!(
+            "overlay screen cutout {}x{} at ({}, {}) does not line up with the expected screen {}x{} at ({}, {}) ({})",
+            (cutout[2] - cutout[0]).round(),
+            (cutout[3] - cutout[1]).round(),
+            cutout[0].round(),
+            cutout[1].round(),
+            width,
+            height,
+            x,
+            y,
+            path.display()
+        )
+    })
 }
 
 pub fn resolve_overlay_for_compose(
-    scene: &SceneConfig,
+    phone: &PhoneConfig,
     config_dir: &Path,
 ) -> Option<ResolvedOverlay> {
-    if let Some(overlay) = scene.phone.overlay.as_ref().map(|path| ResolvedOverlay {
+    if let Some(overlay) = phone.overlay.as_ref().map(|path| ResolvedOverlay {
         path: resolve_path(config_dir, path),
         source: OverlaySource::Explicit,
     }) {
         return Some(overlay);
     }
 
-    let model = scene.phone.model?;
-    let path = default_model_overlay_path(config_dir, model);
+    // Model overlays are portrait artwork; landscape devices are drawn procedurally
+    if phone.orientation == Orientation::Landscape {
+        return None;
+    }
+    let model = phone.model.as_ref()?;
+    let path = default_model_overlay_path(config_dir, model.slug());
     if path.exists() {
         Some(ResolvedOverlay {
             path,
@@ -440,19 +662,24 @@ pub fn resolve_overlay_for_compose(
 }
 
 pub fn resolve_overlay_for_verify(
-    scene: &SceneConfig,
+    phone: &PhoneConfig,
     config_dir: &Path,
 ) -> Option<ResolvedOverlay> {
-    if let Some(overlay) = scene.phone.overlay.as_ref().map(|path| ResolvedOverlay {
+    if let Some(overlay) = phone.overlay.as_ref().map(|path| ResolvedOverlay {
         path: resolve_path(config_dir, path),
         source: OverlaySource::Explicit,
     }) {
         return Some(overlay);
     }
 
-    let model = scene.phone.model?;
+    // Custom and landscape devices are drawn procedurally, so only built-in portrait models
+    // expect an overlay
+    if phone.orientation == Orientation::Landscape {
+        return None;
+    }
+    let model = phone.model.as_ref()?.built_in()?;
     Some(ResolvedOverlay {
-        path: default_model_overlay_path(config_dir, model),
+        path: default_model_overlay_path(config_dir, model_slug(model)),
         source: OverlaySource::ModelDefault,
     })
 }
@@ -461,13 +688,15 @@ pub fn model_slug(model: PhoneModel) -> &'static str {
     match model {
         PhoneModel::Iphone17Pro => "iphone_17_pro",
         PhoneModel::Iphone17ProMax => "iphone_17_pro_max",
+        PhoneModel::IpadPro11 => "ipad_pro_11",
+        PhoneModel::IpadPro13 => "ipad_pro_13",
     }
 }
 
-fn default_model_overlay_path(config_dir: &Path, model: PhoneModel) -> PathBuf {
+fn default_model_overlay_path(config_dir: &Path, slug: &str) -> PathBuf {
     config_dir
         .join(DEFAULT_FRAMES_DIR)
-        .join(format!("{}.png", model_slug(model)))
+        .join(format!("{}.png", slug))
 }
 
 fn resolve_path(config_dir: &Path, path: &Path) -> PathBuf {
@@ -498,15 +727,36 @@ struct OverlayMeta {
 }
 
 fn read_overlay_meta(path: &Path) -> Result<OverlayMeta> {
-    let image =
-        image::open(path).with_context(|| format!("failed to decode {}", path.display()))?;
+    decode_overlay(path).map(|image| overlay_meta(&image))
+}
+
+fn decode_overlay(path: &Path) -> Result<DynamicImage> {
+    image::open(path).with_context(|| format!("failed to decode {}", path.display()))
+}
+
+fn overlay_meta(image: &DynamicImage) -> OverlayMeta {
     let rgba = image.to_rgba8();
     let has_transparency = rgba.pixels().any(|pixel| pixel[3] < 255);
-    Ok(OverlayMeta {
+    OverlayMeta {
         width: rgba.width(),
         height: rgba.height(),
         has_transparency,
-    })
+    }
+}
+
+/// PNG holds 8- or 16-bit RGBA; deeper sources (e.g. float TIFF) are narrowed to 16-bit
+fn normalize_to_png(image: DynamicImage) -> DynamicImage {
+    let color = image.color();
+    if color.bits_per_pixel() / color.channel_count() as u16 > 8 {
+        DynamicImage::ImageRgba16(image.to_rgba16())
+    } else {
+        DynamicImage::ImageRgba8(image.to_rgba8())
+    }
+}
+
+/// Any format the `image` crate can decode, judged by extension
+fn is_image_file(path: &Path) -> bool {
+    ImageFormat::from_path(path).is_ok_and(|format| format.reading_enabled())
 }
 
 fn is_png_file(path: &Path) -> bool {
@@ -541,6 +791,20 @@ mod tests {
     use image::{Rgba, RgbaImage};
     use tempfile::tempdir;
 
+    #[test]
+    fn resolve_frame_pack_accepts_registry_names_and_urls() {
+        let (base_url, files) = resolve_frame_pack("iphone-17").expect("registry pack");
+        assert_eq!(base_url, FRAME_PACKS[0].base_url);
+        assert_eq!(files, vec!["iphone_17_pro.png", "iphone_17_pro_max.png"]);
+
+        let (base_url, files) = resolve_frame_pack("https://example.com/frames").expect("url pack");
+        assert_eq!(base_url, "https://example.com/frames");
+        assert_eq!(files.len(), DEVICE_LISTINGS.len());
+
+        let err = resolve_frame_pack("pixel-9").expect_err("unknown pack");
+        assert!(err.to_string().contains("known packs: iphone-17"));
+    }
+
     #[test]
     fn import_frames_only_accepts_transparent_pngs() {
         let temp = tempdir().expect("tempdir");
@@ -552,13 +816,58 @@ mod tests {
         write_png(&source.join("Opaque.png"), 20, 30, false);
         fs::write(source.join("readme.txt"), "x").expect("write txt");
 
-        let summary = import_frames(&source, &destination, false).expect("import frames");
+        let summary = import_frames(&source, &destination, false, false).expect("import frames");
         assert_eq!(summary.imported, 1);
         assert_eq!(summary.skipped, 2);
         assert!(destination.join("iphone_17_pro.png").exists());
         assert!(!destination.join("opaque.png").exists());
     }
 
+    #[test]
+    fn import_frames_dry_run_plans_without_writing() {
+        let temp = tempdir().expect("tempdir");
+        let source = temp.path().join("source");
+        let destination = temp.path().join("destination");
+        fs::create_dir_all(&source).expect("create source");
+
+        // Both normalize to iphone_17_pro, so the second one collides with the first
+        write_png(&source.join("iPhone 17 Pro.png"), 20, 30, true);
+        write_png(&source.join("iphone-17-pro.webp"), 20, 30, true);
+
+        let summary = import_frames(&source, &destination, false, true).expect("dry run");
+        assert!(summary.dry_run);
+        assert_eq!(summary.imported, 1);
+        assert_eq!(summary.skipped, 1);
+        assert!(summary.notes[0].starts_with("would import"));
+        assert!(summary.notes[1].contains("destination exists"));
+        assert!(!destination.exists());
+    }
+
+    #[test]
+    fn import_frames_converts_other_formats_to_png() {
+        let temp = tempdir().expect("tempdir");
+        let source = temp.path().join("source");
+        let destination = temp.path().join("destination");
+        fs::create_dir_all(&source).expect("create source");
+
+        write_png(&source.join("iPad Pro 13.webp"), 20, 30, true);
+        // JPEG has no alpha channel, so it fails the transparency check
+        DynamicImage::ImageRgba8(RgbaImage::new(20, 30))
+            .to_rgb8()
+            .save(source.join("flat.jpg"))
+            .expect("save jpeg");
+
+        let summary = import_frames(&source, &destination, false, false).expect("import frames");
+        assert_eq!(summary.imported, 1);
+        assert_eq!(summary.skipped, 1);
+        let imported = destination.join("ipad_pro_13.png");
+        let bytes = fs::read(&imported).expect("read imported");
+        assert_eq!(image::guess_format(&bytes).ok(), Some(ImageFormat::Png));
+        let meta = read_overlay_meta(&imported).expect("decode imported png");
+        assert_eq!((meta.width, meta.height), (20, 30));
+        assert!(meta.has_transparency);
+    }
+
     #[test]
     fn verify_overlays_reports_missing_explicit_overlay() {
         let temp = tempdir().expect("tempdir");
@@ -633,12 +942,55 @@ scenes:
         assert!(summary.failed(true));
     }
 
+    #[test]
+    fn verify_overlays_resolves_pro_max_model_overlay() {
+        let temp = tempdir().expect("tempdir");
+        let config_path = temp.path().join("screenforge.yaml");
+        fs::write(
+            &config_path,
+            r#"
+output_dir: ./output
+scenes:
+  - id: pro_max
+    capture:
+      adapter: file
+      path: ./raw.png
+    output:
+      filename: out.png
+      width: 1284
+      height: 2778
+    background: {}
+    phone:
+      model: iphone_17_pro_max
+      x: 10
+      y: 10
+      width: 100
+      height: 200
+"#,
+        )
+        .expect("write config");
+
+        let summary = verify_overlays(&config_path).expect("verify");
+        assert_eq!(summary.warnings, 1);
+        assert!(
+            summary.issues[0]
+                .message
+                .contains("assets/frames/iphone_17_pro_max.png")
+        );
+    }
+
     #[test]
     fn verify_overlays_warns_on_dimension_mismatch() {
         let temp = tempdir().expect("tempdir");
         let frames_dir = temp.path().join("assets/frames");
         fs::create_dir_all(&frames_dir).expect("frames dir");
-        write_png(&frames_dir.join("iphone_17_pro.png"), 300, 600, true);
+        // The iPhone 17 Pro profile screen for a 100x200 phone is 18x80 at (41, 67); at 3x
+        write_overlay(
+            &frames_dir.join("iphone_17_pro.png"),
+            300,
+            600,
+            (123, 201, 54, 240),
+        );
 
         let config_path = temp.path().join("screenforge.yaml");
         fs::write(
@@ -677,6 +1029,66 @@ scenes:
         );
     }
 
+    #[test]
+    fn verify_overlays_warns_on_offset_cutout() {
+        let temp = tempdir().expect("tempdir");
+        let overlay = temp.path().join("offset.png");
+        write_overlay(&overlay, 100, 200, (4, 4, 18, 80));
+
+        let config_path = temp.path().join("screenforge.yaml");
+        fs::write(
+            &config_path,
+            r#"
+output_dir: ./output
+scenes:
+  - id: offset
+    capture:
+      adapter: file
+      path: ./raw.png
+    output:
+      filename: out.png
+      width: 1284
+      height: 2778
+    background: {}
+    phone:
+      model: iphone_17_pro
+      overlay: ./offset.png
+      x: 10
+      y: 10
+      width: 100
+      height: 200
+"#,
+        )
+        .expect("write config");
+
+        let summary = verify_overlays(&config_path).expect("verify");
+        assert_eq!(summary.errors, 0);
+        assert_eq!(summary.warnings, 1);
+        assert!(summary.issues[0].message.contains("does not line up"));
+    }
+
+    #[test]
+    fn verify_levels_serialize_as_labels() {
+        for level in [VerifyLevel::Warning, VerifyLevel::Error] {
+            let json = serde_json::to_value(level).expect("serialize level");
+            assert_eq!(json, level.label());
+        }
+    }
+
+    fn write_overlay(path: &Path, width: u32, height: u32, cutout: (u32, u32, u32, u32)) {
+        let (cutout_x, cutout_y, cutout_w, cutout_h) = cutout;
+        let image = RgbaImage::from_fn(width, height, |x, y| {
+            let inside = (cutout_x..cutout_x + cutout_w).contains(&x)
+                && (cutout_y..cutout_y + cutout_h).contains(&y);
+            if inside {
+                Rgba([0, 0, 0, 0])
+            } else {
+                Rgba([60, 80, 120, 255])
+            }
+        });
+        image.save(path).expect("save png");
+    }
+
     fn write_png(path: &Path, width: u32, height: u32, transparent: bool) {
         let mut image = RgbaImage::new(width, height);
         for y in 0..height {
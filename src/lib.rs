@@ -0,0 +1,65 @@
+//! Generate App Store-style marketing screenshots: a rendered background, a framed device
+//! showing your screenshot, and headline copy.
+//!
+//! The `screenforge` binary is a thin CLI over this crate. To frame images from your own
+//! program, deserialize a [`SceneConfig`] (the same YAML shape as a scene in
+//! `screenforge.yaml`) and hand it one screenshot per device:
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! let config = screenforge::Config::from_path(Path::new("screenforge.yaml"))?;
+//! let screenshot = image::open("upload.png")?;
+//! let scene = &config.scenes[0];
+//! let framed = screenforge::render_scene(&config, scene, &[screenshot], Path::new("."))?;
+//! framed.save("framed.png")?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! [`run`] drives a whole config (capture, render, preview) like `screenforge run`
+//! ([`run_with_progress`] also reports each finished scene), and
+//! [`frame_screenshot`] frames a single image with the quick `snap` layout
+//! ([`snap_framed`] captures it from a booted simulator first).
+//! [`render_background`] renders just a scene's background.
+//!
+//! These re-exports, plus the [`config`], [`pipeline`], [`presets`] and [`snap`] modules they
+//! come from, are the supported API. Other modules exist for the binary and may change.
+
+pub mod config;
+pub mod pipeline;
+pub mod presets;
+pub mod snap;
+
+// Used by the `screenforge` binary; not part of the supported API
+#[doc(hidden)]
+pub mod compare;
+#[doc(hidden)]
+pub mod devices;
+#[doc(hidden)]
+pub mod explain;
+#[doc(hidden)]
+pub mod frames;
+#[doc(hidden)]
+pub mod simulator;
+#[doc(hidden)]
+pub mod validate;
+#[doc(hidden)]
+pub mod watch;
+
+mod background;
+mod capture;
+mod color;
+mod compose;
+mod filters;
+mod palette;
+mod preview;
+mod warp;
+
+pub use background::render_background;
+pub use config::{
+    BackgroundConfig, BackgroundTemplate, CaptureConfig, Config, CopyConfig, DeviceProfileConfig,
+    OutputConfig, PhoneConfig, PhoneModel, SceneConfig,
+};
+pub use palette::PaletteStrategy;
+pub use pipeline::{Progress, RunOptions, RunSummary, render_scene, run, run_with_progress};
+pub use snap::{SnapConfig, SnapResult, frame_screenshot, snap_framed, snap_raw};
@@ -1,68 +1,158 @@
-mod background;
-mod capture;
 mod cli;
-mod color;
-mod compose;
-mod config;
-mod devices;
-mod frames;
-mod palette;
-mod pipeline;
-mod preview;
-mod simulator;
-mod snap;
-
-use anyhow::{Result, bail};
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
 use clap::Parser;
+use log::{LevelFilter, info, warn};
+
+use screenforge::config::Orientation;
+use screenforge::presets::SizePreset;
+use screenforge::snap::{SnapConfig, SnapResult};
+use screenforge::{
+    Progress, RunOptions, compare, devices, explain, frames, pipeline, simulator, snap, validate,
+    watch,
+};
 
 use crate::cli::{Cli, Commands, OutputFormat};
-use crate::snap::SnapConfig;
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    init_logging(cli.verbose);
     match cli.command {
-        Commands::Run { config } => {
-            let summary = pipeline::run(&config)?;
+        Commands::Run {
+            config,
+            all_variants,
+            export_layers,
+            since,
+            export_palettes,
+            watch,
+            zip,
+            force,
+            quiet,
+            open,
+        } => {
+            let options = RunOptions {
+                all_variants,
+                export_layers,
+                since,
+                export_palettes,
+                zip,
+                force,
+            };
+            if !watch {
+                let preview = run_pipeline(&config, &options, cli.fail_on_warning, quiet)?;
+                if open {
+                    open_in_viewer(&preview);
+                }
+                return Ok(());
+            }
+            // Failed runs are reported and the watch keeps going until the next edit; the
+            // preview is opened once, after the first run that succeeds
+            let mut pending_open = open;
+            let mut rerun = || match run_pipeline(&config, &options, cli.fail_on_warning, quiet) {
+                Ok(preview) if pending_open => {
+                    open_in_viewer(&preview);
+                    pending_open = false;
+                }
+                Ok(_) => {}
+                Err(err) => eprintln!("Error: {:#}", err),
+            };
+            rerun();
+            if !quiet {
+                println!("Watching {} for changes (Ctrl-C to stop)", config.display());
+            }
+            watch::watch(&config, rerun)?;
+        }
+        Commands::CompareModels { image, output } => {
+            let summary = compare::compare_models(&image, &output)?;
             println!(
-                "Rendered {} scene(s) into {}",
-                summary.scene_count,
-                summary.output_dir.display()
+                "Framed {} model(s) into {} ({}x{})",
+                summary.model_count,
+                summary.output_path.display(),
+                summary.width,
+                summary.height
             );
-            println!("Preview: {}", summary.preview_path.display());
         }
         Commands::Devices => {
             println!("Built-in phone models:");
             for device in &devices::DEVICE_LISTINGS {
                 println!("  - {} ({})", device.slug, device.display_name);
             }
+            println!("Frame packs (fetch-frames --pack <name>):");
+            for pack in &frames::FRAME_PACKS {
+                println!("  - {} ({})", pack.name, pack.description);
+            }
         }
         Commands::ImportFrames {
             source,
             dest,
             overwrite,
+            dry_run,
+            format,
         } => {
-            let summary = frames::import_frames(&source, &dest, overwrite)?;
-            println!("Imported frames from {}", summary.source.display());
+            let summary = frames::import_frames(&source, &dest, overwrite, dry_run)?;
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&summary)?),
+                OutputFormat::Text => {
+                    println!("Imported frames from {}", summary.source.display());
+                    println!("Destination: {}", summary.destination.display());
+                    println!("Imported: {}", summary.imported);
+                    println!("Skipped: {}", summary.skipped);
+                    for line in &summary.notes {
+                        println!("  - {}", line);
+                    }
+                    if summary.dry_run {
+                        println!("Dry run: nothing was written");
+                    }
+                }
+            }
+            check_warnings(summary.skipped, cli.fail_on_warning)?;
+        }
+        Commands::FetchFrames {
+            pack,
+            dest,
+            overwrite,
+        } => {
+            let summary = frames::fetch_frames(&pack, &dest, overwrite)?;
+            println!("Fetched frames from {}", summary.source.display());
             println!("Destination: {}", summary.destination.display());
             println!("Imported: {}", summary.imported);
             println!("Skipped: {}", summary.skipped);
             for line in summary.notes {
                 println!("  - {}", line);
             }
+            check_warnings(summary.skipped, cli.fail_on_warning)?;
         }
-        Commands::VerifyOverlay { config, strict } => {
+        Commands::VerifyOverlay {
+            config,
+            strict,
+            format,
+        } => {
+            let strict = strict || cli.fail_on_warning;
             let summary = frames::verify_overlays(&config)?;
-            println!(
-                "Overlay checks: {} scene(s), {} overlay candidate(s), {} warning(s), {} error(s)",
-                summary.scene_count, summary.checked_overlays, summary.warnings, summary.errors
-            );
-            for issue in &summary.issues {
-                println!(
-                    "  [{}] {}: {}",
-                    issue.level.label(),
-                    issue.scene_id,
-                    issue.message
-                );
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&summary)?),
+                OutputFormat::Text => {
+                    println!(
+                        "Overlay checks: {} scene(s), {} overlay candidate(s), {} warning(s), {} error(s)",
+                        summary.scene_count,
+                        summary.checked_overlays,
+                        summary.warnings,
+                        summary.errors
+                    );
+                    for issue in &summary.issues {
+                        println!(
+                            "  [{}] {}: {}",
+                            issue.level.label(),
+                            issue.scene_id,
+                            issue.message
+                        );
+                    }
+                }
             }
             if summary.failed(strict) {
                 if strict && summary.errors == 0 && summary.warnings > 0 {
@@ -73,23 +163,72 @@ fn main() -> Result<()> {
                 bail!("overlay verification failed");
             }
         }
+        Commands::Validate { config, format } => {
+            let summary = validate::validate_config(&config)?;
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&summary)?),
+                OutputFormat::Text => {
+                    println!(
+                        "Validated {} scene(s): {} error(s)",
+                        summary.scene_count,
+                        summary.issues.len()
+                    );
+                    for issue in &summary.issues {
+                        println!("  [ERROR] {}: {}", issue.location, issue.message);
+                    }
+                }
+            }
+            if summary.failed() {
+                bail!("config validation failed");
+            }
+        }
+        Commands::Explain { config, format } => {
+            let explanation = explain::explain_config(&config)?;
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&explanation)?),
+                OutputFormat::Text => print_explanation(&explanation),
+            }
+        }
         Commands::Snap {
             simulator,
             output,
             raw,
             list,
+            batch,
+            boot,
+            boot_timeout,
+            scenes,
             format,
+            open,
+            preset,
             model,
+            fallback_model,
+            open_url,
+            record,
+            crop,
             settle_ms,
+            settle_stable,
+            settle_attempts,
+            settle_tolerance,
+            size,
             width,
             height,
+            phone_scale,
+            phone_bias_y,
             headline,
             subheadline,
+            headline_size,
+            subheadline_size,
+            text_position,
             background,
             seed,
             colors,
             auto_colors,
             auto_strategy,
+            qr,
+            qr_position,
+            qr_size,
+            export_layers,
         } => {
             // Handle --list flag
             if list {
@@ -103,6 +242,7 @@ fn main() -> Result<()> {
                             println!("No simulators are currently booted.");
                             println!("\nBoot a simulator with:");
                             println!("  xcrun simctl boot \"iPhone 17 Pro\"");
+                            println!("  screenforge snap --boot \"iPhone 17 Pro\"");
                         } else {
                             println!("Booted simulators:");
                             for sim in &booted {
@@ -119,6 +259,182 @@ fn main() -> Result<()> {
                 return Ok(());
             }
 
+            let mut config = match &preset {
+                Some(path) => SnapConfig::from_path(path)?,
+                None => SnapConfig::default(),
+            };
+            // Flags only override the preset when they were actually given
+            if let Some(size) = size {
+                (config.width, config.height) = SizePreset::from(size).dimensions();
+            }
+            if let Some(width) = width {
+                config.width = width;
+            }
+            if let Some(height) = height {
+                config.height = height;
+            }
+            if let Some(scale) = phone_scale {
+                config.phone_scale = scale;
+            }
+            if let Some(bias) = phone_bias_y {
+                config.phone_bias_y = bias;
+            }
+            if let Some(background) = background {
+                config.background_template = background.into();
+            }
+            if let Some(seed) = seed {
+                config.background_seed = seed;
+            }
+            if let Some(colors) = colors {
+                config.background_colors = colors;
+            }
+            if auto_colors {
+                config.auto_colors = true;
+            }
+            if let Some(strategy) = auto_strategy {
+                config.auto_strategy = strategy.into();
+            }
+            if headline.is_some() {
+                config.headline = headline;
+            }
+            if subheadline.is_some() {
+                config.subheadline = subheadline;
+            }
+            if let Some(size) = headline_size {
+                config.headline_size = size;
+            }
+            if let Some(size) = subheadline_size {
+                config.subheadline_size = size;
+            }
+            if let Some(position) = text_position {
+                config.text_position = position.into();
+            }
+            if open_url.is_some() {
+                config.open_url = open_url;
+            }
+            if crop.is_some() {
+                config.crop = crop;
+            }
+            if let Some(settle_ms) = settle_ms {
+                config.settle_ms = settle_ms;
+            }
+            if settle_stable {
+                config.settle_stable = true;
+            }
+            if let Some(attempts) = settle_attempts {
+                config.settle_attempts = attempts;
+            }
+            if let Some(tolerance) = settle_tolerance {
+                config.settle_tolerance = tolerance;
+            }
+            if let Some(fallback) = fallback_model {
+                config.fallback_model = Some(fallback.into());
+            }
+            if qr.is_some() {
+                config.qr_url = qr;
+            }
+            if let Some(position) = qr_position {
+                config.qr_position = position.into();
+            }
+            if let Some(size) = qr_size {
+                config.qr_size = size;
+            }
+            if export_layers.is_some() {
+                config.export_layers = export_layers;
+            }
+            let settle = config.settle();
+            let crop = config.crop;
+            let snap_config = (!raw).then_some(config);
+            let model = model.map(Into::into);
+            let capture = |query: &str, outputs: &[PathBuf]| match &snap_config {
+                Some(config) => snap::snap_framed(query, outputs, config, model),
+                None => snap::snap_raw(query, outputs, settle.clone(), crop),
+            };
+
+            // Boot before anything looks for booted simulators, so --batch and the
+            // single-capture path both see it
+            let simulator = match &boot {
+                Some(name) => {
+                    let booted =
+                        simulator::boot_simulator(name, Duration::from_secs(boot_timeout))?;
+                    info!("{} ({}) is booted", booted.name, booted.udid);
+                    simulator.or(Some(booted.udid))
+                }
+                None => simulator,
+            };
+
+            if let Some(seconds) = record {
+                let Some(query) = simulator else {
+                    bail!("--record needs a SIMULATOR (or --boot) to record");
+                };
+                let [output] = output.as_slice() else {
+                    bail!("--record writes a single video; pass one --output");
+                };
+                let path = snap::video_output_path(output);
+                let result =
+                    snap::record_video(&query, &path, Duration::from_secs(seconds), &settle)?;
+                match format {
+                    OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&result)?),
+                    OutputFormat::Text => {
+                        println!("✓ Recorded: {}", result.simulator_name);
+                        println!(
+                            "  Output: {} ({}s)",
+                            result.output_path, result.duration_secs
+                        );
+                    }
+                }
+                if open {
+                    open_in_viewer(&path);
+                }
+                return Ok(());
+            }
+
+            if batch {
+                let booted = snap::list_booted()?;
+                if booted.is_empty() {
+                    bail!("no simulators are booted; boot at least one before using --batch");
+                }
+                let mut results = Vec::with_capacity(booted.len());
+                for sim in &booted {
+                    let outputs = snap::batch_output_paths(&output, &sim.udid);
+                    let result = capture(&sim.udid, &outputs)
+                        .with_context(|| format!("failed to capture {} ({})", sim.name, sim.udid))?;
+                    results.push(result);
+                }
+                print_snap_results(&results, format)?;
+                if open {
+                    open_snap_outputs(&results);
+                }
+                check_snap_warnings(&results, cli.fail_on_warning)?;
+                return Ok(());
+            }
+
+            if !scenes.is_empty() {
+                let mut results = Vec::with_capacity(scenes.len());
+                for scene in &scenes {
+                    let outputs = [scene.output.clone()];
+                    let result = match &snap_config {
+                        Some(config) => snap::snap_framed(
+                            &scene.simulator,
+                            &outputs,
+                            &scene.apply(config),
+                            scene.model.or(model),
+                        ),
+                        None => snap::snap_raw(&scene.simulator, &outputs, settle.clone(), crop),
+                    }
+                    .with_context(|| {
+                        format!("failed to capture scene {}", scene.output.display())
+                    })?;
+                    results.push(result);
+                }
+                print_snap_results(&results, format)?;
+                if open {
+                    open_snap_outputs(&results);
+                }
+                check_snap_warnings(&results, cli.fail_on_warning)?;
+                return Ok(());
+            }
+
             // Require simulator argument if not listing
             let query = match simulator {
                 Some(q) => q,
@@ -135,6 +451,7 @@ fn main() -> Result<()> {
                                 println!("\nUsage: screenforge snap <SIMULATOR> [--output <PATH>]");
                                 println!("\nBoot a simulator first:");
                                 println!("  xcrun simctl boot \"iPhone 17 Pro\"");
+                                println!("  screenforge snap --boot \"iPhone 17 Pro\"");
                             } else {
                                 println!("Booted simulators:");
                                 for sim in &booted {
@@ -153,52 +470,17 @@ fn main() -> Result<()> {
                 }
             };
 
-            // Execute snap
-            let result = if raw {
-                snap::snap_raw(&query, &output, settle_ms)?
-            } else {
-                let config = SnapConfig {
-                    width,
-                    height,
-                    phone_x: None,
-                    phone_y: None,
-                    phone_width: None,
-                    phone_height: None,
-                    background_template: background.into(),
-                    background_seed: seed,
-                    background_colors: colors.unwrap_or_else(|| {
-                        vec![
-                            "#0B1022".to_string(),
-                            "#16479A".to_string(),
-                            "#2B8CD6".to_string(),
-                            "#A9E7FF".to_string(),
-                        ]
-                    }),
-                    auto_colors,
-                    auto_strategy: auto_strategy.into(),
-                    headline,
-                    subheadline,
-                    settle_ms,
-                    overlay: None,
-                };
-                snap::snap_framed(&query, &output, &config, model.map(Into::into))?
-            };
-
+            let result = capture(&query, &output)?;
             match format {
                 OutputFormat::Json => {
                     println!("{}", serde_json::to_string_pretty(&result)?);
                 }
-                OutputFormat::Text => {
-                    println!("✓ Captured: {}", result.simulator_name);
-                    if let Some(model) = &result.device_model {
-                        println!("  Model: {}", model);
-                    }
-                    println!(
-                        "  Output: {} ({}x{})",
-                        result.output_path, result.dimensions.width, result.dimensions.height
-                    );
-                }
+                OutputFormat::Text => print_snap_result(&result),
+            }
+            if open {
+                open_snap_outputs(std::slice::from_ref(&result));
             }
+            check_snap_warnings(std::slice::from_ref(&result), cli.fail_on_warning)?;
         }
         Commands::ConvertFrames {
             source,
@@ -214,8 +496,232 @@ fn main() -> Result<()> {
             for line in summary.notes {
                 println!("  - {}", line);
             }
+            check_warnings(summary.skipped, cli.fail_on_warning)?;
         }
     }
 
     Ok(())
 }
+
+/// Run the pipeline once, reporting each scene on stderr, and print its summary. Returns the
+/// preview index path.
+fn run_pipeline(
+    config: &Path,
+    options: &RunOptions,
+    fail_on_warning: bool,
+    quiet: bool,
+) -> Result<PathBuf> {
+    let summary = pipeline::run_with_progress(config, options, |progress| {
+        if !quiet {
+            eprintln!("{}", progress_line(progress));
+        }
+    })?;
+    if quiet {
+        check_warnings(summary.warnings.len(), fail_on_warning)?;
+        return Ok(summary.preview_path);
+    }
+    println!(
+        "Rendered {} scene(s) into {}",
+        summary.scene_count,
+        summary.output_dir.display()
+    );
+    if summary.unchanged > 0 {
+        println!(
+            "Kept {} unchanged scene(s) from the previous run",
+            summary.unchanged
+        );
+    }
+    println!("Preview: {}", summary.preview_path.display());
+    println!("Manifest: {}", summary.manifest_path.display());
+    for palette in &summary.palettes {
+        info!(
+            "{}: palette {}",
+            palette.scene_id,
+            palette.colors.join(", ")
+        );
+    }
+    if let Some(path) = &summary.palettes_path {
+        println!("Palettes: {}", path.display());
+    }
+    if let Some(path) = &summary.zip_path {
+        println!("Archive: {}", path.display());
+    }
+    check_warnings(summary.warnings.len(), fail_on_warning)?;
+    Ok(summary.preview_path)
+}
+
+/// Hand `path` to the platform's default viewer. This is a convenience, so failing to
+/// launch one only warns.
+fn open_in_viewer(path: &Path) {
+    let program = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(windows) {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    let launched = Command::new(program)
+        .arg(path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    if let Err(err) = launched {
+        warn!(
+            "could not open {} with {}: {}",
+            path.display(),
+            program,
+            err
+        );
+    }
+}
+
+fn progress_line(progress: &Progress) -> String {
+    let action = if progress.unchanged {
+        "kept"
+    } else {
+        "rendered"
+    };
+    let outputs = match progress.outputs {
+        1 => String::new(),
+        n => format!(" ({} images)", n),
+    };
+    format!(
+        "[{}/{}] {} {}{}",
+        progress.index, progress.total, action, progress.scene_id, outputs
+    )
+}
+
+fn print_snap_results(results: &[SnapResult], format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(results)?),
+        OutputFormat::Text => results.iter().for_each(print_snap_result),
+    }
+    Ok(())
+}
+
+fn print_snap_result(result: &SnapResult) {
+    println!("✓ Captured: {}", result.simulator_name);
+    if let Some(model) = &result.device_model {
+        println!("  Model: {}", model);
+    }
+    for path in &result.output_paths {
+        println!(
+            "  Output: {} ({}x{})",
+            path, result.dimensions.width, result.dimensions.height
+        );
+    }
+}
+
+fn print_explanation(explanation: &explain::Explanation) {
+    for scene in &explanation.scenes {
+        println!(
+            "{} ({}x{}, {})",
+            scene.scene_id, scene.width, scene.height, scene.filename
+        );
+        for device in &scene.devices {
+            let rect = &device.rect;
+            let orientation = match device.orientation {
+                Orientation::Portrait => "portrait",
+                Orientation::Landscape => "landscape",
+            };
+            println!(
+                "  {}: {} {} at {},{} {}x{} (profile: {})",
+                device.label,
+                device.model.as_deref().unwrap_or("no model"),
+                orientation,
+                rect.x,
+                rect.y,
+                rect.width,
+                rect.height,
+                device.profile.unwrap_or("none")
+            );
+            let style = &device.style;
+            let padding = style.screen_padding;
+            println!(
+                "    style: corner radius {}, padding {}/{}/{}/{} (top/right/bottom/left), \
+                 border {}, frame {}, shadow {}px alpha {}",
+                style.corner_radius,
+                padding.top,
+                padding.right,
+                padding.bottom,
+                padding.left,
+                style.frame_border_width,
+                style.frame_color,
+                style.shadow_offset_y,
+                style.shadow_alpha
+            );
+            let home_indicator = if style.home_indicator { "on" } else { "off" };
+            match style.island {
+                Some(island) => println!(
+                    "    island: width {}, height {}, y offset {}, lens {}; home indicator {}",
+                    island.width_ratio,
+                    island.height_ratio,
+                    island.y_offset_ratio,
+                    island.lens_size_ratio,
+                    home_indicator
+                ),
+                None => println!("    island: none; home indicator {}", home_indicator),
+            }
+            match &device.overlay {
+                Some(overlay) => println!(
+                    "    overlay: {} ({})",
+                    overlay.path.display(),
+                    overlay.source
+                ),
+                None => println!("    overlay: none (procedural frame)"),
+            }
+            match &device.screen {
+                Some(screen) => println!(
+                    "    screen: {},{} {}x{} (from {})",
+                    screen.rect.x,
+                    screen.rect.y,
+                    screen.rect.width,
+                    screen.rect.height,
+                    screen.source
+                ),
+                None => println!(
+                    "    screen: unavailable ({})",
+                    device.problem.as_deref().unwrap_or("unknown")
+                ),
+            }
+        }
+    }
+}
+
+/// Open the first output of every capture for `snap --open`
+fn open_snap_outputs(results: &[SnapResult]) {
+    for result in results {
+        open_in_viewer(Path::new(&result.output_path));
+    }
+}
+
+fn check_snap_warnings(results: &[SnapResult], fail_on_warning: bool) -> Result<()> {
+    let count = results.iter().map(|result| result.warnings.len()).sum();
+    check_warnings(count, fail_on_warning)
+}
+
+/// Route library warnings and diagnostics to stderr. Warnings are always shown;
+/// `-v`/`-vv` raise the level and `RUST_LOG` overrides both.
+fn init_logging(verbose: u8) {
+    let level = match verbose {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        _ => LevelFilter::Debug,
+    };
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_default_env()
+        .format(|buf, record| writeln!(buf, "  [{}] {}", record.level(), record.args()))
+        .init();
+}
+
+/// Fail the command under --fail-on-warning when anything was reported
+fn check_warnings(count: usize, fail_on_warning: bool) -> Result<()> {
+    if fail_on_warning && count > 0 {
+        bail!(
+            "{} warning(s) reported (failing because of --fail-on-warning)",
+            count
+        );
+    }
+    Ok(())
+}
@@ -1,10 +1,12 @@
 use image::{DynamicImage, Rgba};
+use serde::Deserialize;
 use std::collections::HashMap;
 
-use crate::color::{hsl_to_rgb, rgb_to_hsl, rgba_to_hex, Hsl};
+use crate::color::{delta_e, hsl_to_rgb, rgb_to_hsl, rgba_to_hex, Hsl};
 
 /// Strategy for generating background palette from dominant colors
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PaletteStrategy {
     /// Darker/lighter variations of dominant color (good for dark apps)
     #[default]
@@ -17,8 +19,50 @@ pub enum PaletteStrategy {
     Triadic,
 }
 
+/// How sampled pixels are grouped into dominant colors
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClusterMethod {
+    /// Most frequent bins of a coarse color histogram (fast, can merge nearby brand colors)
+    #[default]
+    Histogram,
+    /// K-means seeded from the histogram result, refining each color toward its cluster mean
+    Kmeans,
+}
+
+// Dominant colors closer than this (CIE76 delta E) count as the same color. Raise it for
+// fewer, more distinct colors; lower it to keep subtle variations apart.
+const SIMILAR_DELTA_E: f32 = 15.0;
+
+// Refinement passes for k-means; the histogram seeds are close, so it settles quickly
+const KMEANS_ROUNDS: usize = 8;
+
 /// Extract dominant colors from an image by sampling and clustering
-pub fn extract_dominant_colors(image: &DynamicImage, count: usize) -> Vec<Rgba<u8>> {
+pub fn extract_dominant_colors(
+    image: &DynamicImage,
+    count: usize,
+    method: ClusterMethod,
+) -> Vec<Rgba<u8>> {
+    let samples = sample_pixels(image);
+    if samples.is_empty() {
+        // Fallback: dark color
+        return vec![Rgba([30, 30, 40, 255])];
+    }
+
+    let mut dominant = histogram_colors(&samples, count);
+    if method == ClusterMethod::Kmeans {
+        dominant = kmeans_colors(&samples, dominant);
+    }
+
+    // Ensure we have at least one color
+    if dominant.is_empty() {
+        dominant.push(Rgba([30, 30, 40, 255]));
+    }
+
+    dominant
+}
+
+fn sample_pixels(image: &DynamicImage) -> Vec<Rgba<u8>> {
     let rgba = image.to_rgba8();
     let (width, height) = rgba.dimensions();
 
@@ -50,22 +94,21 @@ pub fn extract_dominant_colors(image: &DynamicImage, count: usize) -> Vec<Rgba<u
         }
     }
 
-    if samples.is_empty() {
-        // Fallback: dark color
-        return vec![Rgba([30, 30, 40, 255])];
-    }
+    samples
+}
 
+fn histogram_colors(samples: &[Rgba<u8>], count: usize) -> Vec<Rgba<u8>> {
     // Simple color quantization using histogram binning
     // Reduce color space to 32 levels per channel
     let mut histogram: HashMap<(u8, u8, u8), usize> = HashMap::new();
-    for pixel in &samples {
+    for pixel in samples {
         let key = (pixel[0] / 8, pixel[1] / 8, pixel[2] / 8);
         *histogram.entry(key).or_insert(0) += 1;
     }
 
     // Sort by frequency and take top colors
     let mut sorted: Vec<_> = histogram.into_iter().collect();
-    sorted.sort_by(|a, b| b.1.cmp(&a.1));
+    sorted.sort_by_key(|entry| std::cmp::Reverse(entry.1));
 
     // Convert back to full colors and filter similar ones
     let mut dominant = Vec::new();
@@ -73,14 +116,7 @@ pub fn extract_dominant_colors(image: &DynamicImage, count: usize) -> Vec<Rgba<u
         let color = Rgba([r * 8 + 4, g * 8 + 4, b * 8 + 4, 255]);
 
         // Skip if too similar to an existing dominant color
-        let dominated = dominant.iter().any(|existing: &Rgba<u8>| {
-            let dr = (color[0] as i32 - existing[0] as i32).abs();
-            let dg = (color[1] as i32 - existing[1] as i32).abs();
-            let db = (color[2] as i32 - existing[2] as i32).abs();
-            dr + dg + db < 60 // Similarity threshold
-        });
-
-        if !dominated {
+        if !dominant.iter().any(|existing| is_similar(color, *existing)) {
             dominant.push(color);
             if dominant.len() >= count {
                 break;
@@ -88,14 +124,85 @@ pub fn extract_dominant_colors(image: &DynamicImage, count: usize) -> Vec<Rgba<u
         }
     }
 
-    // Ensure we have at least one color
-    if dominant.is_empty() {
-        dominant.push(Rgba([30, 30, 40, 255]));
+    dominant
+}
+
+/// Lloyd iterations from `seeds`, returned largest cluster first so the most dominant
+/// color still leads. Centroids that drift into each other are merged.
+fn kmeans_colors(samples: &[Rgba<u8>], seeds: Vec<Rgba<u8>>) -> Vec<Rgba<u8>> {
+    let mut centroids: Vec<[f32; 3]> = seeds
+        .iter()
+        .map(|color| [color[0] as f32, color[1] as f32, color[2] as f32])
+        .collect();
+    if centroids.is_empty() {
+        return seeds;
+    }
+
+    let mut sizes = vec![0usize; centroids.len()];
+    for _ in 0..KMEANS_ROUNDS {
+        let mut sums = vec![[0.0f32; 3]; centroids.len()];
+        sizes.iter_mut().for_each(|size| *size = 0);
+        for pixel in samples {
+            let rgb = [pixel[0] as f32, pixel[1] as f32, pixel[2] as f32];
+            let nearest = nearest_centroid(&centroids, rgb);
+            for (sum, value) in sums[nearest].iter_mut().zip(rgb) {
+                *sum += value;
+            }
+            sizes[nearest] += 1;
+        }
+
+        let mut moved = false;
+        for ((centroid, sum), size) in centroids.iter_mut().zip(&sums).zip(&sizes) {
+            // An empty cluster keeps its seed rather than collapsing to black
+            if *size == 0 {
+                continue;
+            }
+            let mean = sum.map(|value| value / *size as f32);
+            moved |= centroid
+                .iter()
+                .zip(mean)
+                .any(|(old, new)| (old - new).abs() > 0.5);
+            *centroid = mean;
+        }
+        if !moved {
+            break;
+        }
     }
 
+    let mut ranked: Vec<_> = centroids.into_iter().zip(sizes).collect();
+    ranked.sort_by_key(|entry| std::cmp::Reverse(entry.1));
+
+    let mut dominant = Vec::new();
+    for (centroid, _) in ranked {
+        let [r, g, b] = centroid.map(|value| value.round().clamp(0.0, 255.0) as u8);
+        let color = Rgba([r, g, b, 255]);
+        if !dominant.iter().any(|existing| is_similar(color, *existing)) {
+            dominant.push(color);
+        }
+    }
     dominant
 }
 
+fn nearest_centroid(centroids: &[[f32; 3]], rgb: [f32; 3]) -> usize {
+    let distance = |centroid: &[f32; 3]| -> f32 {
+        centroid
+            .iter()
+            .zip(rgb)
+            .map(|(c, v)| (c - v) * (c - v))
+            .sum()
+    };
+    centroids
+        .iter()
+        .enumerate()
+        .min_by(|a, b| distance(a.1).total_cmp(&distance(b.1)))
+        .map(|(index, _)| index)
+        .unwrap_or(0)
+}
+
+fn is_similar(a: Rgba<u8>, b: Rgba<u8>) -> bool {
+    delta_e(a, b) < SIMILAR_DELTA_E
+}
+
 /// Generate a background palette from dominant colors using the specified strategy
 pub fn generate_palette(dominant: &[Rgba<u8>], strategy: PaletteStrategy) -> Vec<String> {
     if dominant.is_empty() {
@@ -113,7 +220,7 @@ pub fn generate_palette(dominant: &[Rgba<u8>], strategy: PaletteStrategy) -> Vec
         PaletteStrategy::Triadic => generate_triadic(base_hsl),
     };
 
-    colors.into_iter().map(|c| rgba_to_hex(c)).collect()
+    colors.into_iter().map(rgba_to_hex).collect()
 }
 
 fn generate_monochromatic(base: Hsl) -> Vec<Rgba<u8>> {
@@ -182,10 +289,28 @@ mod tests {
     #[test]
     fn test_extract_returns_colors() {
         let img = DynamicImage::new_rgba8(100, 100);
-        let colors = extract_dominant_colors(&img, 4);
+        let colors = extract_dominant_colors(&img, 4, ClusterMethod::Histogram);
         assert!(!colors.is_empty());
     }
 
+    #[test]
+    fn kmeans_recovers_exact_region_colors() {
+        // Two flat halves: histogram bins snap to bin centers, k-means lands on the real colors
+        let mut img = image::RgbaImage::from_pixel(200, 200, Rgba([200, 30, 90, 255]));
+        for y in 0..200 {
+            for x in 0..80 {
+                img.put_pixel(x, y, Rgba([10, 120, 220, 255]));
+            }
+        }
+        let img = DynamicImage::ImageRgba8(img);
+
+        let colors = extract_dominant_colors(&img, 4, ClusterMethod::Kmeans);
+        assert_eq!(
+            colors,
+            vec![Rgba([200, 30, 90, 255]), Rgba([10, 120, 220, 255])]
+        );
+    }
+
     #[test]
     fn test_generate_palette_monochromatic() {
         let dominant = vec![Rgba([100, 50, 150, 255])];
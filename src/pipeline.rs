@@ -1,28 +1,159 @@
-use std::collections::HashSet;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::{Context, Result, bail};
-use image::DynamicImage;
+use image::{DynamicImage, RgbaImage};
+use log::{debug, info, warn};
+use serde::Serialize;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
 
-use crate::background::render_background;
-use crate::capture::capture_scene;
-use crate::compose::compose_scene;
-use crate::config::{AutoColorStrategy, Config};
-use crate::palette::{extract_dominant_colors, generate_palette, PaletteStrategy};
+use crate::background::render_scene_background;
+use crate::capture::capture_source;
+use crate::color::{contrast_ratio, parse_hex_rgba};
+use crate::compose::{compose_layers, compose_scene};
+use crate::config::{
+    AlphaMode, AutoColorStrategy, BackgroundConfig, BackgroundTemplate, CaptureConfig, Config,
+    CopyConfig, CornerRadius, DeviceProfileConfig, FontWeight, Headline, ModelRef, OutputConfig,
+    OutputVariant, PhoneConfig, SceneConfig,
+};
+use crate::frames::{VerifyLevel, resolve_overlay_for_compose, scene_overlay_issues};
+use crate::palette::{ClusterMethod, PaletteStrategy, extract_dominant_colors, generate_palette};
 use crate::preview::{PreviewItem, write_index};
 
+// Screenshot/phone aspect ratios further apart than this mean visible cropping
+const CROP_WARN_TOLERANCE: f32 = 0.08;
+// Palettes whose most distant pair is below this contrast render as a flat fill
+const FLAT_PALETTE_CONTRAST: f32 = 1.25;
+// Full-canvas buffers alive while composing one output: the canvas it's drawn onto, plus
+// the copy's own layer and shadow mask, or a tilted device and its warped copy
+const COMPOSE_CANVAS_BUFFERS: u64 = 3;
+// Frame, screenshot and overlay layers kept per device by --export-layers
+const DEVICE_LAYER_BUFFERS: u64 = 3;
+// Estimated peaks above this risk OOM on small CI runners
+const MEMORY_WARN_BYTES: u64 = 1024 * 1024 * 1024;
+// Written into the output directory at the end of each run for `--since last`
+const LAST_RUN_MARKER: &str = ".last-run";
+// Scene id -> fingerprint of the inputs its current outputs were rendered from
+const SCENE_CACHE: &str = ".scene-cache.json";
+
 pub struct RunSummary {
     pub scene_count: usize,
     pub output_dir: PathBuf,
     pub preview_path: PathBuf,
+    pub warnings: Vec<RunWarning>,
+    /// Scenes left as-is because their inputs did not change (cache hit or `--since`)
+    pub unchanged: usize,
+    /// Resolved background colors per scene, in config order
+    pub palettes: Vec<ScenePalette>,
+    pub palettes_path: Option<PathBuf>,
+    /// `<output_dir>/manifest.json`, describing every output of the run
+    pub manifest_path: PathBuf,
+    pub zip_path: Option<PathBuf>,
+}
+
+/// One rendered output in `manifest.json`; paths are relative to the output directory
+#[derive(Debug, Serialize)]
+pub struct ManifestEntry {
+    pub scene_id: String,
+    /// One raw screenshot per device, in draw order
+    pub raw_paths: Vec<String>,
+    pub final_path: String,
+    pub width: u32,
+    pub height: u32,
+    pub phone_model: Option<ModelRef>,
+    pub background_template: BackgroundTemplate,
+    pub colors: Vec<String>,
+    /// Kept from a previous run by `--since`
+    pub unchanged: bool,
+}
+
+/// Background colors a scene was rendered with, after auto extraction
+#[derive(Debug, Clone, Serialize)]
+pub struct ScenePalette {
+    pub scene_id: String,
+    pub colors: Vec<String>,
+}
+
+pub struct RunWarning {
+    pub scene_id: String,
+    pub message: String,
 }
 
-pub fn run(config_path: &Path) -> Result<RunSummary> {
-    let config = Config::from_path(config_path)?;
-    if config.scenes.is_empty() {
-        bail!("config has no scenes");
+/// Optional behavior for a pipeline run, driven by `run` CLI flags
+#[derive(Debug, Default)]
+pub struct RunOptions {
+    /// Render one output per headline variant instead of picking one by seed
+    pub all_variants: bool,
+    /// Also write each compose layer as a PNG under `<dir>/<scene_id>/`
+    pub export_layers: Option<PathBuf>,
+    /// Skip file-based scenes whose source is older than this and whose outputs exist
+    pub since: Option<Since>,
+    /// Write `<output_dir>/palettes.json` mapping scene id to the colors actually used
+    pub export_palettes: bool,
+    /// Package the final images and manifest into a zip archive at this path
+    pub zip: Option<PathBuf>,
+    /// Re-render every scene even when its cached fingerprint matches
+    pub force: bool,
+}
+
+/// Reported to the `run_with_progress` callback as each scene finishes
+#[derive(Debug, Clone, Copy)]
+pub struct Progress<'a> {
+    /// 1-based position of the scene in the config
+    pub index: usize,
+    pub total: usize,
+    pub scene_id: &'a str,
+    /// Images the scene produced (one per size and headline variant)
+    pub outputs: usize,
+    /// The scene was skipped by `since` and its previous outputs kept
+    pub unchanged: bool,
+}
+
+/// Cutoff for incremental runs
+#[derive(Debug, Clone, Copy)]
+pub enum Since {
+    /// The start of the previous run, recorded in `<output_dir>/.last-run`
+    LastRun,
+    Time(SystemTime),
+}
+
+impl FromStr for Since {
+    type Err = String;
+
+    /// Accepts `last` or a Unix timestamp in seconds
+    fn from_str(raw: &str) -> std::result::Result<Self, Self::Err> {
+        if raw == "last" {
+            return Ok(Self::LastRun);
+        }
+        raw.parse::<u64>()
+            .map(|secs| Self::Time(UNIX_EPOCH + Duration::from_secs(secs)))
+            .map_err(|_| {
+                format!(
+                    "expected 'last' or a Unix timestamp in seconds, got '{}'",
+                    raw
+                )
+            })
     }
+}
+
+pub fn run(config_path: &Path, options: &RunOptions) -> Result<RunSummary> {
+    run_with_progress(config_path, options, |_| {})
+}
+
+/// [`run`], calling `progress` after every scene so long runs can report where they are
+pub fn run_with_progress(
+    config_path: &Path,
+    options: &RunOptions,
+    progress: impl Fn(&Progress),
+) -> Result<RunSummary> {
+    let (config, source) = Config::load(config_path)?;
 
     let config_dir = config_path
         .parent()
@@ -32,60 +163,861 @@ pub fn run(config_path: &Path) -> Result<RunSummary> {
     let raw_dir = output_root.join("raw");
     let final_dir = output_root.join("final");
     let preview_path = output_root.join("index.html");
+    let manifest_path = output_root.join("manifest.json");
 
     fs::create_dir_all(&raw_dir)
         .with_context(|| format!("failed creating {}", raw_dir.display()))?;
     fs::create_dir_all(&final_dir)
         .with_context(|| format!("failed creating {}", final_dir.display()))?;
 
+    let run_started = SystemTime::now();
+    let last_run_path = output_root.join(LAST_RUN_MARKER);
+    let cutoff = match options.since {
+        Some(Since::Time(time)) => Some(time),
+        Some(Since::LastRun) => read_last_run(&last_run_path),
+        None => None,
+    };
+    let cache_path = output_root.join(SCENE_CACHE);
+    let previous_cache = read_scene_cache(&cache_path);
+    let mut scene_cache = BTreeMap::new();
+
     let mut seen_ids = HashSet::new();
     let mut preview_items = Vec::with_capacity(config.scenes.len());
+    let mut warnings = Vec::new();
+    let mut unchanged = 0usize;
+    let mut palettes = Vec::with_capacity(config.scenes.len());
+    let mut manifest = Vec::with_capacity(config.scenes.len());
 
-    for scene in &config.scenes {
+    if let Some(warning) = check_memory_estimate(&config.scenes, options.export_layers.is_some()) {
+        push_warning(&mut warnings, &warning.scene_id, warning.message);
+    }
+
+    let total = config.scenes.len();
+    for (index, scene) in config.scenes.iter().enumerate() {
         if !seen_ids.insert(scene.id.clone()) {
             bail!("duplicate scene id '{}'", scene.id);
         }
 
-        let raw_path = raw_dir.join(format!("{}.png", scene.id));
-        capture_scene(scene, &config_dir, &raw_path)?;
-
-        let raw_img = image::open(&raw_path)
-            .with_context(|| format!("failed opening raw screenshot {}", raw_path.display()))?;
+        let mut variants = Vec::new();
+        for variant in expand_variants(scene, options.all_variants) {
+            variants.extend(expand_sizes(&variant)?);
+        }
 
-        // Extract colors from screenshot if auto_colors is enabled
-        let bg_config = if scene.background.auto_colors {
-            let palette = extract_auto_palette(&raw_img, scene.background.auto_strategy);
-            let mut cfg = scene.background.clone();
-            cfg.colors = palette;
-            cfg
-        } else {
-            scene.background.clone()
+        // The config part of the cached fingerprint catches YAML edits since the last render
+        let config_unchanged = || -> Result<bool> {
+            let config = config_fingerprint(&source, index, options.all_variants)?;
+            Ok(previous_cache
+                .get(&scene.id)
+                .and_then(|fingerprint| fingerprint.split('-').next())
+                == Some(config.as_str()))
         };
+        if let Some(cutoff) = cutoff
+            && !options.force
+            && options.export_layers.is_none()
+            && is_unchanged_since(scene, &config_dir, &raw_dir, &final_dir, &variants, cutoff)
+            && config_unchanged()?
+        {
+            info!("{}: source unchanged, keeping existing output", scene.id);
+            unchanged += 1;
+            if let Some(fingerprint) = previous_cache.get(&scene.id) {
+                scene_cache.insert(scene.id.clone(), fingerprint.clone());
+            }
+            // The kept raw screenshots stand in for a capture, so warnings still reach
+            // --fail-on-warning
+            let mut screenshots = Vec::with_capacity(1 + scene.phones.len());
+            for device in 0..scene.devices().count() {
+                let device_path = device_raw_path(&raw_dir, &scene.id, device);
+                screenshots.push(image::open(&device_path).with_context(|| {
+                    format!("failed opening raw screenshot {}", device_path.display())
+                })?);
+            }
+            let bg_config = resolve_background(scene, &config_dir, &screenshots[0]);
+            collect_scene_warnings(
+                scene,
+                &config_dir,
+                &config.devices,
+                &screenshots,
+                &bg_config,
+                &mut warnings,
+            );
+            let colors = bg_config.colors;
+            manifest.extend(
+                variants
+                    .iter()
+                    .map(|variant| manifest_entry(variant, &scene.id, &colors, true)),
+            );
+            palettes.push(ScenePalette {
+                scene_id: scene.id.clone(),
+                colors,
+            });
+            preview_items.extend(
+                variants
+                    .iter()
+                    .map(|variant| preview_item(variant, &scene.id)),
+            );
+            progress(&Progress {
+                index: index + 1,
+                total,
+                scene_id: &scene.id,
+                outputs: variants.len(),
+                unchanged: true,
+            });
+            continue;
+        }
 
-        let background = render_background(&bg_config, scene.output.width, scene.output.height)?;
-        let final_img = compose_scene(&raw_img, scene, background, &config_dir)?;
-
-        let final_path = final_dir.join(&scene.output.filename);
-        final_img
-            .save(&final_path)
-            .with_context(|| format!("failed writing {}", final_path.display()))?;
+        let mut screenshots = Vec::with_capacity(1 + scene.phones.len());
+        for (device, (capture, _)) in scene.devices().enumerate() {
+            let device_path = device_raw_path(&raw_dir, &scene.id, device);
+            debug!("{}: capturing into {}", scene.id, device_path.display());
+            capture_source(&scene.id, capture, &config_dir, &device_path)?;
+            let screenshot = image::open(&device_path).with_context(|| {
+                format!("failed opening raw screenshot {}", device_path.display())
+            })?;
+            screenshots.push(screenshot);
+        }
+        let fingerprint = scene_fingerprint(
+            &source,
+            index,
+            scene,
+            &config.fonts,
+            &config_dir,
+            &raw_dir,
+            options.all_variants,
+        )?;
+        // Layer export needs the render itself, so it never reuses cached outputs
+        let cached = !options.force
+            && options.export_layers.is_none()
+            && previous_cache.get(&scene.id) == Some(&fingerprint)
+            && variants
+                .iter()
+                .all(|variant| final_dir.join(&variant.output.filename).exists());
+        scene_cache.insert(scene.id.clone(), fingerprint);
+        let raw_img = &screenshots[0];
 
-        preview_items.push(PreviewItem {
+        let bg_config = resolve_background(scene, &config_dir, raw_img);
+        palettes.push(ScenePalette {
             scene_id: scene.id.clone(),
-            raw_rel: format!("raw/{}.png", scene.id),
-            final_rel: format!("final/{}", scene.output.filename),
+            colors: bg_config.colors.clone(),
+        });
+
+        collect_scene_warnings(
+            scene,
+            &config_dir,
+            &config.devices,
+            &screenshots,
+            &bg_config,
+            &mut warnings,
+        );
+
+        // Warnings are still collected above so --fail-on-warning sees cached scenes too
+        if cached {
+            info!("{}: inputs unchanged, keeping existing output", scene.id);
+            unchanged += 1;
+            manifest.extend(
+                variants
+                    .iter()
+                    .map(|variant| manifest_entry(variant, &scene.id, &bg_config.colors, true)),
+            );
+            preview_items.extend(
+                variants
+                    .iter()
+                    .map(|variant| preview_item(variant, &scene.id)),
+            );
+            progress(&Progress {
+                index: index + 1,
+                total,
+                scene_id: &scene.id,
+                outputs: variants.len(),
+                unchanged: true,
+            });
+            continue;
+        }
+
+        // Sizes shared by several variants (e.g. one per headline) reuse one background
+        let outputs = variants.len();
+        let mut backgrounds = HashMap::new();
+        for variant in variants {
+            let size = (variant.output.width, variant.output.height);
+            let background = match backgrounds.entry(size) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(render_scene_background(
+                    &bg_config, size.0, size.1, raw_img,
+                )?),
+            };
+            let final_img = match &options.export_layers {
+                Some(layers_dir) => {
+                    let layers = compose_layers(
+                        &screenshots,
+                        &variant,
+                        background.clone(),
+                        &config_dir,
+                        &config.fonts,
+                        &config.devices,
+                    )?;
+                    layers.save(&layers_dir.join(&variant.id))?;
+                    layers.flatten()
+                }
+                None => compose_scene(
+                    &screenshots,
+                    &variant,
+                    background.clone(),
+                    &config_dir,
+                    &config.fonts,
+                    &config.devices,
+                )?,
+            };
+
+            let final_path = final_dir.join(&variant.output.filename);
+            if let Some(parent) = final_path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("failed creating {}", parent.display()))?;
+            }
+            final_img
+                .save(&final_path)
+                .with_context(|| format!("failed writing {}", final_path.display()))?;
+            info!("{}: wrote {}", variant.id, final_path.display());
+
+            manifest.push(manifest_entry(
+                &variant,
+                &scene.id,
+                &bg_config.colors,
+                false,
+            ));
+            preview_items.push(preview_item(&variant, &scene.id));
+        }
+        progress(&Progress {
+            index: index + 1,
+            total,
+            scene_id: &scene.id,
+            outputs,
+            unchanged: false,
         });
     }
 
     write_index(&preview_path, &preview_items)?;
+    let manifest_json = serde_json::to_string_pretty(&manifest)?;
+    fs::write(&manifest_path, manifest_json + "\n")
+        .with_context(|| format!("failed writing {}", manifest_path.display()))?;
+
+    let palettes_path = if options.export_palettes {
+        let path = output_root.join("palettes.json");
+        write_palettes(&path, &palettes)?;
+        Some(path)
+    } else {
+        None
+    };
+
+    if let Some(path) = &options.zip {
+        write_zip(path, &output_root, &manifest, &manifest_path)?;
+    }
+
+    let started_secs = run_started
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    fs::write(&last_run_path, format!("{}\n", started_secs))
+        .with_context(|| format!("failed writing {}", last_run_path.display()))?;
+    let cache_json = serde_json::to_string_pretty(&scene_cache)?;
+    fs::write(&cache_path, cache_json + "\n")
+        .with_context(|| format!("failed writing {}", cache_path.display()))?;
 
     Ok(RunSummary {
         scene_count: preview_items.len(),
         output_dir: output_root,
         preview_path,
+        warnings,
+        unchanged,
+        palettes,
+        palettes_path,
+        manifest_path,
+        zip_path: options.zip.clone(),
     })
 }
 
+/// Render one scene to its final image without writing anything. `screenshots` holds one
+/// image per device in `SceneConfig::devices` order; relative paths (background image,
+/// overlays, logo) resolve against `config_dir`.
+pub fn render_scene(
+    config: &Config,
+    scene: &SceneConfig,
+    screenshots: &[DynamicImage],
+    config_dir: &Path,
+) -> Result<RgbaImage> {
+    let Some(raw_img) = screenshots.first() else {
+        bail!("scene '{}' needs at least one screenshot", scene.id);
+    };
+    let bg_config = resolve_background(scene, config_dir, raw_img);
+    let background =
+        render_scene_background(&bg_config, scene.output.width, scene.output.height, raw_img)?;
+    compose_scene(
+        screenshots,
+        scene,
+        background,
+        config_dir,
+        &config.fonts,
+        &config.devices,
+    )
+}
+
+fn manifest_entry(
+    variant: &SceneConfig,
+    scene_id: &str,
+    colors: &[String],
+    unchanged: bool,
+) -> ManifestEntry {
+    ManifestEntry {
+        scene_id: variant.id.clone(),
+        raw_paths: device_raw_rels(variant, scene_id),
+        final_path: format!("final/{}", variant.output.filename),
+        width: variant.output.width,
+        height: variant.output.height,
+        phone_model: variant.phone().model.clone(),
+        background_template: variant.background.template,
+        colors: colors.to_vec(),
+        unchanged,
+    }
+}
+
+fn preview_item(variant: &SceneConfig, scene_id: &str) -> PreviewItem {
+    PreviewItem {
+        scene_id: variant.id.clone(),
+        raw_rels: device_raw_rels(variant, scene_id),
+        final_rel: format!("final/{}", variant.output.filename),
+        width: variant.output.width,
+        height: variant.output.height,
+        phone_model: variant
+            .phone()
+            .model
+            .as_ref()
+            .map(|model| model.slug().to_string()),
+        background_template: variant.background.template.name().to_string(),
+        headline: variant
+            .copy
+            .as_ref()
+            .map(|copy| copy.headline.select(variant.background.seed).to_string())
+            .filter(|headline| !headline.is_empty()),
+    }
+}
+
+/// Extract colors from the screenshot when auto_colors is enabled and resolve the
+/// background image path against the config directory
+fn resolve_background(
+    scene: &SceneConfig,
+    config_dir: &Path,
+    raw_img: &DynamicImage,
+) -> BackgroundConfig {
+    let mut cfg = scene.background.clone();
+    if cfg.auto_colors {
+        cfg.colors = extract_auto_palette(raw_img, cfg.auto_strategy);
+    }
+    if let Some(path) = cfg.image.as_mut() {
+        *path = resolve_path(config_dir, path);
+    }
+    cfg
+}
+
+/// Write `{ "<scene_id>": ["#RRGGBB", ...] }` so a generated palette can be pinned in config
+fn write_palettes(path: &Path, palettes: &[ScenePalette]) -> Result<()> {
+    let map: serde_json::Map<String, serde_json::Value> = palettes
+        .iter()
+        .map(|palette| (palette.scene_id.clone(), palette.colors.clone().into()))
+        .collect();
+    let json = serde_json::to_string_pretty(&map)?;
+    fs::write(path, json + "\n").with_context(|| format!("failed writing {}", path.display()))
+}
+
+/// Zip every final image as `<output_id>/<file name>`, plus `manifest.json` at the root
+fn write_zip(
+    path: &Path,
+    output_root: &Path,
+    manifest: &[ManifestEntry],
+    manifest_path: &Path,
+) -> Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed creating {}", parent.display()))?;
+    }
+    let file =
+        fs::File::create(path).with_context(|| format!("failed creating {}", path.display()))?;
+    let mut archive = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut entries: Vec<(String, PathBuf)> = manifest
+        .iter()
+        .map(|entry| {
+            let source = output_root.join(&entry.final_path);
+            let file_name = source
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            (format!("{}/{}", entry.scene_id, file_name), source)
+        })
+        .collect();
+    if manifest_path.exists() {
+        entries.push(("manifest.json".to_string(), manifest_path.to_path_buf()));
+    }
+
+    for (name, source) in entries {
+        let bytes =
+            fs::read(&source).with_context(|| format!("failed reading {}", source.display()))?;
+        archive
+            .start_file(name.as_str(), options)
+            .with_context(|| format!("failed adding {} to {}", name, path.display()))?;
+        archive.write_all(&bytes)?;
+    }
+    archive
+        .finish()
+        .with_context(|| format!("failed writing {}", path.display()))?;
+    info!("wrote {}", path.display());
+    Ok(())
+}
+
+fn read_scene_cache(path: &Path) -> BTreeMap<String, String> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Files a scene reads besides its config: capture sources, overlays, background image,
+/// logo and fonts, resolved against `config_dir`
+pub(crate) fn scene_inputs(scene: &SceneConfig, config_dir: &Path) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    for (capture, phone) in scene.devices() {
+        match capture {
+            CaptureConfig::File { path, .. } | CaptureConfig::Region { path, .. } => {
+                paths.push(path.clone());
+            }
+            CaptureConfig::Simctl { .. }
+            | CaptureConfig::Adb { .. }
+            | CaptureConfig::Command { .. } => {}
+        }
+        if let Some(overlay) = resolve_overlay_for_compose(phone, config_dir) {
+            paths.push(overlay.path);
+        }
+    }
+    paths.extend(scene.background.image.clone());
+    if let Some(copy) = &scene.copy {
+        paths.extend(copy.logo.as_ref().map(|logo| logo.path.clone()));
+        paths.extend(copy.font_path.clone());
+        paths.extend(copy.fonts.values().cloned());
+    }
+    paths
+        .iter()
+        .map(|path| resolve_path(config_dir, path))
+        .collect()
+}
+
+/// Hash of everything a scene's render depends on: the scene's YAML, the shared `fonts`
+/// and `devices`, the fresh captures in `raw_dir` and the contents of every referenced
+/// file. The crate version is included so an upgrade re-renders everything.
+fn scene_fingerprint(
+    source: &serde_yaml::Value,
+    scene_index: usize,
+    scene: &SceneConfig,
+    fonts: &HashMap<FontWeight, PathBuf>,
+    config_dir: &Path,
+    raw_dir: &Path,
+    all_variants: bool,
+) -> Result<String> {
+    let config = config_fingerprint(source, scene_index, all_variants)?;
+    let mut hasher = DefaultHasher::new();
+    let mut inputs = scene_inputs(scene, config_dir);
+    inputs.extend(fonts.values().map(|path| resolve_path(config_dir, path)));
+    // Font maps iterate in random order
+    inputs.sort();
+    let raw_paths =
+        (0..scene.devices().count()).map(|index| device_raw_path(raw_dir, &scene.id, index));
+    for path in raw_paths.chain(inputs) {
+        path.hash(&mut hasher);
+        // A missing file hashes as empty; rendering reports it if it matters
+        fs::read(&path).unwrap_or_default().hash(&mut hasher);
+    }
+    Ok(format!("{}-{:016x}", config, hasher.finish()))
+}
+
+/// Hash of the YAML a scene renders from (its entry, `fonts` and `devices`), without any
+/// file contents. Scene fingerprints start with it so `--since` can tell config edits apart.
+fn config_fingerprint(
+    source: &serde_yaml::Value,
+    scene_index: usize,
+    all_variants: bool,
+) -> Result<String> {
+    let mut hasher = DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    all_variants.hash(&mut hasher);
+    let scene_source = source
+        .get("scenes")
+        .and_then(|scenes| scenes.get(scene_index));
+    for value in [scene_source, source.get("fonts"), source.get("devices")] {
+        serde_yaml::to_string(&value)?.hash(&mut hasher);
+    }
+    Ok(format!("{:016x}", hasher.finish()))
+}
+
+fn read_last_run(path: &Path) -> Option<SystemTime> {
+    let secs = fs::read_to_string(path).ok()?.trim().parse::<u64>().ok()?;
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+/// Raw screenshot path for a scene device: `<id>.png` for the first device and
+/// `<id>.phone<N>.png` for device N
+fn device_raw_path(raw_dir: &Path, scene_id: &str, index: usize) -> PathBuf {
+    raw_dir.join(device_raw_name(scene_id, index))
+}
+
+fn device_raw_name(scene_id: &str, index: usize) -> String {
+    if index == 0 {
+        format!("{}.png", scene_id)
+    } else {
+        format!("{}.phone{}.png", scene_id, index)
+    }
+}
+
+/// `raw/...` paths relative to the output directory, one per device
+fn device_raw_rels(scene: &SceneConfig, scene_id: &str) -> Vec<String> {
+    (0..scene.phones.len())
+        .map(|index| format!("raw/{}", device_raw_name(scene_id, index)))
+        .collect()
+}
+
+/// File and region scenes can be skipped when every device source predates the cutoff and
+/// every output from a previous run is still on disk. Device and command captures always re-run.
+/// Callers also compare the config fingerprint, since YAML edits leave the sources untouched.
+fn is_unchanged_since(
+    scene: &SceneConfig,
+    config_dir: &Path,
+    raw_dir: &Path,
+    final_dir: &Path,
+    variants: &[SceneConfig],
+    cutoff: SystemTime,
+) -> bool {
+    let sources_unchanged = scene.devices().enumerate().all(|(index, (capture, _))| {
+        let source = match capture {
+            CaptureConfig::File { path, .. } | CaptureConfig::Region { path, .. } => {
+                resolve_path(config_dir, path)
+            }
+            CaptureConfig::Simctl { .. }
+            | CaptureConfig::Adb { .. }
+            | CaptureConfig::Command { .. } => return false,
+        };
+        fs::metadata(&source)
+            .and_then(|meta| meta.modified())
+            .is_ok_and(|modified| modified <= cutoff)
+            && device_raw_path(raw_dir, &scene.id, index).exists()
+    });
+
+    sources_unchanged
+        && variants
+            .iter()
+            .all(|variant| final_dir.join(&variant.output.filename).exists())
+}
+
+/// Log a warning and keep it on the run summary so `--fail-on-warning` can see it
+fn push_warning(warnings: &mut Vec<RunWarning>, scene_id: &str, message: String) {
+    warn!("{}: {}", scene_id, message);
+    warnings.push(RunWarning {
+        scene_id: scene_id.to_string(),
+        message,
+    });
+}
+
+fn collect_scene_warnings(
+    scene: &SceneConfig,
+    config_dir: &Path,
+    devices: &HashMap<String, DeviceProfileConfig>,
+    screenshots: &[DynamicImage],
+    bg_config: &BackgroundConfig,
+    warnings: &mut Vec<RunWarning>,
+) {
+    let mut warn = |message: String| push_warning(warnings, &scene.id, message);
+
+    for issue in scene_overlay_issues(scene, config_dir, devices).unwrap_or_default() {
+        if issue.level == VerifyLevel::Warning {
+            warn(issue.message);
+        }
+    }
+
+    for (raw_img, (capture, phone)) in screenshots.iter().zip(scene.devices()) {
+        if let CaptureConfig::File { alpha, .. } | CaptureConfig::Region { alpha, .. } = capture
+            && *alpha == AlphaMode::Warn
+        {
+            let transparent = raw_img
+                .to_rgba8()
+                .pixels()
+                .filter(|pixel| pixel[3] < 255)
+                .count();
+            if transparent > 0 {
+                warn(format!(
+                    "capture has {} transparent pixel(s); the background will show through the screenshot",
+                    transparent
+                ));
+            }
+        }
+        if raw_img.width() == 0 || phone.width == 0 || phone.height == 0 {
+            continue;
+        }
+        let raw_aspect = raw_img.height() as f32 / raw_img.width() as f32;
+        let phone_aspect = phone.height as f32 / phone.width as f32;
+        if (raw_aspect / phone_aspect - 1.0).abs() > CROP_WARN_TOLERANCE {
+            warn(format!(
+                "screenshot {}x{} does not match phone aspect {}x{}; it will be cropped to fit",
+                raw_img.width(),
+                raw_img.height(),
+                phone.width,
+                phone.height
+            ));
+        }
+    }
+
+    // Transparent, image and blurred screenshot backgrounds never draw the palette
+    let uses_palette = !bg_config.transparent
+        && bg_config.image.is_none()
+        && !matches!(bg_config.template, BackgroundTemplate::BlurredScreenshot);
+    if !uses_palette {
+        return;
+    }
+    let palette: Vec<_> = bg_config
+        .colors
+        .iter()
+        .filter_map(|raw| parse_hex_rgba(raw).ok())
+        .collect();
+    let max_contrast = palette
+        .iter()
+        .flat_map(|a| palette.iter().map(move |b| contrast_ratio(*a, *b)))
+        .fold(1.0f32, f32::max);
+    if palette.len() >= 2 && max_contrast < FLAT_PALETTE_CONTRAST {
+        warn(format!(
+            "background colors are nearly identical (max contrast {:.2}); the background will look flat",
+            max_contrast
+        ));
+    }
+}
+
+/// Estimated peak bytes while rendering one scene: the background cached for each distinct
+/// output size, plus the compose buffers of its largest output. `layered_devices` is the
+/// device count when layers are exported, which keeps a set of layers per device, else 0.
+fn estimate_scene_memory(sizes: &[(u32, u32)], layered_devices: usize) -> u64 {
+    let canvas_bytes = |(width, height): (u32, u32)| u64::from(width) * u64::from(height) * 4;
+    let backgrounds: u64 = sizes
+        .iter()
+        .copied()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .map(canvas_bytes)
+        .sum();
+    let device_buffers = DEVICE_LAYER_BUFFERS * layered_devices as u64;
+    let largest = sizes.iter().copied().map(canvas_bytes).max().unwrap_or(0);
+    backgrounds + largest * (COMPOSE_CANVAS_BUFFERS + device_buffers)
+}
+
+/// Warn before rendering when the largest scene's estimated peak exceeds `MEMORY_WARN_BYTES`.
+/// Scenes whose output variants are invalid are skipped; rendering reports them.
+fn check_memory_estimate(scenes: &[SceneConfig], export_layers: bool) -> Option<RunWarning> {
+    let layered_devices = |scene: &SceneConfig| {
+        if export_layers {
+            scene.devices().count()
+        } else {
+            0
+        }
+    };
+    let (scene, sizes, peak) = scenes
+        .iter()
+        .filter_map(|scene| {
+            let sizes: Vec<_> = expand_sizes(scene)
+                .ok()?
+                .iter()
+                .map(|sized| (sized.output.width, sized.output.height))
+                .collect();
+            let peak = estimate_scene_memory(&sizes, layered_devices(scene));
+            Some((scene, sizes, peak))
+        })
+        .max_by_key(|(_, _, peak)| *peak)?;
+    if peak <= MEMORY_WARN_BYTES {
+        return None;
+    }
+
+    let (width, height) = sizes
+        .iter()
+        .copied()
+        .max_by_key(|(width, height)| u64::from(*width) * u64::from(*height))?;
+    let layers = match layered_devices(scene) {
+        0 => String::new(),
+        devices => format!(", layers for {} device(s)", devices),
+    };
+    Some(RunWarning {
+        scene_id: scene.id.clone(),
+        message: format!(
+            "estimated peak render memory is {} MB ({} output size(s) up to {}x{}{}), above the {} MB limit; reduce the output dimensions or variants",
+            peak / (1024 * 1024),
+            sizes.len(),
+            width,
+            height,
+            layers,
+            MEMORY_WARN_BYTES / (1024 * 1024),
+        ),
+    })
+}
+
+/// Split a scene with a headline list into one scene per variant when requested.
+/// Otherwise the scene is rendered once and compose picks a headline from the seed.
+fn expand_variants(scene: &SceneConfig, all_variants: bool) -> Vec<SceneConfig> {
+    let headlines = match &scene.copy {
+        Some(copy) if all_variants && matches!(copy.headline, Headline::Variants(_)) => {
+            copy.headline.variants()
+        }
+        _ => return vec![scene.clone()],
+    };
+
+    let path = Path::new(&scene.output.filename);
+    let stem = path
+        .file_stem()
+        .and_then(|value| value.to_str())
+        .unwrap_or(&scene.output.filename);
+    let ext = path
+        .extension()
+        .and_then(|value| value.to_str())
+        .unwrap_or("png");
+
+    headlines
+        .into_iter()
+        .enumerate()
+        .map(|(index, headline)| {
+            let mut variant = scene.clone();
+            variant.id = format!("{}-v{}", scene.id, index + 1);
+            variant.output.filename = format!("{}-v{}.{}", stem, index + 1, ext);
+            if let Some(copy) = variant.copy.as_mut() {
+                copy.headline = Headline::Single(headline.to_string());
+            }
+            variant
+        })
+        .collect()
+}
+
+/// Split a scene with `output.variants` into one scene per target size, written to
+/// `final/<name>/<filename>`. Device centers follow the canvas while device, text and
+/// spacing sizes scale by the smaller axis ratio so nothing is stretched.
+pub(crate) fn expand_sizes(scene: &SceneConfig) -> Result<Vec<SceneConfig>> {
+    if scene.output.variants.is_empty() {
+        return Ok(vec![scene.clone()]);
+    }
+    if scene.output.width == 0 || scene.output.height == 0 {
+        bail!(
+            "{}: output width and height must be non-zero to scale output variants",
+            scene.id
+        );
+    }
+
+    let mut names = HashSet::new();
+    let mut sized = Vec::with_capacity(scene.output.variants.len());
+    for target in &scene.output.variants {
+        if target.name.is_empty()
+            || target.name == "."
+            || target.name == ".."
+            || target.name.contains(['/', '\\'])
+        {
+            bail!(
+                "{}: output variant name '{}' must be a plain directory name",
+                scene.id,
+                target.name
+            );
+        }
+        if !names.insert(target.name.as_str()) {
+            bail!("{}: duplicate output variant '{}'", scene.id, target.name);
+        }
+        if target.width == 0 || target.height == 0 {
+            bail!(
+                "{}: output variant '{}' has invalid size {}x{}",
+                scene.id,
+                target.name,
+                target.width,
+                target.height
+            );
+        }
+        sized.push(resize_scene(scene, target));
+    }
+    Ok(sized)
+}
+
+fn resize_scene(scene: &SceneConfig, target: &OutputVariant) -> SceneConfig {
+    let scale_x = target.width as f32 / scene.output.width as f32;
+    let scale_y = target.height as f32 / scene.output.height as f32;
+    let scale = scale_x.min(scale_y);
+
+    let mut sized = scene.clone();
+    sized.id = format!("{}-{}", scene.id, target.name);
+    sized.output = OutputConfig {
+        filename: format!("{}/{}", target.name, scene.output.filename),
+        width: target.width,
+        height: target.height,
+        variants: Vec::new(),
+    };
+    for instance in &mut sized.phones {
+        scale_phone(&mut instance.phone, scale_x, scale_y, scale);
+    }
+    if let Some(copy) = sized.copy.as_mut() {
+        scale_copy(copy, scale);
+    }
+    sized
+}
+
+fn scale_phone(phone: &mut PhoneConfig, scale_x: f32, scale_y: f32, scale: f32) {
+    let center_x = (phone.x as f32 + phone.width as f32 / 2.0) * scale_x;
+    let center_y = (phone.y as f32 + phone.height as f32 / 2.0) * scale_y;
+    phone.width = scale_px(phone.width, scale);
+    phone.height = scale_px(phone.height, scale);
+    phone.x = (center_x - phone.width as f32 / 2.0).round().max(0.0) as u32;
+    phone.y = (center_y - phone.height as f32 / 2.0).round().max(0.0) as u32;
+
+    if let CornerRadius::Pixels(px) = phone.corner_radius {
+        phone.corner_radius = CornerRadius::Pixels(scale_px(px, scale));
+    }
+    let padding = &mut phone.screen_padding;
+    for side in [
+        &mut padding.top,
+        &mut padding.right,
+        &mut padding.bottom,
+        &mut padding.left,
+    ] {
+        *side = scale_px(*side, scale);
+    }
+    phone.frame_border_width = scale_px(phone.frame_border_width, scale);
+    phone.shadow_offset_y = scale_offset(phone.shadow_offset_y, scale);
+    phone.shadow_blur = scale_px(phone.shadow_blur, scale);
+    phone.shadow_spread = scale_offset(phone.shadow_spread, scale);
+    phone.glow_radius = scale_px(phone.glow_radius, scale);
+    phone.reflection_gap = scale_px(phone.reflection_gap, scale);
+}
+
+fn scale_copy(copy: &mut CopyConfig, scale: f32) {
+    copy.headline_size *= scale;
+    copy.subheadline_size *= scale;
+    copy.y_offset = scale_offset(copy.y_offset, scale);
+    copy.line_gap = scale_px(copy.line_gap, scale);
+    copy.max_width = copy.max_width.map(|width| scale_px(width, scale));
+    copy.padding = scale_px(copy.padding, scale);
+    copy.stroke_width = scale_px(copy.stroke_width, scale);
+    copy.shadow_offset_x = scale_offset(copy.shadow_offset_x, scale);
+    copy.shadow_offset_y = scale_offset(copy.shadow_offset_y, scale);
+    copy.shadow_blur = scale_px(copy.shadow_blur, scale);
+    copy.text_background_padding = scale_px(copy.text_background_padding, scale);
+    if let Some(logo) = copy.logo.as_mut() {
+        logo.height = scale_px(logo.height, scale);
+        logo.gap = scale_px(logo.gap, scale);
+    }
+}
+
+fn scale_px(value: u32, scale: f32) -> u32 {
+    (value as f32 * scale).round() as u32
+}
+
+fn scale_offset(value: i32, scale: f32) -> i32 {
+    (value as f32 * scale).round() as i32
+}
+
 fn resolve_path(config_dir: &Path, path: &Path) -> PathBuf {
     if path.is_absolute() {
         path.to_path_buf()
@@ -95,7 +1027,7 @@ fn resolve_path(config_dir: &Path, path: &Path) -> PathBuf {
 }
 
 fn extract_auto_palette(image: &DynamicImage, strategy: AutoColorStrategy) -> Vec<String> {
-    let dominant = extract_dominant_colors(image, 4);
+    let dominant = extract_dominant_colors(image, 4, ClusterMethod::Histogram);
     let palette_strategy = match strategy {
         AutoColorStrategy::Monochromatic => PaletteStrategy::Monochromatic,
         AutoColorStrategy::Analogous => PaletteStrategy::Analogous,
@@ -104,3 +1036,435 @@ fn extract_auto_palette(image: &DynamicImage, strategy: AutoColorStrategy) -> Ve
     };
     generate_palette(&dominant, palette_strategy)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_estimate_counts_sizes_and_exported_layers() {
+        let canvas = 1320 * 2868 * 4;
+        // Devices draw straight onto the canvas unless their layers are exported
+        assert_eq!(estimate_scene_memory(&[(1320, 2868)], 0), canvas * 4);
+        assert_eq!(estimate_scene_memory(&[(1320, 2868)], 3), canvas * 13);
+        // Each distinct size keeps its background; the largest output sets the compose cost
+        let small = 660 * 1434 * 4;
+        assert_eq!(
+            estimate_scene_memory(&[(660, 1434), (1320, 2868), (660, 1434)], 0),
+            small + canvas * 4
+        );
+
+        let scene: SceneConfig = serde_yaml::from_str(
+            r#"
+id: poster
+capture: { adapter: file, path: shot.png }
+output:
+  filename: poster.png
+  width: 4000
+  height: 8000
+  variants: [{ name: half, width: 2000, height: 4000 }, { name: full, width: 4000, height: 8000 }]
+background: {}
+phone: { x: 0, y: 0, width: 100, height: 200 }
+phones:
+  - { capture: { adapter: file, path: second.png }, x: 100, y: 0, width: 100, height: 200 }
+"#,
+        )
+        .expect("scene");
+        assert!(check_memory_estimate(std::slice::from_ref(&scene), false).is_none());
+        let warning = check_memory_estimate(std::slice::from_ref(&scene), true).expect("warning");
+        assert_eq!(warning.scene_id, "poster");
+        assert!(
+            warning
+                .message
+                .contains("2 output size(s) up to 4000x8000, layers for 2 device(s)")
+        );
+    }
+
+    #[test]
+    fn output_variants_rescale_the_layout() {
+        let scene: SceneConfig = serde_yaml::from_str(
+            r#"
+id: hero
+capture: { adapter: file, path: shot.png }
+output:
+  filename: hero.png
+  width: 1000
+  height: 2000
+  variants:
+    - { name: "small", width: 500, height: 800 }
+background: {}
+phone: { x: 250, y: 500, width: 500, height: 1000, corner_radius: 80 }
+copy: { headline: Hello, headline_size: 100 }
+"#,
+        )
+        .expect("scene");
+
+        let sized = expand_sizes(&scene).expect("sizes");
+        assert_eq!(sized.len(), 1);
+        let small = &sized[0];
+        assert_eq!(small.id, "hero-small");
+        assert_eq!(small.output.filename, "small/hero.png");
+        assert_eq!((small.output.width, small.output.height), (500, 800));
+        // Height is the tighter axis (0.4); the phone stays centered on the canvas
+        assert_eq!((small.phone().width, small.phone().height), (200, 400));
+        assert_eq!((small.phone().x, small.phone().y), (150, 200));
+        assert_eq!(small.phone().corner_radius, CornerRadius::Pixels(32));
+        assert_eq!(small.copy.as_ref().unwrap().headline_size, 40.0);
+    }
+
+    #[test]
+    fn render_scene_composes_in_memory() {
+        let config: Config = serde_yaml::from_str(
+            r##"
+scenes:
+  - id: hero
+    capture: { adapter: file, path: shot.png }
+    output: { filename: hero.png, width: 120, height: 240 }
+    background: { template: solid, colors: ["#102030"] }
+    phone: { x: 20, y: 40, width: 80, height: 160, corner_radius: 12 }
+"##,
+        )
+        .expect("config");
+        let scene = &config.scenes[0];
+        let shot = DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(
+            40,
+            80,
+            image::Rgba([250, 250, 250, 255]),
+        ));
+
+        let image = render_scene(&config, scene, &[shot], Path::new(".")).expect("render");
+        assert_eq!(image.dimensions(), (120, 240));
+        assert_eq!(*image.get_pixel(2, 2), image::Rgba([16, 32, 48, 255]));
+        assert_eq!(image.get_pixel(60, 120)[0], 250);
+        assert!(render_scene(&config, scene, &[], Path::new(".")).is_err());
+    }
+
+    #[test]
+    fn transparent_background_keeps_alpha_in_the_written_png() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        image::RgbaImage::from_pixel(40, 80, image::Rgba([250, 250, 250, 255]))
+            .save(temp.path().join("shot.png"))
+            .expect("write screenshot");
+        let config_path = temp.path().join("screenforge.yaml");
+        let yaml = r##"
+output_dir: out
+scenes:
+  - id: cutout
+    capture: { adapter: file, path: shot.png }
+    output: { filename: cutout.png, width: 120, height: 240 }
+    background: { transparent: true, colors: ["#101010", "#111111"] }
+    phone: { x: 20, y: 40, width: 80, height: 160, corner_radius: 12 }
+"##;
+        fs::write(&config_path, yaml).expect("write config");
+
+        let summary = run(&config_path, &RunOptions::default()).expect("run");
+        assert!(summary.warnings.is_empty());
+        let written = image::open(temp.path().join("out/final/cutout.png"))
+            .expect("final image")
+            .to_rgba8();
+        assert_eq!(written.get_pixel(2, 2)[3], 0);
+        assert_eq!(
+            *written.get_pixel(60, 120),
+            image::Rgba([250, 250, 250, 255])
+        );
+    }
+
+    #[test]
+    fn phones_only_scenes_export_layers_per_device() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        for (name, color) in [("a.png", [250, 20, 20, 255]), ("b.png", [20, 20, 250, 255])] {
+            image::RgbaImage::from_pixel(40, 80, image::Rgba(color))
+                .save(temp.path().join(name))
+                .expect("write screenshot");
+        }
+        let config_path = temp.path().join("screenforge.yaml");
+        let yaml = r##"
+output_dir: out
+scenes:
+  - id: pair
+    output: { filename: pair.png, width: 240, height: 240 }
+    background: { template: solid, colors: ["#102030"] }
+    phones:
+      - { capture: { adapter: file, path: a.png }, x: 20, y: 40, width: 80, height: 160 }
+      - { capture: { adapter: file, path: b.png }, x: 140, y: 40, width: 80, height: 160 }
+"##;
+        fs::write(&config_path, yaml).expect("write config");
+
+        let layers_dir = temp.path().join("layers");
+        let options = RunOptions {
+            export_layers: Some(layers_dir.clone()),
+            ..RunOptions::default()
+        };
+        let summary = run(&config_path, &options).expect("run");
+
+        // Each device's screenshot lands in its own layer, not on top of the first device's
+        let screenshot = |name: &str| {
+            image::open(layers_dir.join("pair").join(name))
+                .expect("layer")
+                .to_rgba8()
+        };
+        let (first, second) = (
+            screenshot("screenshot.png"),
+            screenshot("screenshot.phone1.png"),
+        );
+        assert_eq!(first.get_pixel(60, 120)[0], 250);
+        assert_eq!(first.get_pixel(180, 120)[3], 0);
+        assert_eq!(second.get_pixel(180, 120)[2], 250);
+        assert_eq!(second.get_pixel(60, 120)[3], 0);
+
+        let manifest = fs::read_to_string(&summary.manifest_path).expect("manifest");
+        let manifest: serde_json::Value = serde_json::from_str(&manifest).expect("json");
+        assert_eq!(
+            manifest[0]["raw_paths"],
+            serde_json::json!(["raw/pair.png", "raw/pair.phone1.png"])
+        );
+        assert!(temp.path().join("out/raw/pair.phone1.png").exists());
+    }
+
+    #[test]
+    fn overlays_resolve_against_the_config_directory() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        image::RgbaImage::from_pixel(40, 80, image::Rgba([250, 250, 250, 255]))
+            .save(temp.path().join("shot.png"))
+            .expect("write screenshot");
+        // Opaque 8px rim around a transparent screen cutout
+        let frame = |color| {
+            image::RgbaImage::from_fn(80, 160, |x, y| {
+                if (8..72).contains(&x) && (8..152).contains(&y) {
+                    image::Rgba([0, 0, 0, 0])
+                } else {
+                    color
+                }
+            })
+        };
+        fs::create_dir_all(temp.path().join("frames")).expect("frames dir");
+        fs::create_dir_all(temp.path().join("assets/frames")).expect("model frames dir");
+        frame(image::Rgba([200, 0, 0, 255]))
+            .save(temp.path().join("frames/red.png"))
+            .expect("write explicit overlay");
+        // Shadows the repository's own assets/frames, so a lookup relative to the working
+        // directory would pick the wrong file
+        frame(image::Rgba([0, 200, 0, 255]))
+            .save(temp.path().join("assets/frames/iphone_17_pro.png"))
+            .expect("write model overlay");
+
+        let config_path = temp.path().join("screenforge.yaml");
+        let yaml = r##"
+output_dir: out
+scenes:
+  - id: explicit
+    capture: { adapter: file, path: shot.png }
+    output: { filename: explicit.png, width: 120, height: 240 }
+    background: { template: solid, colors: ["#102030"] }
+    phone: { x: 20, y: 40, width: 80, height: 160, overlay: frames/red.png }
+  - id: model
+    capture: { adapter: file, path: shot.png }
+    output: { filename: model.png, width: 120, height: 240 }
+    background: { template: solid, colors: ["#102030"] }
+    phone: { model: iphone_17_pro, x: 20, y: 40, width: 80, height: 160 }
+"##;
+        fs::write(&config_path, yaml).expect("write config");
+
+        run(&config_path, &RunOptions::default()).expect("run");
+        for (name, rim) in [
+            ("explicit", image::Rgba([200, 0, 0, 255])),
+            ("model", image::Rgba([0, 200, 0, 255])),
+        ] {
+            let written = image::open(temp.path().join(format!("out/final/{name}.png")))
+                .expect("final image")
+                .to_rgba8();
+            assert_eq!(*written.get_pixel(22, 120), rim, "{name} rim");
+            assert_eq!(
+                *written.get_pixel(60, 120),
+                image::Rgba([250, 250, 250, 255]),
+                "{name} screen"
+            );
+        }
+    }
+
+    /// Two solid-background scenes sharing one screenshot, rendered into `<dir>/out`
+    fn write_two_scene_config(dir: &Path) -> PathBuf {
+        image::RgbaImage::from_pixel(40, 80, image::Rgba([250, 250, 250, 255]))
+            .save(dir.join("shot.png"))
+            .expect("write screenshot");
+        let scene = |id: &str| {
+            format!(
+                r##"
+  - id: {id}
+    capture: {{ adapter: file, path: shot.png }}
+    output: {{ filename: {id}.png, width: 120, height: 240 }}
+    background: {{ template: solid, colors: ["#102030"] }}
+    phone: {{ x: 20, y: 40, width: 80, height: 160 }}"##
+            )
+        };
+        let config_path = dir.join("screenforge.yaml");
+        let yaml = format!("output_dir: out\nscenes:{}{}\n", scene("one"), scene("two"));
+        fs::write(&config_path, yaml).expect("write config");
+        config_path
+    }
+
+    #[test]
+    fn run_reports_progress_per_scene() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let config_path = write_two_scene_config(temp.path());
+
+        let seen = std::cell::RefCell::new(Vec::new());
+        run_with_progress(&config_path, &RunOptions::default(), |progress| {
+            seen.borrow_mut().push(format!(
+                "{}/{} {}",
+                progress.index, progress.total, progress.scene_id
+            ));
+        })
+        .expect("run");
+        assert_eq!(seen.into_inner(), ["1/2 one", "2/2 two"]);
+    }
+
+    #[test]
+    fn reruns_keep_scenes_whose_inputs_did_not_change() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let config_path = write_two_scene_config(temp.path());
+        let unchanged = |options: &RunOptions| run(&config_path, options).expect("run").unchanged;
+        let options = RunOptions::default();
+        assert_eq!(unchanged(&options), 0);
+        assert_eq!(unchanged(&options), 2);
+
+        // A deleted output or an edited scene re-renders just that scene
+        fs::remove_file(temp.path().join("out/final/one.png")).expect("remove output");
+        assert_eq!(unchanged(&options), 1);
+        let yaml = fs::read_to_string(&config_path).expect("read config");
+        fs::write(&config_path, yaml.replacen("#102030", "#203040", 1)).expect("edit config");
+        assert_eq!(unchanged(&options), 1);
+
+        let forced = RunOptions {
+            force: true,
+            ..RunOptions::default()
+        };
+        assert_eq!(unchanged(&forced), 0);
+    }
+
+    #[test]
+    fn since_rerenders_scenes_whose_config_changed() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let config_path = write_two_scene_config(temp.path());
+        let unchanged = |options: &RunOptions| run(&config_path, options).expect("run").unchanged;
+        assert_eq!(unchanged(&RunOptions::default()), 0);
+
+        // A cutoff after every source was written, so only the config can invalidate
+        let since = RunOptions {
+            since: Some(Since::Time(SystemTime::now() + Duration::from_secs(3600))),
+            ..RunOptions::default()
+        };
+        assert_eq!(unchanged(&since), 2);
+        let yaml = fs::read_to_string(&config_path).expect("read config");
+        fs::write(&config_path, yaml.replacen("#102030", "#203040", 1)).expect("edit config");
+        assert_eq!(unchanged(&since), 1);
+        assert_eq!(unchanged(&since), 2);
+    }
+
+    #[test]
+    fn force_rerenders_scenes_kept_by_since() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let config_path = write_two_scene_config(temp.path());
+        run(&config_path, &RunOptions::default()).expect("run");
+
+        let forced = RunOptions {
+            since: Some(Since::Time(SystemTime::now() + Duration::from_secs(3600))),
+            force: true,
+            ..RunOptions::default()
+        };
+        assert_eq!(run(&config_path, &forced).expect("run").unchanged, 0);
+    }
+
+    #[test]
+    fn since_keeps_warnings_for_unchanged_scenes() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let config_path = write_two_scene_config(temp.path());
+        // A square screenshot in a tall phone is cropped, which warns for every scene
+        image::RgbaImage::from_pixel(80, 80, image::Rgba([250, 250, 250, 255]))
+            .save(temp.path().join("shot.png"))
+            .expect("write screenshot");
+        let first = run(&config_path, &RunOptions::default()).expect("run");
+        assert_eq!(first.warnings.len(), 2);
+
+        let since = RunOptions {
+            since: Some(Since::Time(SystemTime::now() + Duration::from_secs(3600))),
+            ..RunOptions::default()
+        };
+        let kept = run(&config_path, &since).expect("run");
+        assert_eq!(kept.unchanged, 2);
+        let messages = |warnings: &[RunWarning]| {
+            warnings
+                .iter()
+                .map(|warning| warning.message.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(messages(&kept.warnings), messages(&first.warnings));
+    }
+
+    #[test]
+    fn zip_packages_final_images_and_the_manifest() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let config_path = write_two_scene_config(temp.path());
+        let zip_path = temp.path().join("dist/shots.zip");
+        let options = RunOptions {
+            zip: Some(zip_path.clone()),
+            ..RunOptions::default()
+        };
+        run(&config_path, &options).expect("run");
+
+        let file = fs::File::open(&zip_path).expect("open zip");
+        let archive = zip::ZipArchive::new(file).expect("read zip");
+        let mut names: Vec<&str> = archive.file_names().collect();
+        names.sort_unstable();
+        assert_eq!(names, ["manifest.json", "one/one.png", "two/two.png"]);
+    }
+
+    #[test]
+    fn alpha_warn_captures_report_a_run_warning() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let config_path = write_two_scene_config(temp.path());
+        let mut shot = image::RgbaImage::from_pixel(40, 80, image::Rgba([250, 250, 250, 255]));
+        shot.put_pixel(0, 0, image::Rgba([250, 250, 250, 0]));
+        shot.save(temp.path().join("shot.png"))
+            .expect("write screenshot");
+        let flattened = run(&config_path, &RunOptions::default()).expect("run");
+        assert!(flattened.warnings.is_empty());
+
+        let yaml = fs::read_to_string(&config_path).expect("read config");
+        let yaml = yaml.replace("path: shot.png", "path: shot.png, alpha: warn");
+        fs::write(&config_path, yaml).expect("edit config");
+        let summary = run(&config_path, &RunOptions::default()).expect("run");
+        assert_eq!(summary.warnings.len(), 2);
+        let message = &summary.warnings[0].message;
+        assert!(message.contains("1 transparent pixel(s)"), "{message}");
+    }
+
+    #[test]
+    fn auto_colors_replace_configured_colors() {
+        let scene: SceneConfig = serde_yaml::from_str(
+            r##"
+id: hero
+capture: { adapter: file, path: shot.png }
+output: { filename: hero.png, width: 100, height: 200 }
+background:
+  colors: ["#000000", "#FFFFFF"]
+  auto_colors: true
+  auto_strategy: monochromatic
+phone: { x: 0, y: 0, width: 100, height: 200 }
+"##,
+        )
+        .expect("scene");
+        let shot = DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(
+            64,
+            64,
+            image::Rgba([200, 40, 40, 255]),
+        ));
+
+        let background = resolve_background(&scene, Path::new("."), &shot);
+        assert_eq!(
+            background.colors,
+            extract_auto_palette(&shot, AutoColorStrategy::Monochromatic)
+        );
+        assert_ne!(background.colors, scene.background.colors);
+    }
+}
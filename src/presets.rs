@@ -0,0 +1,66 @@
+use serde::Deserialize;
+
+/// Canonical App Store screenshot sizes, in portrait pixels
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SizePreset {
+    #[serde(rename = "app-store-6-9")]
+    AppStore6_9,
+    #[serde(rename = "app-store-6-7")]
+    AppStore6_7,
+    #[serde(rename = "app-store-6-5")]
+    AppStore6_5,
+    #[serde(rename = "app-store-6-3")]
+    AppStore6_3,
+    #[serde(rename = "app-store-5-5")]
+    AppStore5_5,
+    #[serde(rename = "ipad-13")]
+    Ipad13,
+    #[serde(rename = "ipad-12-9")]
+    Ipad12_9,
+}
+
+impl SizePreset {
+    pub fn dimensions(self) -> (u32, u32) {
+        match self {
+            Self::AppStore6_9 => (1320, 2868),
+            Self::AppStore6_7 => (1290, 2796),
+            Self::AppStore6_5 => (1284, 2778),
+            Self::AppStore6_3 => (1206, 2622),
+            Self::AppStore5_5 => (1242, 2208),
+            Self::Ipad13 => (2064, 2752),
+            Self::Ipad12_9 => (2048, 2732),
+        }
+    }
+}
+
+/// Pixel size from an optional preset, with explicit `width`/`height` taking precedence
+pub fn resolve_size(
+    size: Option<SizePreset>,
+    width: Option<u32>,
+    height: Option<u32>,
+) -> Result<(u32, u32), String> {
+    let preset = size.map(SizePreset::dimensions);
+    match (
+        width.or(preset.map(|(w, _)| w)),
+        height.or(preset.map(|(_, h)| h)),
+    ) {
+        (Some(width), Some(height)) => Ok((width, height)),
+        _ => {
+            Err("expected `width` and `height`, or a `size` preset like app-store-6-9".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_dimensions_override_the_preset() {
+        let preset = Some(SizePreset::AppStore6_7);
+        assert_eq!(resolve_size(preset, None, None), Ok((1290, 2796)));
+        assert_eq!(resolve_size(preset, Some(1000), None), Ok((1000, 2796)));
+        assert_eq!(resolve_size(None, Some(10), Some(20)), Ok((10, 20)));
+        assert!(resolve_size(None, Some(10), None).is_err());
+    }
+}
@@ -5,28 +5,70 @@ use anyhow::{Context, Result};
 
 pub struct PreviewItem {
     pub scene_id: String,
-    pub raw_rel: String,
+    /// One raw screenshot per device; the slider compares the final image with the first
+    pub raw_rels: Vec<String>,
     pub final_rel: String,
+    /// Configured output size, which may differ from a stale file on disk
+    pub width: u32,
+    pub height: u32,
+    pub phone_model: Option<String>,
+    pub background_template: String,
+    pub headline: Option<String>,
+}
+
+struct FinalStats {
+    width: u32,
+    height: u32,
+    bytes: u64,
 }
 
 pub fn write_index(path: &Path, items: &[PreviewItem]) -> Result<()> {
+    let base_dir = path.parent().unwrap_or(Path::new(""));
     let mut cards = String::new();
+    let mut total_bytes = 0u64;
     for item in items {
+        let stats = final_stats(&base_dir.join(&item.final_rel));
+        let meta = match &stats {
+            Some(stats) => {
+                total_bytes += stats.bytes;
+                format!(
+                    "{}x{} · {}",
+                    stats.width,
+                    stats.height,
+                    format_bytes(stats.bytes)
+                )
+            }
+            None => "size unavailable".to_string(),
+        };
         cards.push_str(&format!(
-            r#"<section class="card">
+            r#"<section class="card" data-scene="{scene}">
   <h2>{scene}</h2>
+  <p class="meta">{meta}</p>
+  {table}
+  <div class="compare" style="--split: 50%">
+    <img class="compare-raw" src="{raw}" alt="raw {scene}" loading="lazy"/>
+    <img class="compare-final" src="{final_img}" alt="final {scene}" loading="lazy"/>
+    <input type="range" min="0" max="100" value="50" aria-label="Final / raw split for {scene}"/>
+  </div>
   <div class="grid">
-    <figure><figcaption>Raw</figcaption><img src="{raw}" alt="raw {scene}" loading="lazy"/></figure>
-    <figure><figcaption>Final</figcaption><img src="{final_img}" alt="final {scene}" loading="lazy"/></figure>
+{raw_figures}    <figure><figcaption>Final</figcaption><img src="{final_img}" alt="final {scene}" loading="lazy"/></figure>
   </div>
 </section>
 "#,
             scene = html_escape(&item.scene_id),
-            raw = html_escape(&item.raw_rel),
+            table = meta_table(item),
+            raw = html_escape(item.raw_rels.first().map_or("", String::as_str)),
+            raw_figures = raw_figures(item),
             final_img = html_escape(&item.final_rel)
         ));
     }
 
+    let summary = format!(
+        "{} image(s) · {} total",
+        items.len(),
+        format_bytes(total_bytes)
+    );
+
     let html = format!(
         r#"<!doctype html>
 <html lang="en">
@@ -35,18 +77,36 @@ pub fn write_index(path: &Path, items: &[PreviewItem]) -> Result<()> {
   <meta name="viewport" content="width=device-width, initial-scale=1" />
   <title>Screenforge Preview</title>
   <style>
+    :root {{
+      --bg: #0c111b;
+      --fg: #e4ebf6;
+      --card: #11192a;
+      --card-border: #263449;
+      --heading: #bfd0ea;
+      --figure-border: #213046;
+      --image-bg: #070b13;
+    }}
+    :root[data-theme="light"] {{
+      --bg: #f4f6fa;
+      --fg: #1a2233;
+      --card: #ffffff;
+      --card-border: #d5dce8;
+      --heading: #3a4a66;
+      --figure-border: #e1e6ef;
+      --image-bg: #eef1f6;
+    }}
     body {{
       margin: 0;
       padding: 24px;
       font-family: ui-sans-serif, -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif;
-      background: #0c111b;
-      color: #e4ebf6;
+      background: var(--bg);
+      color: var(--fg);
     }}
     h1 {{ margin: 0 0 24px 0; font-size: 28px; }}
     .card {{
       margin-bottom: 28px;
-      background: #11192a;
-      border: 1px solid #263449;
+      background: var(--card);
+      border: 1px solid var(--card-border);
       border-radius: 12px;
       padding: 16px;
     }}
@@ -55,8 +115,33 @@ pub fn write_index(path: &Path, items: &[PreviewItem]) -> Result<()> {
       font-size: 16px;
       letter-spacing: 0.04em;
       text-transform: uppercase;
-      color: #bfd0ea;
+      color: var(--heading);
+    }}
+    /* Filter box and theme toggle need JS, so they stay hidden without it */
+    .toolbar {{ display: none; gap: 12px; margin: 0 0 24px 0; }}
+    .js .toolbar {{ display: flex; }}
+    .toolbar input,
+    .toolbar button {{
+      font: inherit;
+      font-size: 14px;
+      padding: 6px 10px;
+      border-radius: 8px;
+      border: 1px solid var(--card-border);
+      background: var(--card);
+      color: var(--fg);
     }}
+    .toolbar input {{ flex: 1; max-width: 320px; }}
+    .toolbar button {{ cursor: pointer; }}
+    .summary {{ margin: -12px 0 24px 0; font-size: 14px; opacity: 0.8; }}
+    .meta {{ margin: -6px 0 12px 0; font-size: 13px; opacity: 0.7; font-variant-numeric: tabular-nums; }}
+    .meta-table {{ margin: 0 0 14px 0; font-size: 13px; border-collapse: collapse; }}
+    .meta-table th {{
+      padding: 2px 14px 2px 0;
+      text-align: left;
+      font-weight: 500;
+      opacity: 0.6;
+    }}
+    .meta-table td {{ padding: 2px 0; }}
     .grid {{
       display: grid;
       grid-template-columns: repeat(auto-fit, minmax(260px, 1fr));
@@ -64,10 +149,10 @@ pub fn write_index(path: &Path, items: &[PreviewItem]) -> Result<()> {
     }}
     figure {{
       margin: 0;
-      background: #0c111b;
+      background: var(--bg);
       border-radius: 10px;
       padding: 10px;
-      border: 1px solid #213046;
+      border: 1px solid var(--figure-border);
     }}
     figcaption {{
       margin-bottom: 10px;
@@ -81,13 +166,84 @@ pub fn write_index(path: &Path, items: &[PreviewItem]) -> Result<()> {
       height: auto;
       border-radius: 8px;
       display: block;
-      background: #070b13;
+      background: var(--image-bg);
+    }}
+    /* Before/after slider: final (left of the split) over raw; shown only when JS runs */
+    .compare {{
+      display: none;
+      position: relative;
+      max-width: 420px;
+      margin: 0 auto 16px auto;
+    }}
+    .js .compare {{ display: block; }}
+    .compare .compare-raw {{
+      position: absolute;
+      inset: 0;
+      height: 100%;
+      object-fit: contain;
+    }}
+    .compare .compare-final {{
+      position: relative;
+      clip-path: inset(0 calc(100% - var(--split)) 0 0);
+    }}
+    .compare::after {{
+      content: "";
+      position: absolute;
+      top: 0;
+      bottom: 0;
+      left: var(--split);
+      width: 2px;
+      margin-left: -1px;
+      background: var(--fg);
+      pointer-events: none;
+    }}
+    .compare input {{
+      position: absolute;
+      inset: 0;
+      width: 100%;
+      height: 100%;
+      margin: 0;
+      opacity: 0;
+      cursor: ew-resize;
     }}
   </style>
 </head>
 <body>
   <h1>Screenforge Preview</h1>
+  <p class="summary">{summary}</p>
+  <div class="toolbar">
+    <input id="filter" type="search" placeholder="Filter scenes by id" aria-label="Filter scenes by id"/>
+    <button id="theme" type="button">Toggle theme</button>
+  </div>
   {cards}
+  <script>
+    var root = document.documentElement;
+    root.classList.add("js");
+    // localStorage throws for file:// pages in some browsers; the theme then just isn't kept
+    function storedTheme() {{
+      try {{ return localStorage.getItem("screenforge-theme"); }} catch (e) {{ return null; }}
+    }}
+    function setTheme(theme) {{
+      root.setAttribute("data-theme", theme);
+      try {{ localStorage.setItem("screenforge-theme", theme); }} catch (e) {{}}
+    }}
+    root.setAttribute("data-theme", storedTheme() === "light" ? "light" : "dark");
+    document.getElementById("theme").addEventListener("click", function () {{
+      setTheme(root.getAttribute("data-theme") === "light" ? "dark" : "light");
+    }});
+    document.getElementById("filter").addEventListener("input", function (event) {{
+      var query = event.target.value.trim().toLowerCase();
+      document.querySelectorAll(".card").forEach(function (card) {{
+        var id = card.getAttribute("data-scene").toLowerCase();
+        card.hidden = query !== "" && id.indexOf(query) === -1;
+      }});
+    }});
+    document.querySelectorAll(".compare input").forEach(function (slider) {{
+      slider.addEventListener("input", function () {{
+        slider.parentElement.style.setProperty("--split", slider.value + "%");
+      }});
+    }});
+  </script>
 </body>
 </html>"#
     );
@@ -100,6 +256,70 @@ pub fn write_index(path: &Path, items: &[PreviewItem]) -> Result<()> {
     Ok(())
 }
 
+/// A grid figure per raw screenshot, numbered when the scene has several devices
+fn raw_figures(item: &PreviewItem) -> String {
+    let scene = html_escape(&item.scene_id);
+    item.raw_rels
+        .iter()
+        .enumerate()
+        .map(|(index, raw)| {
+            let caption = match item.raw_rels.len() {
+                1 => "Raw".to_string(),
+                _ => format!("Raw {}", index + 1),
+            };
+            format!(
+                "    <figure><figcaption>{caption}</figcaption><img src=\"{raw}\" alt=\"raw {scene}\" loading=\"lazy\"/></figure>\n",
+                raw = html_escape(raw),
+            )
+        })
+        .collect()
+}
+
+fn meta_table(item: &PreviewItem) -> String {
+    let rows = [
+        ("Size", format!("{}x{}", item.width, item.height)),
+        (
+            "Phone",
+            item.phone_model
+                .clone()
+                .unwrap_or_else(|| "generic".to_string()),
+        ),
+        ("Background", item.background_template.clone()),
+        (
+            "Headline",
+            item.headline.clone().unwrap_or_else(|| "—".to_string()),
+        ),
+    ];
+    let rows: String = rows
+        .iter()
+        .map(|(label, value)| format!("<tr><th>{label}</th><td>{}</td></tr>", html_escape(value)))
+        .collect();
+    format!(r#"<table class="meta-table">{rows}</table>"#)
+}
+
+fn final_stats(path: &Path) -> Option<FinalStats> {
+    let bytes = fs::metadata(path).ok()?.len();
+    let (width, height) = image::image_dimensions(path).ok()?;
+    Some(FinalStats {
+        width,
+        height,
+        bytes,
+    })
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    let value = bytes as f64;
+    if value >= MB {
+        format!("{:.1} MB", value / MB)
+    } else if value >= KB {
+        format!("{:.0} KB", value / KB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
 fn html_escape(input: &str) -> String {
     input
         .replace('&', "&amp;")
@@ -108,3 +328,90 @@ fn html_escape(input: &str) -> String {
         .replace('"', "&quot;")
         .replace('\'', "&#39;")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_byte_sizes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(300 * 1024), "300 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 + 512 * 1024), "5.5 MB");
+    }
+
+    #[test]
+    fn cards_get_a_comparison_slider_and_keep_the_grid() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("index.html");
+        let items = [PreviewItem {
+            scene_id: "hero".to_string(),
+            raw_rels: vec![
+                "raw/hero.png".to_string(),
+                "raw/hero.phone1.png".to_string(),
+            ],
+            final_rel: "final/hero.png".to_string(),
+            width: 1290,
+            height: 2796,
+            phone_model: Some("iphone_17_pro".to_string()),
+            background_template: "mesh".to_string(),
+            headline: Some("Fast & <simple>".to_string()),
+        }];
+        write_index(&path, &items).expect("write index");
+
+        let html = fs::read_to_string(&path).expect("read index");
+        assert!(html.contains(r#"<img class="compare-final" src="final/hero.png""#));
+        assert!(html.contains(r#"<input type="range""#));
+        assert!(html.contains(r#"<div class="grid">"#));
+        // The slider uses the first device; the grid shows every device's capture
+        assert!(html.contains(r#"<img class="compare-raw" src="raw/hero.png""#));
+        assert!(html.contains(r#"<figcaption>Raw 2</figcaption><img src="raw/hero.phone1.png""#));
+    }
+
+    #[test]
+    fn page_has_a_filter_box_and_theme_toggle() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("index.html");
+        let items = [PreviewItem {
+            scene_id: "hero \"one\"".to_string(),
+            raw_rels: vec!["raw/hero.png".to_string()],
+            final_rel: "final/hero.png".to_string(),
+            width: 1290,
+            height: 2796,
+            phone_model: None,
+            background_template: "mesh".to_string(),
+            headline: None,
+        }];
+        write_index(&path, &items).expect("write index");
+
+        let html = fs::read_to_string(&path).expect("read index");
+        assert!(html.contains(r#"<section class="card" data-scene="hero &quot;one&quot;">"#));
+        assert!(html.contains(r#"<input id="filter""#));
+        assert!(html.contains(r#":root[data-theme="light"]"#));
+        assert!(html.contains("localStorage"));
+        assert!(!html.contains("<link") && !html.contains("src=\"http"));
+    }
+
+    #[test]
+    fn cards_list_scene_metadata() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("index.html");
+        let items = [PreviewItem {
+            scene_id: "hero".to_string(),
+            raw_rels: vec!["raw/hero.png".to_string()],
+            final_rel: "final/hero.png".to_string(),
+            width: 1290,
+            height: 2796,
+            phone_model: None,
+            background_template: "stripes".to_string(),
+            headline: Some("Fast & <simple>".to_string()),
+        }];
+        write_index(&path, &items).expect("write index");
+
+        let html = fs::read_to_string(&path).expect("read index");
+        assert!(html.contains("<tr><th>Size</th><td>1290x2796</td></tr>"));
+        assert!(html.contains("<tr><th>Phone</th><td>generic</td></tr>"));
+        assert!(html.contains("<tr><th>Background</th><td>stripes</td></tr>"));
+        assert!(html.contains("<td>Fast &amp; &lt;simple&gt;</td>"));
+    }
+}
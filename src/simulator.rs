@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result, bail};
+use log::info;
 use serde::{Deserialize, Serialize};
 
 use crate::config::PhoneModel;
@@ -167,6 +170,69 @@ pub fn find_booted_simulators() -> Result<Vec<Simulator>> {
     Ok(simulators.into_iter().filter(|s| s.is_booted()).collect())
 }
 
+/// Boot the simulator matching `query` (any state) and wait until simctl reports it as
+/// Booted. Already booted simulators are returned as-is.
+pub fn boot_simulator(query: &str, timeout: Duration) -> Result<Simulator> {
+    const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    let simulator = find_simulator(query)?;
+    if simulator.is_booted() {
+        return Ok(simulator);
+    }
+
+    // A simulator that is already starting up only needs the wait
+    if simulator.state != "Booting" {
+        info!("booting {} ({})", simulator.name, simulator.udid);
+        let output = Command::new("xcrun")
+            .args(["simctl", "boot", &simulator.udid])
+            .output()
+            .context("failed to execute xcrun simctl boot")?;
+        if !output.status.success() {
+            bail!(
+                "simctl boot failed for '{}': {}",
+                simulator.name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+    }
+
+    let started = Instant::now();
+    loop {
+        if let Some(current) = list_simulators()?
+            .into_iter()
+            .find(|s| s.udid == simulator.udid)
+            && current.is_booted()
+        {
+            return Ok(current);
+        }
+        if started.elapsed() >= timeout {
+            bail!(
+                "timed out after {}s waiting for simulator '{}' to boot",
+                timeout.as_secs(),
+                simulator.name
+            );
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Open `url` (typically an app deep link) in a booted simulator
+pub fn open_url(simulator: &Simulator, url: &str) -> Result<()> {
+    info!("opening {} in {}", url, simulator.name);
+    let output = Command::new("xcrun")
+        .args(["simctl", "openurl", &simulator.udid, url])
+        .output()
+        .context("failed to execute xcrun simctl openurl")?;
+    if !output.status.success() {
+        bail!(
+            "simctl openurl failed for '{}': {}",
+            simulator.name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
 /// Map device type identifier to PhoneModel
 fn detect_phone_model(device_type: &str) -> Option<PhoneModel> {
     // device_type looks like: com.apple.CoreSimulator.SimDeviceType.iPhone-17-Pro
@@ -175,6 +241,11 @@ fn detect_phone_model(device_type: &str) -> Option<PhoneModel> {
     match suffix {
         "iPhone-17-Pro" => Some(PhoneModel::Iphone17Pro),
         "iPhone-17-Pro-Max" => Some(PhoneModel::Iphone17ProMax),
+        // iPad identifiers carry chip and memory suffixes, e.g. iPad-Pro-11-inch-M4-8GB
+        _ if suffix.starts_with("iPad-Pro-11-inch") => Some(PhoneModel::IpadPro11),
+        _ if suffix.starts_with("iPad-Pro-13-inch") || suffix.starts_with("iPad-Pro-12-9-inch") => {
+            Some(PhoneModel::IpadPro13)
+        }
         _ => None,
     }
 }
@@ -193,6 +264,14 @@ mod tests {
             detect_phone_model("com.apple.CoreSimulator.SimDeviceType.iPhone-17-Pro-Max"),
             Some(PhoneModel::Iphone17ProMax)
         );
+        assert_eq!(
+            detect_phone_model("com.apple.CoreSimulator.SimDeviceType.iPad-Pro-11-inch-M4-8GB"),
+            Some(PhoneModel::IpadPro11)
+        );
+        assert_eq!(
+            detect_phone_model("com.apple.CoreSimulator.SimDeviceType.iPad-Pro-13-inch-M4-16GB"),
+            Some(PhoneModel::IpadPro13)
+        );
         assert_eq!(
             detect_phone_model("com.apple.CoreSimulator.SimDeviceType.iPhone-15-Pro"),
             None
@@ -1,26 +1,33 @@
+use std::collections::HashMap;
 use std::fs;
+use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
 use std::thread;
 use std::time::Duration;
 
 use anyhow::{Context, Result, bail};
-use serde::Serialize;
+use image::{DynamicImage, ImageFormat, Rgb, RgbImage, RgbaImage};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
 
-use crate::background::render_background;
-use crate::compose::compose_scene;
+use crate::background::render_scene_background;
+use crate::compose::{compose_layers, compose_scene, draw_qr_code};
 use crate::config::{
-    BackgroundConfig, BackgroundTemplate, CaptureConfig, CopyConfig, Insets, OutputConfig,
-    PhoneConfig, PhoneModel, SceneConfig,
+    AlphaMode, BackgroundConfig, BackgroundTemplate, CaptureConfig, CopyConfig, Corner,
+    CornerRadius, Headline, Insets, Orientation, OutputConfig, PhoneConfig, PhoneInstance,
+    PhoneModel, SceneConfig, TextPosition,
 };
-use crate::palette::{PaletteStrategy, extract_dominant_colors, generate_palette};
-use crate::simulator::{find_booted_simulators, find_simulator};
+use crate::palette::{ClusterMethod, PaletteStrategy, extract_dominant_colors, generate_palette};
+use crate::simulator::{Simulator, find_booted_simulators, find_simulator, open_url};
 
 static FRAME_IPHONE_17_PRO: &[u8] = include_bytes!("../assets/frames/iphone_17_pro.png");
 static FRAME_IPHONE_17_PRO_MAX: &[u8] = include_bytes!("../assets/frames/iphone_17_pro_max.png");
 
 /// Configuration for a snap operation, loaded from YAML preset or CLI flags
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SnapConfig {
     /// Output dimensions (default: App Store 6.5/6.7" = 1284x2778)
     pub width: u32,
@@ -31,6 +38,10 @@ pub struct SnapConfig {
     pub phone_y: Option<u32>,
     pub phone_width: Option<u32>,
     pub phone_height: Option<u32>,
+    /// Auto-layout phone width as a fraction of the output width
+    pub phone_scale: f32,
+    /// Auto-layout downward shift of the phone as a fraction of the output height
+    pub phone_bias_y: f32,
 
     /// Background settings
     pub background_template: BackgroundTemplate,
@@ -42,12 +53,38 @@ pub struct SnapConfig {
     /// Optional copy/text
     pub headline: Option<String>,
     pub subheadline: Option<String>,
+    /// Headline and subheadline font sizes in pixels
+    pub headline_size: f32,
+    pub subheadline_size: f32,
+    /// Where the copy sits; the auto layout leaves room for it above or below the phone
+    pub text_position: TextPosition,
 
     /// Settle time before capture (ms)
     pub settle_ms: u64,
+    /// Keep capturing `settle_ms` apart until two consecutive screenshots match
+    pub settle_stable: bool,
+    /// Captures to try before giving up on a stable screen
+    pub settle_attempts: u32,
+    /// Percentage of pixels allowed to differ between captures that count as stable
+    pub settle_tolerance: f32,
+    /// URL (e.g. an app deep link) opened in the simulator before settling
+    pub open_url: Option<String>,
+    /// Region of the raw screenshot to keep, applied before framing
+    pub crop: Option<CropRect>,
 
     /// Frame overlay path (optional)
     pub overlay: Option<PathBuf>,
+
+    /// Model used only when detection fails and no explicit model is given
+    pub fallback_model: Option<PhoneModel>,
+
+    /// QR code payload (e.g. App Store link) composited in a corner
+    pub qr_url: Option<String>,
+    pub qr_position: Corner,
+    pub qr_size: u32,
+
+    /// Directory for per-layer PNG export (optional)
+    pub export_layers: Option<PathBuf>,
 }
 
 impl Default for SnapConfig {
@@ -60,6 +97,8 @@ impl Default for SnapConfig {
             phone_y: None,
             phone_width: None,
             phone_height: None,
+            phone_scale: 0.73,
+            phone_bias_y: 0.05,
             background_template: BackgroundTemplate::Mesh,
             background_seed: 42,
             background_colors: vec![
@@ -72,9 +111,209 @@ impl Default for SnapConfig {
             auto_strategy: PaletteStrategy::Analogous,
             headline: None,
             subheadline: None,
+            headline_size: 120.0,
+            subheadline_size: 56.0,
+            text_position: TextPosition::AbovePhone,
             settle_ms: 500,
+            settle_stable: false,
+            settle_attempts: 10,
+            settle_tolerance: 0.0,
+            open_url: None,
+            crop: None,
             overlay: None,
+            fallback_model: None,
+            qr_url: None,
+            qr_position: Corner::BottomRight,
+            qr_size: 220,
+            export_layers: None,
+        }
+    }
+}
+
+impl SnapConfig {
+    pub fn settle(&self) -> Settle {
+        Settle {
+            ms: self.settle_ms,
+            stable: self.settle_stable,
+            attempts: self.settle_attempts,
+            tolerance: self.settle_tolerance,
+            open_url: self.open_url.clone(),
+        }
+    }
+
+    /// Load a `snap --preset` file. Missing keys keep their defaults and relative paths
+    /// resolve against the preset's directory.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("failed to read snap preset {}", path.display()))?;
+        let mut config: Self = serde_yaml::from_str(&raw)
+            .with_context(|| format!("failed to parse snap preset {}", path.display()))?;
+
+        let preset_dir = path.parent().unwrap_or(Path::new(""));
+        for dir in [&mut config.overlay, &mut config.export_layers]
+            .into_iter()
+            .flatten()
+        {
+            if dir.is_relative() {
+                *dir = preset_dir.join(&*dir);
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Pixel region of a raw screenshot, `x,y,width,height` on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CropRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl CropRect {
+    /// Cut this region out of `image`, failing when it does not fit inside it
+    pub fn apply(&self, image: &DynamicImage) -> Result<DynamicImage> {
+        let fits_x = self
+            .x
+            .checked_add(self.width)
+            .is_some_and(|end| end <= image.width());
+        let fits_y = self
+            .y
+            .checked_add(self.height)
+            .is_some_and(|end| end <= image.height());
+        if self.width == 0 || self.height == 0 || !fits_x || !fits_y {
+            bail!(
+                "crop {}x{}+{}+{} is outside the {}x{} screenshot",
+                self.width,
+                self.height,
+                self.x,
+                self.y,
+                image.width(),
+                image.height()
+            );
+        }
+        Ok(image.crop_imm(self.x, self.y, self.width, self.height))
+    }
+}
+
+impl FromStr for CropRect {
+    type Err = String;
+
+    fn from_str(raw: &str) -> std::result::Result<Self, Self::Err> {
+        let values = raw
+            .split(',')
+            .map(|value| value.trim().parse::<u32>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|_| format!("invalid crop '{}'; expected x,y,width,height", raw))?;
+        let [x, y, width, height] = values[..] else {
+            return Err(format!("invalid crop '{}'; expected x,y,width,height", raw));
+        };
+        Ok(Self {
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+}
+
+/// One `snap --scene` spec: `sim=<query>;output=<path>` plus optional `headline`,
+/// `subheadline`, `seed`, `background`, `colors` (comma-separated) and `model` keys.
+/// Keys that are left out fall back to the regular snap flags.
+#[derive(Debug, Clone)]
+pub struct InlineScene {
+    pub simulator: String,
+    pub output: PathBuf,
+    pub headline: Option<String>,
+    pub subheadline: Option<String>,
+    pub seed: Option<u64>,
+    pub background: Option<BackgroundTemplate>,
+    pub colors: Option<Vec<String>>,
+    pub model: Option<PhoneModel>,
+}
+
+impl InlineScene {
+    /// Layer this scene's overrides on top of the flag-derived config
+    pub fn apply(&self, base: &SnapConfig) -> SnapConfig {
+        let mut config = base.clone();
+        if let Some(headline) = &self.headline {
+            config.headline = Some(headline.clone());
+        }
+        if let Some(subheadline) = &self.subheadline {
+            config.subheadline = Some(subheadline.clone());
+        }
+        if let Some(seed) = self.seed {
+            config.background_seed = seed;
+        }
+        if let Some(background) = self.background {
+            config.background_template = background;
+        }
+        if let Some(colors) = &self.colors {
+            config.background_colors = colors.clone();
         }
+        config
+    }
+}
+
+impl FromStr for InlineScene {
+    type Err = String;
+
+    fn from_str(raw: &str) -> std::result::Result<Self, Self::Err> {
+        let mut simulator = None;
+        let mut output = None;
+        let mut scene = InlineScene {
+            simulator: String::new(),
+            output: PathBuf::new(),
+            headline: None,
+            subheadline: None,
+            seed: None,
+            background: None,
+            colors: None,
+            model: None,
+        };
+
+        for pair in raw
+            .split(';')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+        {
+            let Some((key, value)) = pair.split_once('=') else {
+                return Err(format!("expected key=value, got '{}'", pair));
+            };
+            let value = value.trim();
+            match key.trim() {
+                "sim" | "simulator" => simulator = Some(value.to_string()),
+                "output" => output = Some(PathBuf::from(value)),
+                "headline" => scene.headline = Some(value.to_string()),
+                "subheadline" => scene.subheadline = Some(value.to_string()),
+                "seed" => {
+                    let seed = value
+                        .parse()
+                        .map_err(|_| format!("invalid seed '{}'", value))?;
+                    scene.seed = Some(seed);
+                }
+                "background" => {
+                    let template = serde_yaml::from_str(value)
+                        .map_err(|_| format!("unknown background template '{}'", value))?;
+                    scene.background = Some(template);
+                }
+                "colors" => {
+                    scene.colors = Some(value.split(',').map(|c| c.trim().to_string()).collect())
+                }
+                "model" => {
+                    let model = serde_yaml::from_str(value)
+                        .map_err(|_| format!("unknown phone model '{}'", value))?;
+                    scene.model = Some(model);
+                }
+                other => return Err(format!("unknown scene key '{}'", other)),
+            }
+        }
+
+        scene.simulator = simulator.ok_or("scene is missing 'sim=<simulator>'")?;
+        scene.output = output.ok_or("scene is missing 'output=<path>'")?;
+        Ok(scene)
     }
 }
 
@@ -86,8 +325,12 @@ pub struct SnapResult {
     pub simulator_udid: String,
     pub device_model: Option<String>,
     pub output_path: String,
+    /// Every path written, in `--output` order (the first equals `output_path`)
+    pub output_paths: Vec<String>,
     pub raw_path: Option<String>,
     pub dimensions: Dimensions,
+    /// Problems that did not stop the capture; `--fail-on-warning` turns them into an error
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -96,43 +339,54 @@ pub struct Dimensions {
     pub height: u32,
 }
 
+/// How long to wait before the screenshot that gets kept
+#[derive(Debug, Clone)]
+pub struct Settle {
+    pub ms: u64,
+    /// Poll until two consecutive captures match instead of sleeping once
+    pub stable: bool,
+    pub attempts: u32,
+    /// Percentage of pixels that may differ between matching captures
+    pub tolerance: f32,
+    /// Opened first so the wait covers the screen it navigates to
+    pub open_url: Option<String>,
+}
+
 /// Take a raw screenshot from a simulator without framing
-pub fn snap_raw(query: &str, output_path: &Path, settle_ms: u64) -> Result<SnapResult> {
+pub fn snap_raw(
+    query: &str,
+    output_paths: &[PathBuf],
+    settle: Settle,
+    crop: Option<CropRect>,
+) -> Result<SnapResult> {
+    let Some(output_path) = output_paths.first() else {
+        bail!("at least one output path is required");
+    };
+
     let simulator = find_simulator(query)?;
 
     if !simulator.is_booted() {
         bail!(
-            "simulator '{}' is not booted (state: {}). Boot it first with:\n  xcrun simctl boot '{}'",
+            "simulator '{}' is not booted (state: {}). Boot it first with:\n  xcrun simctl boot '{}'\nor pass --boot '{}' to snap",
             simulator.name,
             simulator.state,
+            simulator.udid,
             simulator.udid
         );
     }
 
-    // Settle time
-    if settle_ms > 0 {
-        thread::sleep(Duration::from_millis(settle_ms));
-    }
-
-    // Take screenshot (suppress simctl debug output)
-    let output = Command::new("xcrun")
-        .args(["simctl", "io", &simulator.udid, "screenshot"])
-        .arg(output_path)
-        .output()
-        .context("failed to execute xcrun simctl")?;
+    // simctl always writes PNG, so capture to a temp file and encode each output by extension
+    let raw_path = std::env::temp_dir().join(format!("screenforge_raw_{}.png", simulator.udid));
+    capture_settled(&simulator, &raw_path, &settle)?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!(
-            "simctl screenshot failed for simulator '{}': {}",
-            simulator.name,
-            stderr.trim()
-        );
+    let mut img = image::open(&raw_path)
+        .with_context(|| format!("failed to open screenshot {}", raw_path.display()))?;
+    let _ = std::fs::remove_file(&raw_path);
+    if let Some(crop) = crop {
+        img = crop.apply(&img)?;
     }
 
-    // Get image dimensions
-    let img = image::open(output_path)
-        .with_context(|| format!("failed to open screenshot {}", output_path.display()))?;
+    save_outputs(&img.to_rgba8(), output_paths)?;
 
     Ok(SnapResult {
         success: true,
@@ -140,47 +394,274 @@ pub fn snap_raw(query: &str, output_path: &Path, settle_ms: u64) -> Result<SnapR
         simulator_udid: simulator.udid,
         device_model: simulator.phone_model.map(|m| format!("{:?}", m)),
         output_path: output_path.to_string_lossy().to_string(),
+        output_paths: display_paths(output_paths),
         raw_path: None,
         dimensions: Dimensions {
             width: img.width(),
             height: img.height(),
         },
+        warnings: Vec::new(),
+    })
+}
+
+/// Result of `snap --record`, suitable for JSON output
+#[derive(Debug, Serialize)]
+pub struct RecordResult {
+    pub success: bool,
+    pub simulator_name: String,
+    pub simulator_udid: String,
+    pub device_model: Option<String>,
+    pub output_path: String,
+    pub duration_secs: u64,
+}
+
+/// Record `duration` of a simulator's screen to `output_path` as H.264 video, for App Store
+/// app previews. The video is kept at the simulator's resolution and is not framed.
+/// `settle.open_url` is opened and `settle.ms` waited out before recording starts.
+pub fn record_video(
+    query: &str,
+    output_path: &Path,
+    duration: Duration,
+    settle: &Settle,
+) -> Result<RecordResult> {
+    if duration.is_zero() {
+        bail!("recording duration must be at least one second");
+    }
+    let simulator = find_simulator(query)?;
+    if !simulator.is_booted() {
+        bail!(
+            "simulator '{}' is not booted (state: {}). Boot it first with:\n  xcrun simctl boot '{}'\nor pass --boot '{}' to snap",
+            simulator.name,
+            simulator.state,
+            simulator.udid,
+            simulator.udid
+        );
+    }
+
+    if let Some(url) = &settle.open_url {
+        open_url(&simulator, url)?;
+    }
+    if settle.ms > 0 {
+        thread::sleep(Duration::from_millis(settle.ms));
+    }
+
+    let mut child = Command::new("xcrun")
+        .args([
+            "simctl",
+            "io",
+            &simulator.udid,
+            "recordVideo",
+            "--codec=h264",
+            "--force",
+        ])
+        .arg(output_path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to execute xcrun simctl recordVideo")?;
+
+    // simctl reports when frames start flowing; the clip is timed from there
+    let stderr = child
+        .stderr
+        .take()
+        .context("simctl recordVideo has no stderr")?;
+    let mut lines = BufReader::new(stderr).lines();
+    let mut log = Vec::new();
+    let mut started = false;
+    for line in lines.by_ref().map_while(|line| line.ok()) {
+        started = line.contains("Recording started");
+        log.push(line);
+        if started {
+            break;
+        }
+    }
+    if !started {
+        let _ = child.wait();
+        bail!(
+            "simctl recordVideo failed for simulator '{}': {}",
+            simulator.name,
+            log.join("\n").trim()
+        );
+    }
+
+    info!(
+        "recording {} for {}s",
+        simulator.name,
+        duration.as_secs_f32()
+    );
+    thread::sleep(duration);
+
+    // recordVideo only finalizes the file on SIGINT, as after Ctrl-C in a terminal
+    let interrupted = Command::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()
+        .context("failed to stop simctl recordVideo")?;
+    if !interrupted.success() {
+        let _ = child.kill();
+        bail!("failed to stop recording simulator '{}'", simulator.name);
+    }
+    log.extend(lines.map_while(|line| line.ok()));
+    let status = child
+        .wait()
+        .context("failed waiting for simctl recordVideo")?;
+    if !status.success() && !output_path.is_file() {
+        bail!(
+            "simctl recordVideo failed for simulator '{}': {}",
+            simulator.name,
+            log.join("\n").trim()
+        );
+    }
+
+    Ok(RecordResult {
+        success: true,
+        simulator_name: simulator.name,
+        simulator_udid: simulator.udid,
+        device_model: simulator.phone_model.map(|m| format!("{:?}", m)),
+        output_path: output_path.to_string_lossy().to_string(),
+        duration_secs: duration.as_secs(),
     })
 }
 
+/// Where `snap --record` writes: `.mp4` and `.mov` paths are kept, any other extension (such
+/// as the default `snap_output.png`) becomes `.mp4`
+pub fn video_output_path(path: &Path) -> PathBuf {
+    let is_video = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("mp4") || ext.eq_ignore_ascii_case("mov"));
+    if is_video {
+        path.to_path_buf()
+    } else {
+        path.with_extension("mp4")
+    }
+}
+
 /// Take a screenshot and frame it with device chrome
 pub fn snap_framed(
     query: &str,
-    output_path: &Path,
+    output_paths: &[PathBuf],
     config: &SnapConfig,
     model_override: Option<PhoneModel>,
 ) -> Result<SnapResult> {
+    let Some(output_path) = output_paths.first() else {
+        bail!("at least one output path is required");
+    };
     let simulator = find_simulator(query)?;
 
     if !simulator.is_booted() {
         bail!(
-            "simulator '{}' is not booted (state: {}). Boot it first with:\n  xcrun simctl boot '{}'",
+            "simulator '{}' is not booted (state: {}). Boot it first with:\n  xcrun simctl boot '{}'\nor pass --boot '{}' to snap",
             simulator.name,
             simulator.state,
+            simulator.udid,
             simulator.udid
         );
     }
 
-    // Determine phone model
+    // Determine phone model: explicit override, then detection, then the fallback
+    let mut warnings = Vec::new();
     let phone_model = model_override.or(simulator.phone_model);
+    let phone_model = match (phone_model, config.fallback_model) {
+        (None, Some(fallback)) => {
+            info!(
+                "could not detect a supported phone model for '{}' ({}); using fallback {:?}",
+                simulator.name, simulator.device_type, fallback
+            );
+            Some(fallback)
+        }
+        (None, None) => {
+            let message = format!(
+                "could not detect a supported phone model for '{}' ({}); framing without a model preset (use --model or --fallback-model)",
+                simulator.name, simulator.device_type
+            );
+            warn!("{}", message);
+            warnings.push(message);
+            None
+        }
+        (detected, _) => detected,
+    };
 
     // Create temp file for raw screenshot
     let raw_path = std::env::temp_dir().join(format!("screenforge_snap_{}.png", simulator.udid));
 
-    // Settle time
-    if config.settle_ms > 0 {
-        thread::sleep(Duration::from_millis(config.settle_ms));
+    capture_settled(&simulator, &raw_path, &config.settle())?;
+
+    // Load raw screenshot
+    let mut raw_img = image::open(&raw_path)
+        .with_context(|| format!("failed to open raw screenshot {}", raw_path.display()))?;
+    if let Some(crop) = &config.crop {
+        raw_img = crop.apply(&raw_img)?;
     }
 
-    // Take screenshot (suppress simctl debug output)
+    let output_name = output_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let final_img = frame_screenshot(&raw_img, &raw_path, &output_name, config, phone_model)?;
+
+    // Save output once per requested path, encoded by extension
+    save_outputs(&final_img, output_paths)?;
+
+    Ok(SnapResult {
+        success: true,
+        simulator_name: simulator.name,
+        simulator_udid: simulator.udid,
+        device_model: phone_model.map(|m| format!("{:?}", m)),
+        output_path: output_path.to_string_lossy().to_string(),
+        output_paths: display_paths(output_paths),
+        raw_path: Some(raw_path.to_string_lossy().to_string()),
+        dimensions: Dimensions {
+            width: config.width,
+            height: config.height,
+        },
+        warnings,
+    })
+}
+
+/// Open the deep link if any, wait for the UI to settle, then leave the screenshot to keep
+/// at `path`
+fn capture_settled(simulator: &Simulator, path: &Path, settle: &Settle) -> Result<()> {
+    if let Some(url) = &settle.open_url {
+        open_url(simulator, url)?;
+    }
+    let delay = Duration::from_millis(settle.ms);
+    if !settle.stable {
+        if settle.ms > 0 {
+            thread::sleep(delay);
+        }
+        return take_screenshot(simulator, path);
+    }
+
+    take_screenshot(simulator, path)?;
+    let mut previous = open_capture(path)?;
+    for attempt in 1..settle.attempts.max(2) {
+        thread::sleep(delay);
+        take_screenshot(simulator, path)?;
+        let current = open_capture(path)?;
+        if images_match(&previous, &current, settle.tolerance) {
+            info!(
+                "'{}' settled after {} capture(s)",
+                simulator.name,
+                attempt + 1
+            );
+            return Ok(());
+        }
+        previous = current;
+    }
+    bail!(
+        "simulator '{}' never settled after {} captures {}ms apart; raise --settle-attempts or --settle-tolerance",
+        simulator.name,
+        settle.attempts.max(2),
+        settle.ms
+    )
+}
+
+fn take_screenshot(simulator: &Simulator, path: &Path) -> Result<()> {
+    // Suppress simctl debug output
     let output = Command::new("xcrun")
         .args(["simctl", "io", &simulator.udid, "screenshot"])
-        .arg(&raw_path)
+        .arg(path)
         .output()
         .context("failed to execute xcrun simctl")?;
 
@@ -192,18 +673,51 @@ pub fn snap_framed(
             stderr.trim()
         );
     }
+    Ok(())
+}
 
-    // Load raw screenshot
-    let raw_img = image::open(&raw_path)
-        .with_context(|| format!("failed to open raw screenshot {}", raw_path.display()))?;
+fn open_capture(path: &Path) -> Result<RgbaImage> {
+    Ok(image::open(path)
+        .with_context(|| format!("failed to open screenshot {}", path.display()))?
+        .to_rgba8())
+}
+
+/// Whether two captures are the same screen: equal size and at most `tolerance` percent
+/// of pixels differing
+pub fn images_match(a: &RgbaImage, b: &RgbaImage, tolerance: f32) -> bool {
+    if a.dimensions() != b.dimensions() {
+        return false;
+    }
+    let total = a.pixels().len();
+    if total == 0 {
+        return true;
+    }
+    let differing = a.pixels().zip(b.pixels()).filter(|(p, q)| p != q).count();
+    differing as f32 / total as f32 * 100.0 <= tolerance.max(0.0)
+}
 
+/// Frame an already-captured screenshot into a finished image using the snap layout
+pub fn frame_screenshot(
+    raw_img: &DynamicImage,
+    raw_path: &Path,
+    output_name: &str,
+    config: &SnapConfig,
+    phone_model: Option<PhoneModel>,
+) -> Result<RgbaImage> {
     // Resolve overlay path from user config or model defaults.
     // When invoked from outside the repo, cwd-relative asset lookup can fail,
     // so we search common roots and then fall back to embedded overlays.
-    let resolved_overlay = config
-        .overlay
-        .clone()
-        .or_else(|| phone_model.and_then(resolve_model_overlay));
+    // A wider-than-tall screenshot gets a landscape device; model overlays are portrait only
+    let orientation = if raw_img.width() > raw_img.height() {
+        Orientation::Landscape
+    } else {
+        Orientation::Portrait
+    };
+    let resolved_overlay = config.overlay.clone().or_else(|| {
+        phone_model
+            .filter(|_| orientation == Orientation::Portrait)
+            .and_then(resolve_model_overlay)
+    });
 
     // Calculate phone dimensions based on output size.
     // If we have an overlay, preserve its aspect ratio so the frame is not distorted.
@@ -218,30 +732,54 @@ pub fn snap_framed(
             }
         });
     let (phone_width, phone_height, phone_x, phone_y) =
-        calculate_phone_layout(config, &raw_img, overlay_aspect);
+        calculate_phone_layout(config, raw_img, overlay_aspect)?;
 
     // Determine background colors (auto-extract or use provided)
     let background_colors = if config.auto_colors {
-        let dominant = extract_dominant_colors(&raw_img, 4);
+        let dominant = extract_dominant_colors(raw_img, 4, ClusterMethod::Histogram);
         generate_palette(&dominant, config.auto_strategy)
     } else {
         config.background_colors.clone()
     };
 
+    let phone = PhoneConfig {
+        model: phone_model.map(Into::into),
+        x: phone_x,
+        y: phone_y,
+        width: phone_width,
+        height: phone_height,
+        corner_radius: CornerRadius::Pixels(88),
+        screen_padding: Insets::default(),
+        frame_color: "#11151B".to_string(),
+        frame_border_width: 8,
+        shadow_offset_y: 18,
+        shadow_alpha: 74,
+        shadow_blur: 0,
+        shadow_spread: 0,
+        overlay: resolved_overlay,
+        overlay_tint: None,
+        home_indicator: None,
+        auto_rim_light: false,
+        min_frame_contrast: 1.6,
+        rotation_degrees: 0.0,
+        perspective: None,
+        status_bar: None,
+        glow_color: None,
+        glow_radius: 60,
+        reflection: false,
+        reflection_opacity: 0.25,
+        reflection_gap: 8,
+        orientation,
+    };
+
     // Build scene config for compose
     let scene = SceneConfig {
         id: "snap".to_string(),
-        capture: CaptureConfig::File {
-            path: raw_path.clone(),
-        },
         output: OutputConfig {
-            filename: output_path
-                .file_name()
-                .unwrap_or_default()
-                .to_string_lossy()
-                .to_string(),
+            filename: output_name.to_string(),
             width: config.width,
             height: config.height,
+            variants: Vec::new(),
         },
         background: BackgroundConfig {
             template: config.background_template,
@@ -249,54 +787,128 @@ pub fn snap_framed(
             colors: background_colors,
             auto_colors: false,
             auto_strategy: Default::default(),
+            vignette: None,
+            deterministic_layout: false,
+            image: None,
+            mesh: Default::default(),
+            stripes: Default::default(),
+            radial: Default::default(),
+            blur: Default::default(),
+            grain: 1.0,
+            angle: 90.0,
+            transparent: false,
+            linear_blend: false,
         },
-        phone: PhoneConfig {
-            model: phone_model,
-            x: phone_x,
-            y: phone_y,
-            width: phone_width,
-            height: phone_height,
-            corner_radius: 88,
-            screen_padding: Insets::default(),
-            frame_color: "#11151B".to_string(),
-            frame_border_width: 8,
-            shadow_offset_y: 18,
-            shadow_alpha: 74,
-            overlay: resolved_overlay,
-        },
+        phones: vec![PhoneInstance {
+            capture: CaptureConfig::File {
+                path: raw_path.to_path_buf(),
+                alpha: AlphaMode::Preserve,
+                alpha_color: "#FFFFFF".to_string(),
+            },
+            phone,
+        }],
+        main_phone: true,
         copy: build_copy_config(config),
+        post: None,
     };
 
     // Render background
-    let background = render_background(&scene.background, config.width, config.height)?;
+    let background =
+        render_scene_background(&scene.background, config.width, config.height, raw_img)?;
 
     // Compose final image
-    let final_img = compose_scene(&raw_img, &scene, background, Path::new("."))?;
+    let fonts = HashMap::new();
+    let devices = HashMap::new();
+    let screenshots = std::slice::from_ref(raw_img);
+    let mut final_img = match &config.export_layers {
+        Some(layers_dir) => {
+            let layers = compose_layers(
+                screenshots,
+                &scene,
+                background,
+                Path::new("."),
+                &fonts,
+                &devices,
+            )?;
+            layers.save(layers_dir)?;
+            layers.flatten()
+        }
+        None => compose_scene(
+            screenshots,
+            &scene,
+            background,
+            Path::new("."),
+            &fonts,
+            &devices,
+        )?,
+    };
 
-    // Save output
-    if let Some(parent) = output_path.parent() {
-        std::fs::create_dir_all(parent)
-            .with_context(|| format!("failed to create output directory {}", parent.display()))?;
+    if let Some(url) = &config.qr_url {
+        draw_qr_code(&mut final_img, url, config.qr_position, config.qr_size)?;
     }
 
-    final_img
-        .save(output_path)
-        .with_context(|| format!("failed to save output {}", output_path.display()))?;
+    Ok(final_img)
+}
 
-    Ok(SnapResult {
-        success: true,
-        simulator_name: simulator.name,
-        simulator_udid: simulator.udid,
-        device_model: phone_model.map(|m| format!("{:?}", m)),
-        output_path: output_path.to_string_lossy().to_string(),
-        raw_path: Some(raw_path.to_string_lossy().to_string()),
-        dimensions: Dimensions {
-            width: config.width,
-            height: config.height,
-        },
+/// Save one composed image to each path in the format implied by its extension.
+/// JPEG has no alpha channel, so those outputs are composited over white first.
+fn save_outputs(image: &RgbaImage, output_paths: &[PathBuf]) -> Result<()> {
+    for path in output_paths {
+        let format = ImageFormat::from_path(path)
+            .with_context(|| format!("unsupported output format for {}", path.display()))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("failed to create output directory {}", parent.display())
+            })?;
+        }
+
+        let result = match format {
+            ImageFormat::Jpeg => flatten_onto_white(image).save_with_format(path, format),
+            _ => image.save_with_format(path, format),
+        };
+        result.with_context(|| format!("failed to save output {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Drop alpha by compositing over white, so transparent areas (e.g. around a framed phone
+/// on a transparent background) don't turn black
+fn flatten_onto_white(image: &RgbaImage) -> RgbImage {
+    RgbImage::from_fn(image.width(), image.height(), |x, y| {
+        let pixel = image.get_pixel(x, y);
+        let alpha = u16::from(pixel[3]);
+        Rgb([0, 1, 2].map(|channel| {
+            ((u16::from(pixel[channel]) * alpha + 255 * (255 - alpha) + 127) / 255) as u8
+        }))
     })
 }
 
+/// Output paths for one simulator in `snap --batch`: `shot.png` becomes `shot_<udid>.png`
+pub fn batch_output_paths(output_paths: &[PathBuf], udid: &str) -> Vec<PathBuf> {
+    output_paths
+        .iter()
+        .map(|path| {
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let name = match path.extension() {
+                Some(ext) => format!("{}_{}.{}", stem, udid, ext.to_string_lossy()),
+                None => format!("{}_{}", stem, udid),
+            };
+            path.with_file_name(name)
+        })
+        .collect()
+}
+
+fn display_paths(paths: &[PathBuf]) -> Vec<String> {
+    paths
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect()
+}
+
 /// List all booted simulators (for agent discovery)
 #[derive(Debug, Serialize)]
 pub struct SimulatorInfo {
@@ -327,7 +939,7 @@ fn calculate_phone_layout(
     config: &SnapConfig,
     raw_img: &image::DynamicImage,
     overlay_aspect: Option<f32>,
-) -> (u32, u32, u32, u32) {
+) -> Result<(u32, u32, u32, u32)> {
     // Use explicit config if provided
     if let (Some(w), Some(h), Some(x), Some(y)) = (
         config.phone_width,
@@ -335,13 +947,20 @@ fn calculate_phone_layout(
         config.phone_x,
         config.phone_y,
     ) {
-        return (w, h, x, y);
+        return Ok((w, h, x, y));
+    }
+
+    if !(config.phone_scale > 0.0 && config.phone_scale <= 1.0) {
+        bail!(
+            "phone scale {} must be greater than 0 and at most 1",
+            config.phone_scale
+        );
     }
 
     let output_w = config.width;
     let output_h = config.height;
-    // Calculate phone size to fill ~73% of output width, maintaining aspect ratio
-    let target_phone_width = (output_w as f32 * 0.73) as u32;
+    // Size the phone to `phone_scale` of the output width, maintaining aspect ratio
+    let target_phone_width = (output_w as f32 * config.phone_scale) as u32;
     let aspect_ratio =
         overlay_aspect.unwrap_or_else(|| raw_img.height() as f32 / raw_img.width() as f32);
     let target_phone_height = (target_phone_width as f32 * aspect_ratio) as u32;
@@ -349,36 +968,58 @@ fn calculate_phone_layout(
     // Center horizontally
     let phone_x = (output_w - target_phone_width) / 2;
 
-    // Position in lower portion of canvas (leave room for headline)
-    let phone_y = if config.headline.is_some() {
-        // Leave top 20% for copy so composition feels less top-heavy.
-        (output_h as f32 * 0.20) as u32
-    } else {
-        // Center vertically with slight offset down
-        (output_h - target_phone_height) / 2 + (output_h as f32 * 0.05) as u32
+    // With a headline above, the phone hangs from 15% down so the copy has room; with one
+    // below, its bottom edge sits at 75% so the default bias leaves the copy the same 20%
+    // band. Otherwise it is centered. Either way `phone_bias_y` then shifts it down.
+    let anchor_y = match (&config.headline, config.text_position) {
+        (Some(_), TextPosition::AbovePhone | TextPosition::Top) => output_h as f32 * 0.15,
+        (Some(_), TextPosition::BelowPhone | TextPosition::Bottom) => {
+            output_h as f32 * 0.75 - target_phone_height as f32
+        }
+        _ => (output_h as f32 - target_phone_height as f32) / 2.0,
     };
+    let phone_y = (anchor_y + output_h as f32 * config.phone_bias_y).max(0.0) as u32;
 
-    (
+    Ok((
         config.phone_width.unwrap_or(target_phone_width),
         config.phone_height.unwrap_or(target_phone_height),
         config.phone_x.unwrap_or(phone_x),
         config.phone_y.unwrap_or(phone_y),
-    )
+    ))
 }
 
 fn build_copy_config(config: &SnapConfig) -> Option<CopyConfig> {
     config.headline.as_ref().map(|headline| CopyConfig {
-        headline: headline.clone(),
+        headline: Headline::Single(headline.clone()),
         subheadline: config.subheadline.clone().unwrap_or_default(),
+        bullets: Vec::new(),
         color: "#F4F8FF".to_string(),
-        position: crate::config::TextPosition::AbovePhone,
+        auto_contrast: false,
+        color_gradient: None,
+        gradient_direction: crate::config::GradientDirection::Vertical,
+        position: config.text_position,
         y_offset: 0,
-        headline_size: 120.0,
-        subheadline_size: 56.0,
+        headline_size: config.headline_size,
+        subheadline_size: config.subheadline_size,
         headline_weight: crate::config::FontWeight::Bold,
         subheadline_weight: crate::config::FontWeight::Regular,
+        font_path: None,
+        fonts: HashMap::new(),
         line_gap: 24,
+        line_height: 1.2,
         max_width: None,
+        align: crate::config::TextAlign::Center,
+        transform: Default::default(),
+        padding: 60,
+        stroke_color: None,
+        stroke_width: 3,
+        shadow_color: None,
+        shadow_offset_x: 0,
+        shadow_offset_y: 6,
+        shadow_blur: 8,
+        text_background_color: None,
+        text_background_padding: 24,
+        logo: None,
     })
 }
 
@@ -403,17 +1044,17 @@ fn overlay_search_roots() -> Vec<PathBuf> {
         roots.push(cwd);
     }
 
-    if let Ok(exe) = std::env::current_exe() {
-        if let Some(exe_dir) = exe.parent() {
-            roots.push(exe_dir.to_path_buf());
-            for ancestor in exe_dir.ancestors().take(6) {
-                roots.push(ancestor.to_path_buf());
-            }
+    if let Ok(exe) = std::env::current_exe()
+        && let Some(exe_dir) = exe.parent()
+    {
+        roots.push(exe_dir.to_path_buf());
+        for ancestor in exe_dir.ancestors().take(6) {
+            roots.push(ancestor.to_path_buf());
+        }
 
-            // Homebrew-style installs often place assets in <prefix>/share/screenforge.
-            if let Some(prefix) = exe_dir.parent() {
-                roots.push(prefix.join("share").join("screenforge"));
-            }
+        // Homebrew-style installs often place assets in <prefix>/share/screenforge.
+        if let Some(prefix) = exe_dir.parent() {
+            roots.push(prefix.join("share").join("screenforge"));
         }
     }
 
@@ -433,19 +1074,214 @@ fn overlay_search_roots() -> Vec<PathBuf> {
 
 fn materialize_embedded_overlay(model: PhoneModel) -> Result<PathBuf> {
     let slug = crate::frames::model_slug(model);
+    let Some(bytes) = embedded_overlay_bytes(model) else {
+        bail!("no embedded overlay for {}", slug);
+    };
     let dest = std::env::temp_dir().join(format!("screenforge_overlay_{}.png", slug));
 
     if !dest.exists() {
-        fs::write(&dest, embedded_overlay_bytes(model))
+        fs::write(&dest, bytes)
             .with_context(|| format!("failed writing embedded overlay {}", dest.display()))?;
     }
 
     Ok(dest)
 }
 
-fn embedded_overlay_bytes(model: PhoneModel) -> &'static [u8] {
+/// iPads ship without an embedded frame and are drawn procedurally
+fn embedded_overlay_bytes(model: PhoneModel) -> Option<&'static [u8]> {
     match model {
-        PhoneModel::Iphone17Pro => FRAME_IPHONE_17_PRO,
-        PhoneModel::Iphone17ProMax => FRAME_IPHONE_17_PRO_MAX,
+        PhoneModel::Iphone17Pro => Some(FRAME_IPHONE_17_PRO),
+        PhoneModel::Iphone17ProMax => Some(FRAME_IPHONE_17_PRO_MAX),
+        PhoneModel::IpadPro11 | PhoneModel::IpadPro13 => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_inline_scene_specs() {
+        let scene: InlineScene =
+            "sim=iPhone 17 Pro; output=out/a.png; headline=Fast; seed=7; background=stripes; colors=#000000,#FFFFFF; model=iphone_17_pro_max"
+                .parse()
+                .expect("scene");
+        assert_eq!(scene.simulator, "iPhone 17 Pro");
+        assert_eq!(scene.output, PathBuf::from("out/a.png"));
+        assert_eq!(scene.seed, Some(7));
+        assert!(matches!(
+            scene.background,
+            Some(BackgroundTemplate::Stripes)
+        ));
+        assert_eq!(scene.model, Some(PhoneModel::Iphone17ProMax));
+
+        let config = scene.apply(&SnapConfig::default());
+        assert_eq!(config.headline.as_deref(), Some("Fast"));
+        assert_eq!(config.background_colors, vec!["#000000", "#FFFFFF"]);
+
+        assert!("output=a.png".parse::<InlineScene>().is_err());
+        assert!("sim=x;output=a.png;size=3".parse::<InlineScene>().is_err());
+    }
+
+    #[test]
+    fn save_outputs_encodes_each_path_by_extension() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let paths = [
+            temp.path().join("shot.jpg"),
+            temp.path().join("nested/shot.png"),
+        ];
+        let image = RgbaImage::from_pixel(8, 4, image::Rgba([10, 20, 30, 255]));
+        save_outputs(&image, &paths).expect("save");
+
+        let format = |path: &Path| {
+            image::ImageReader::open(path)
+                .expect("open")
+                .with_guessed_format()
+                .expect("guess")
+                .format()
+        };
+        assert_eq!(format(&paths[0]), Some(ImageFormat::Jpeg));
+        assert_eq!(format(&paths[1]), Some(ImageFormat::Png));
+        assert_eq!(image::open(&paths[0]).expect("decode").width(), 8);
+    }
+
+    #[test]
+    fn jpeg_outputs_composite_transparency_over_white() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("shot.jpg");
+        let mut image = RgbaImage::from_pixel(16, 16, image::Rgba([0, 0, 0, 0]));
+        image.put_pixel(0, 0, image::Rgba([0, 0, 0, 128]));
+        let half_black = *flatten_onto_white(&image).get_pixel(0, 0);
+        assert_eq!(half_black, Rgb([127, 127, 127]));
+        save_outputs(&image, std::slice::from_ref(&path)).expect("save");
+
+        let decoded = image::open(&path).expect("decode").to_rgb8();
+        let corner = decoded.get_pixel(15, 15);
+        assert!(corner.0.iter().all(|&channel| channel >= 250));
+    }
+
+    #[test]
+    fn crop_must_fit_inside_the_screenshot() {
+        let crop: CropRect = "2, 3, 4,5".parse().expect("crop");
+        assert_eq!(
+            crop,
+            CropRect {
+                x: 2,
+                y: 3,
+                width: 4,
+                height: 5
+            }
+        );
+        assert!("1,2,3".parse::<CropRect>().is_err());
+        assert!("1,2,3,-4".parse::<CropRect>().is_err());
+
+        let image = DynamicImage::ImageRgba8(RgbaImage::new(10, 8));
+        let cropped = crop.apply(&image).expect("fits");
+        assert_eq!((cropped.width(), cropped.height()), (4, 5));
+
+        let err = "8,0,4,4"
+            .parse::<CropRect>()
+            .expect("crop")
+            .apply(&image)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "crop 4x4+8+0 is outside the 10x8 screenshot"
+        );
+    }
+
+    #[test]
+    fn phone_scale_and_bias_drive_the_auto_layout() {
+        let raw = DynamicImage::ImageRgba8(RgbaImage::new(100, 200));
+        let mut config = SnapConfig {
+            width: 1000,
+            height: 2000,
+            ..SnapConfig::default()
+        };
+        assert_eq!(
+            calculate_phone_layout(&config, &raw, None).expect("layout"),
+            (730, 1460, 135, 370)
+        );
+
+        config.phone_scale = 0.5;
+        config.phone_bias_y = -0.1;
+        config.headline = Some("Hi".to_string());
+        assert_eq!(
+            calculate_phone_layout(&config, &raw, None).expect("layout"),
+            (500, 1000, 250, 100)
+        );
+
+        config.text_position = TextPosition::BelowPhone;
+        assert_eq!(
+            calculate_phone_layout(&config, &raw, None).expect("layout"),
+            (500, 1000, 250, 300)
+        );
+
+        config.phone_scale = 1.5;
+        assert!(calculate_phone_layout(&config, &raw, None).is_err());
+    }
+
+    #[test]
+    fn images_match_within_tolerance() {
+        let a = RgbaImage::from_pixel(10, 10, image::Rgba([0, 0, 0, 255]));
+        let mut b = a.clone();
+        assert!(images_match(&a, &b, 0.0));
+
+        b.put_pixel(3, 3, image::Rgba([255, 0, 0, 255]));
+        assert!(!images_match(&a, &b, 0.0));
+        assert!(images_match(&a, &b, 1.0));
+        assert!(!images_match(&a, &RgbaImage::new(10, 11), 100.0));
+    }
+
+    #[test]
+    fn preset_keeps_defaults_for_missing_keys() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let preset = temp.path().join("snap.yaml");
+        fs::write(
+            &preset,
+            "headline: Ship faster\nbackground_template: stripes\nauto_strategy: triadic\nexport_layers: layers\n",
+        )
+        .expect("write preset");
+
+        let config = SnapConfig::from_path(&preset).expect("preset");
+        assert_eq!(config.headline.as_deref(), Some("Ship faster"));
+        assert!(matches!(
+            config.background_template,
+            BackgroundTemplate::Stripes
+        ));
+        assert_eq!(config.width, 1284);
+        assert_eq!(config.background_seed, 42);
+        assert_eq!(config.export_layers, Some(temp.path().join("layers")));
+
+        fs::write(&preset, "hieght: 100\n").expect("write preset");
+        assert!(SnapConfig::from_path(&preset).is_err());
+    }
+
+    #[test]
+    fn recordings_get_a_video_extension() {
+        assert_eq!(
+            video_output_path(Path::new("snap_output.png")),
+            PathBuf::from("snap_output.mp4")
+        );
+        assert_eq!(
+            video_output_path(Path::new("out/preview.MOV")),
+            PathBuf::from("out/preview.MOV")
+        );
+        assert_eq!(
+            video_output_path(Path::new("preview")),
+            PathBuf::from("preview.mp4")
+        );
+    }
+
+    #[test]
+    fn batch_paths_append_the_udid_to_each_stem() {
+        let outputs = vec![PathBuf::from("out/shot.png"), PathBuf::from("shot.jpg")];
+        assert_eq!(
+            batch_output_paths(&outputs, "ABC-123"),
+            vec![
+                PathBuf::from("out/shot_ABC-123.png"),
+                PathBuf::from("shot_ABC-123.jpg"),
+            ]
+        );
     }
 }
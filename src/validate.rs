@@ -0,0 +1,241 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::color::parse_hex_rgba;
+use crate::config::{CaptureConfig, Config, CopyConfig, PhoneConfig, SceneConfig};
+
+/// One problem found by `validate`; `location` is a scene id or a top-level key path
+#[derive(Debug, Serialize)]
+pub struct ValidationIssue {
+    pub location: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidationSummary {
+    pub scene_count: usize,
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationSummary {
+    pub fn failed(&self) -> bool {
+        !self.issues.is_empty()
+    }
+}
+
+/// Load a config and check it for problems `run` would only hit mid-way: duplicate scene
+/// ids, missing capture files, zero-size phones or outputs and unparseable colors. Nothing
+/// is captured or written. Errors from loading the config itself are returned as-is.
+pub fn validate_config(config_path: &Path) -> Result<ValidationSummary> {
+    let config = Config::from_path(config_path)?;
+    let config_dir = config_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let mut issues = Vec::new();
+    let mut push = |location: &str, message: String| {
+        issues.push(ValidationIssue {
+            location: location.to_string(),
+            message,
+        })
+    };
+
+    let mut slugs: Vec<_> = config.devices.keys().collect();
+    slugs.sort();
+    for slug in slugs {
+        let location = format!("devices.{}", slug);
+        if let Some(message) = color_issue("frame_color", &config.devices[slug].frame_color) {
+            push(&location, message);
+        }
+    }
+
+    let mut seen_ids = HashSet::new();
+    for scene in &config.scenes {
+        if !seen_ids.insert(scene.id.as_str()) {
+            push(&scene.id, "duplicate scene id".to_string());
+        }
+        for message in scene_issues(scene, &config_dir) {
+            push(&scene.id, message);
+        }
+    }
+
+    Ok(ValidationSummary {
+        scene_count: config.scenes.len(),
+        issues,
+    })
+}
+
+fn scene_issues(scene: &SceneConfig, config_dir: &Path) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    let output = &scene.output;
+    if output.width == 0 || output.height == 0 {
+        issues.push(format!(
+            "output size {}x{} has a zero dimension",
+            output.width, output.height
+        ));
+    }
+    for variant in &output.variants {
+        if variant.width == 0 || variant.height == 0 {
+            issues.push(format!(
+                "output variant '{}' size {}x{} has a zero dimension",
+                variant.name, variant.width, variant.height
+            ));
+        }
+    }
+
+    for (index, (capture, phone)) in scene.devices().enumerate() {
+        // Only `phones` entries get a prefix, so single-phone scenes read naturally
+        let device = match scene.device_label(index).as_str() {
+            "phone" => String::new(),
+            label => format!("{}: ", label),
+        };
+        issues.extend(
+            capture_issues(capture, config_dir)
+                .into_iter()
+                .chain(phone_issues(phone))
+                .map(|message| format!("{}{}", device, message)),
+        );
+    }
+
+    for (index, color) in scene.background.colors.iter().enumerate() {
+        issues.extend(color_issue(&format!("background.colors[{}]", index), color));
+    }
+    if let Some(vignette) = &scene.background.vignette {
+        issues.extend(color_issue("background.vignette.color", &vignette.color));
+    }
+    if let Some(copy) = &scene.copy {
+        issues.extend(copy_issues(copy));
+    }
+    if let Some([shadow, highlight]) = scene.post.as_ref().and_then(|post| post.duotone.as_ref()) {
+        issues.extend(color_issue("post.duotone[0]", shadow));
+        issues.extend(color_issue("post.duotone[1]", highlight));
+    }
+    issues
+}
+
+fn capture_issues(capture: &CaptureConfig, config_dir: &Path) -> Vec<String> {
+    let (path, alpha_color) = match capture {
+        CaptureConfig::File {
+            path, alpha_color, ..
+        }
+        | CaptureConfig::Region {
+            path, alpha_color, ..
+        } => (path, alpha_color),
+        _ => return Vec::new(),
+    };
+    let mut issues = Vec::new();
+    if !config_dir.join(path).is_file() {
+        issues.push(format!("capture file {} does not exist", path.display()));
+    }
+    if let CaptureConfig::Region { width, height, .. } = capture
+        && (*width == 0 || *height == 0)
+    {
+        issues.push(format!(
+            "capture region {}x{} has a zero dimension",
+            width, height
+        ));
+    }
+    issues.extend(color_issue("capture.alpha_color", alpha_color));
+    issues
+}
+
+fn phone_issues(phone: &PhoneConfig) -> Vec<String> {
+    let mut issues = Vec::new();
+    if phone.width == 0 || phone.height == 0 {
+        issues.push(format!(
+            "phone size {}x{} has a zero dimension",
+            phone.width, phone.height
+        ));
+    }
+    issues.extend(color_issue("phone.frame_color", &phone.frame_color));
+    if let Some(color) = &phone.overlay_tint {
+        issues.extend(color_issue("phone.overlay_tint", color));
+    }
+    if let Some(color) = &phone.glow_color {
+        issues.extend(color_issue("phone.glow_color", color));
+    }
+    if let Some(color) = phone.status_bar.as_ref().and_then(|bar| bar.color.as_ref()) {
+        issues.extend(color_issue("phone.status_bar.color", color));
+    }
+    issues
+}
+
+fn copy_issues(copy: &CopyConfig) -> Vec<String> {
+    let mut colors = vec![("copy.color", &copy.color)];
+    if let Some([from, to]) = &copy.color_gradient {
+        colors.push(("copy.color_gradient[0]", from));
+        colors.push(("copy.color_gradient[1]", to));
+    }
+    for (field, color) in [
+        ("copy.stroke_color", &copy.stroke_color),
+        ("copy.shadow_color", &copy.shadow_color),
+        ("copy.text_background_color", &copy.text_background_color),
+    ] {
+        if let Some(color) = color {
+            colors.push((field, color));
+        }
+    }
+    colors
+        .into_iter()
+        .filter_map(|(field, color)| color_issue(field, color))
+        .collect()
+}
+
+fn color_issue(field: &str, color: &str) -> Option<String> {
+    parse_hex_rgba(color)
+        .err()
+        .map(|err| format!("{}: {}", field, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    #[test]
+    fn reports_every_problem_at_once() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        fs::write(temp.path().join("home.png"), b"").expect("write capture");
+        let config_path = temp.path().join("screenforge.yaml");
+        let yaml = r##"
+scenes:
+  - id: home
+    capture: { adapter: file, path: ./home.png }
+    output: { filename: home.png, width: 100, height: 200 }
+    background: { colors: ["#112233", "#12345"] }
+    phone: { x: 0, y: 0, width: 50, height: 100 }
+  - id: home
+    capture: { adapter: file, path: ./missing.png }
+    output: { filename: other.png, width: 0, height: 200 }
+    background: {}
+    phone: { x: 0, y: 0, width: 50, height: 0 }
+    copy: { headline: Hi, color: "not-a-color" }
+"##;
+        fs::write(&config_path, yaml).expect("write config");
+
+        let summary = validate_config(&config_path).expect("config loads");
+        let messages: Vec<_> = summary
+            .issues
+            .iter()
+            .map(|issue| format!("{}: {}", issue.location, issue.message))
+            .collect();
+        assert_eq!(summary.scene_count, 2);
+        assert_eq!(messages.len(), 6, "{messages:#?}");
+        assert!(messages[0].starts_with("home: background.colors[1]:"));
+        assert_eq!(messages[1], "home: duplicate scene id");
+        assert_eq!(messages[2], "home: output size 0x200 has a zero dimension");
+        assert_eq!(
+            messages[3],
+            "home: capture file ./missing.png does not exist"
+        );
+        assert_eq!(messages[4], "home: phone size 50x0 has a zero dimension");
+        assert!(messages[5].starts_with("home: copy.color:"));
+        assert!(summary.failed());
+    }
+}
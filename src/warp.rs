@@ -0,0 +1,246 @@
+use image::{Rgba, RgbaImage};
+
+/// 3x3 projective transform mapping source points to destination points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Homography([f64; 9]);
+
+impl Homography {
+    pub const IDENTITY: Self = Self([1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+
+    /// Clockwise rotation by `degrees` about (`cx`, `cy`) in image coordinates (y down)
+    pub fn rotation(cx: f64, cy: f64, degrees: f64) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self([
+            cos,
+            -sin,
+            cx - cx * cos + cy * sin,
+            sin,
+            cos,
+            cy - cx * sin - cy * cos,
+            0.0,
+            0.0,
+            1.0,
+        ])
+    }
+
+    /// Transform taking the corners of `src` onto the corners of `dst`, both given as
+    /// top-left, top-right, bottom-right, bottom-left. `None` for degenerate quads.
+    pub fn from_quads(src: [(f64, f64); 4], dst: [(f64, f64); 4]) -> Option<Self> {
+        // Standard DLT setup with h33 fixed to 1: two equations per corner pair
+        let mut rows = [[0.0f64; 9]; 8];
+        for (i, ((x, y), (u, v))) in src.into_iter().zip(dst).enumerate() {
+            rows[i * 2] = [x, y, 1.0, 0.0, 0.0, 0.0, -u * x, -u * y, u];
+            rows[i * 2 + 1] = [0.0, 0.0, 0.0, x, y, 1.0, -v * x, -v * y, v];
+        }
+        let h = solve(rows)?;
+        Some(Self([h[0], h[1], h[2], h[3], h[4], h[5], h[6], h[7], 1.0]))
+    }
+
+    /// `self` applied after `first`
+    pub fn then_after(&self, first: &Self) -> Self {
+        let (a, b) = (&self.0, &first.0);
+        let mut out = [0.0; 9];
+        for row in 0..3 {
+            for col in 0..3 {
+                out[row * 3 + col] = (0..3).map(|k| a[row * 3 + k] * b[k * 3 + col]).sum();
+            }
+        }
+        Self(out)
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let m = &self.0;
+        let cofactors = [
+            m[4] * m[8] - m[5] * m[7],
+            m[2] * m[7] - m[1] * m[8],
+            m[1] * m[5] - m[2] * m[4],
+            m[5] * m[6] - m[3] * m[8],
+            m[0] * m[8] - m[2] * m[6],
+            m[2] * m[3] - m[0] * m[5],
+            m[3] * m[7] - m[4] * m[6],
+            m[1] * m[6] - m[0] * m[7],
+            m[0] * m[4] - m[1] * m[3],
+        ];
+        let det = m[0] * cofactors[0] + m[1] * cofactors[3] + m[2] * cofactors[6];
+        if det.abs() < 1e-12 {
+            return None;
+        }
+        Some(Self(cofactors.map(|c| c / det)))
+    }
+
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        let m = &self.0;
+        let w = m[6] * x + m[7] * y + m[8];
+        (
+            (m[0] * x + m[1] * y + m[2]) / w,
+            (m[3] * x + m[4] * y + m[5]) / w,
+        )
+    }
+}
+
+/// Gauss-Jordan elimination on an 8x8 augmented system
+fn solve(mut rows: [[f64; 9]; 8]) -> Option<[f64; 8]> {
+    for col in 0..8 {
+        let pivot = (col..8).max_by(|&a, &b| rows[a][col].abs().total_cmp(&rows[b][col].abs()))?;
+        if rows[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        rows.swap(col, pivot);
+        let lead = rows[col][col];
+        for value in rows[col].iter_mut() {
+            *value /= lead;
+        }
+        let pivot_row = rows[col];
+        for (index, row) in rows.iter_mut().enumerate() {
+            let factor = row[col];
+            if index == col || factor == 0.0 {
+                continue;
+            }
+            for (value, pivot) in row.iter_mut().zip(pivot_row).skip(col) {
+                *value -= factor * pivot;
+            }
+        }
+    }
+    Some(rows.map(|row| row[8]))
+}
+
+/// Resample `layer` through `transform` (source -> destination) with bilinear sampling.
+/// Only the box the warped content can reach is resampled; the canvas size is kept.
+pub fn warp_layer(layer: &RgbaImage, transform: &Homography) -> RgbaImage {
+    let (width, height) = layer.dimensions();
+    let mut out = RgbaImage::new(width, height);
+    let Some((min_x, min_y, max_x, max_y)) = opaque_bounds(layer) else {
+        return out;
+    };
+    let Some(inverse) = transform.inverse() else {
+        return out;
+    };
+
+    let (x0, y0, x1, y1) = (
+        min_x as f64,
+        min_y as f64,
+        (max_x + 1) as f64,
+        (max_y + 1) as f64,
+    );
+    let (mut left, mut top, mut right, mut bottom) = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+    for (x, y) in [(x0, y0), (x1, y0), (x1, y1), (x0, y1)] {
+        let (dx, dy) = transform.apply(x, y);
+        left = left.min(dx);
+        top = top.min(dy);
+        right = right.max(dx);
+        bottom = bottom.max(dy);
+    }
+    let x_range = (left.floor().max(0.0) as u32)..(right.ceil().min(width as f64) as u32);
+    let y_range = (top.floor().max(0.0) as u32)..(bottom.ceil().min(height as f64) as u32);
+
+    for y in y_range {
+        for x in x_range.clone() {
+            // Map the destination pixel center back into the source
+            let (sx, sy) = inverse.apply(x as f64 + 0.5, y as f64 + 0.5);
+            let pixel = sample_bilinear(layer, (sx - 0.5) as f32, (sy - 0.5) as f32);
+            if pixel[3] > 0 {
+                out.put_pixel(x, y, pixel);
+            }
+        }
+    }
+    out
+}
+
+/// Inclusive bounds of the non-transparent pixels, if any
+fn opaque_bounds(image: &RgbaImage) -> Option<(u32, u32, u32, u32)> {
+    let mut bounds: Option<(u32, u32, u32, u32)> = None;
+    for (x, y, pixel) in image.enumerate_pixels() {
+        if pixel[3] == 0 {
+            continue;
+        }
+        bounds = Some(match bounds {
+            None => (x, y, x, y),
+            Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+        });
+    }
+    bounds
+}
+
+/// Bilinear sample in premultiplied space; samples outside the image are transparent
+fn sample_bilinear(image: &RgbaImage, x: f32, y: f32) -> Rgba<u8> {
+    let x0 = x.floor();
+    let y0 = y.floor();
+    let fx = x - x0;
+    let fy = y - y0;
+    let mut acc = [0.0f32; 4];
+    for (ox, oy, weight) in [
+        (0, 0, (1.0 - fx) * (1.0 - fy)),
+        (1, 0, fx * (1.0 - fy)),
+        (0, 1, (1.0 - fx) * fy),
+        (1, 1, fx * fy),
+    ] {
+        let px = x0 as i64 + ox;
+        let py = y0 as i64 + oy;
+        if weight <= 0.0
+            || px < 0
+            || py < 0
+            || px >= image.width() as i64
+            || py >= image.height() as i64
+        {
+            continue;
+        }
+        let p = image.get_pixel(px as u32, py as u32);
+        let a = p[3] as f32 * weight;
+        acc[0] += p[0] as f32 * a;
+        acc[1] += p[1] as f32 * a;
+        acc[2] += p[2] as f32 * a;
+        acc[3] += a;
+    }
+    if acc[3] <= 0.0 {
+        return Rgba([0, 0, 0, 0]);
+    }
+    let channel = |v: f32| (v / acc[3]).round().clamp(0.0, 255.0) as u8;
+    Rgba([
+        channel(acc[0]),
+        channel(acc[1]),
+        channel(acc[2]),
+        acc[3].round().clamp(0.0, 255.0) as u8,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: (f64, f64), expected: (f64, f64)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 1e-6 && (actual.1 - expected.1).abs() < 1e-6,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn quad_homography_maps_every_corner() {
+        let src = [(0.0, 0.0), (100.0, 0.0), (100.0, 200.0), (0.0, 200.0)];
+        let dst = [(10.0, 0.0), (90.0, 30.0), (90.0, 170.0), (10.0, 200.0)];
+        let h = Homography::from_quads(src, dst).unwrap();
+        for (s, d) in src.into_iter().zip(dst) {
+            assert_near(h.apply(s.0, s.1), d);
+        }
+        let back = h.inverse().unwrap();
+        assert_near(back.apply(90.0, 30.0), (100.0, 0.0));
+    }
+
+    #[test]
+    fn rotation_turns_clockwise_about_the_center() {
+        let h = Homography::rotation(50.0, 50.0, 90.0);
+        assert_near(h.apply(100.0, 50.0), (50.0, 100.0));
+        let both = h.then_after(&Homography::rotation(50.0, 50.0, -90.0));
+        assert_near(both.apply(12.0, 34.0), (12.0, 34.0));
+    }
+
+    #[test]
+    fn identity_warp_keeps_pixels() {
+        let mut layer = RgbaImage::new(8, 8);
+        layer.put_pixel(3, 4, Rgba([200, 100, 50, 255]));
+        let out = warp_layer(&layer, &Homography::IDENTITY);
+        assert_eq!(out, layer);
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use log::{debug, warn};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+use crate::config::Config;
+use crate::pipeline::scene_inputs;
+
+// Editors often save in several steps (write, rename, chmod); wait for them to go quiet
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Block forever, calling `on_change` whenever the config or a file it references is edited.
+/// The watched set is rebuilt after every change so newly referenced assets are picked up.
+pub fn watch(config_path: &Path, mut on_change: impl FnMut()) -> Result<()> {
+    loop {
+        let targets = watch_targets(config_path);
+        let (tx, rx) = mpsc::channel();
+        let mut watcher =
+            notify::recommended_watcher(tx).context("failed to start file watcher")?;
+
+        // Watch directories rather than files so atomic save-by-rename is still seen
+        let mut dirs = HashSet::new();
+        for target in &targets {
+            if let Some(dir) = target.parent()
+                && dirs.insert(dir.to_path_buf())
+            {
+                watcher
+                    .watch(dir, RecursiveMode::NonRecursive)
+                    .with_context(|| format!("failed to watch {}", dir.display()))?;
+            }
+        }
+        debug!(
+            "watching {} file(s) in {} dir(s)",
+            targets.len(),
+            dirs.len()
+        );
+
+        wait_for_change(&rx, &targets)?;
+        while rx.recv_timeout(DEBOUNCE).is_ok() {}
+        drop(watcher);
+        on_change();
+    }
+}
+
+fn wait_for_change(rx: &Receiver<notify::Result<Event>>, targets: &HashSet<PathBuf>) -> Result<()> {
+    loop {
+        match rx.recv().context("file watcher stopped")? {
+            Ok(event) => {
+                let edit = matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                );
+                if edit && event.paths.iter().any(|path| targets.contains(path)) {
+                    return Ok(());
+                }
+            }
+            Err(err) => warn!("file watcher error: {}", err),
+        }
+    }
+}
+
+/// The config file plus every input it references, normalized to match watcher event paths.
+/// A config that fails to load only watches itself until it is fixed.
+fn watch_targets(config_path: &Path) -> HashSet<PathBuf> {
+    let config_dir = config_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let mut inputs = vec![config_path.to_path_buf()];
+    if let Ok(config) = Config::from_path(config_path) {
+        inputs.extend(config_inputs(&config, &config_dir));
+    }
+    inputs.iter().filter_map(|path| normalize(path)).collect()
+}
+
+fn config_inputs(config: &Config, config_dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = config
+        .fonts
+        .values()
+        .map(|path| resolve_path(config_dir, path))
+        .collect();
+    for scene in &config.scenes {
+        paths.extend(scene_inputs(scene, config_dir));
+    }
+    paths
+}
+
+/// Canonical parent joined with the file name, which is how events for a watched
+/// directory report their paths
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    Some(parent.canonicalize().ok()?.join(path.file_name()?))
+}
+
+fn resolve_path(config_dir: &Path, path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        config_dir.join(path)
+    }
+}